@@ -11,9 +11,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // - Run `cargo build` to regenerate the API.
     // - Comment the following lines.
     // - Commit the changes.
+    //
+    // The metrics and trace messages are vendored in
+    // `crates/weaver_live_check/src/otlp_protobuf/proto`, shared with the protobuf
+    // decoding of the live check, and re-exported by the generated services.
 
     // tonic_prost_build::configure()
     //     .out_dir("src/registry/otlp/grpc_stubs")
+    //     .extern_path(".opentelemetry.proto.common", "::weaver_live_check::otlp_protobuf::proto::common")
+    //     .extern_path(".opentelemetry.proto.resource", "::weaver_live_check::otlp_protobuf::proto::resource")
+    //     .extern_path(".opentelemetry.proto.metrics", "::weaver_live_check::otlp_protobuf::proto::metrics")
+    //     .extern_path(".opentelemetry.proto.trace", "::weaver_live_check::otlp_protobuf::proto::trace")
     //     .compile_protos(
     //         &[
     //             "src/registry/otlp/proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
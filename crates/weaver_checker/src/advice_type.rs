@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The advice types of the builtin live check advisors.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Declares the builtin advice types once: the variants of [`BuiltinAdviceType`],
+/// their advice type strings and the `*_ADVICE_TYPE` string constants.
+macro_rules! builtin_advice_types {
+    ($($(#[doc = $doc:literal])* $variant:ident, $constant:ident => $advice_type:literal;)*) => {
+        /// The type of an advice given by the builtin advisors, any other type e.g.
+        /// from a Rego policy is kept as `Custom`. Serialized as the plain advice type
+        /// string e.g. "deprecated".
+        #[derive(Debug, Clone)]
+        pub enum BuiltinAdviceType {
+            $($(#[doc = $doc])* $variant,)*
+            /// Any other advice type, e.g. emitted by a Rego policy
+            Custom(String),
+        }
+
+        impl BuiltinAdviceType {
+            /// All the builtin advice types, `Custom` excepted
+            pub const ALL: &'static [BuiltinAdviceType] = &[$(BuiltinAdviceType::$variant,)*];
+
+            /// Returns the advice type string e.g. "type_mismatch".
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(BuiltinAdviceType::$variant => $advice_type,)*
+                    BuiltinAdviceType::Custom(advice_type) => advice_type,
+                }
+            }
+        }
+
+        impl From<&str> for BuiltinAdviceType {
+            fn from(advice_type: &str) -> Self {
+                match advice_type {
+                    $($advice_type => BuiltinAdviceType::$variant,)*
+                    _ => BuiltinAdviceType::Custom(advice_type.to_owned()),
+                }
+            }
+        }
+
+        $(
+            #[doc = concat!("The `", $advice_type, "` advice type")]
+            pub const $constant: &str = $advice_type;
+        )*
+    };
+}
+
+builtin_advice_types! {
+    /// Missing Attribute
+    MissingAttribute, MISSING_ATTRIBUTE_ADVICE_TYPE => "missing_attribute";
+    /// Template Attribute
+    TemplateAttribute, TEMPLATE_ATTRIBUTE_ADVICE_TYPE => "template_attribute";
+    /// Template Should Aggregate
+    TemplateShouldAggregate, TEMPLATE_SHOULD_AGGREGATE_ADVICE_TYPE => "template_should_aggregate";
+    /// Missing Metric
+    MissingMetric, MISSING_METRIC_ADVICE_TYPE => "missing_metric";
+    /// Missing identifying dimension
+    MissingIdentifyingDimension, MISSING_IDENTIFYING_DIMENSION_ADVICE_TYPE => "missing_identifying_dimension";
+    /// Deprecated
+    Deprecated, DEPRECATED_ADVICE_TYPE => "deprecated";
+    /// Type Mismatch
+    TypeMismatch, TYPE_MISMATCH_ADVICE_TYPE => "type_mismatch";
+    /// Unstable
+    NotStable, NOT_STABLE_ADVICE_TYPE => "not_stable";
+    /// Unit mismatch
+    UnitMismatch, UNIT_MISMATCH_ADVICE_TYPE => "unit_mismatch";
+    /// Instrument mismatch
+    UnexpectedInstrument, UNEXPECTED_INSTRUMENT_ADVICE_TYPE => "unexpected_instrument";
+    /// Negative counter value
+    CounterNegativeValue, COUNTER_NEGATIVE_VALUE_ADVICE_TYPE => "counter_negative_value";
+    /// Temporality mismatch
+    TemporalityMismatch, TEMPORALITY_MISMATCH_ADVICE_TYPE => "temporality_mismatch";
+    /// Undefined enum variant
+    UndefinedEnumVariant, UNDEFINED_ENUM_VARIANT_ADVICE_TYPE => "undefined_enum_variant";
+    /// Custom value of an open enum
+    CustomEnumValue, CUSTOM_ENUM_VALUE_ADVICE_TYPE => "custom_enum_value";
+    /// Enum value matching a member only when ignoring case
+    EnumValueCaseMismatch, ENUM_VALUE_CASE_MISMATCH_ADVICE_TYPE => "enum_value_case_mismatch";
+    /// Attribute type changed
+    AttributeTypeChanged, ATTRIBUTE_TYPE_CHANGED_ADVICE_TYPE => "attribute_type_changed";
+    /// Sample valid in the newer registry
+    ValidInNewerRegistry, VALID_IN_NEWER_REGISTRY_ADVICE_TYPE => "valid_in_newer_registry";
+    /// Scope attribute should be a resource attribute
+    ScopeAttributeShouldBeResource, SCOPE_ATTRIBUTE_SHOULD_BE_RESOURCE_ADVICE_TYPE => "scope_attribute_should_be_resource";
+    /// Cross field constraint violation
+    CrossFieldConstraintViolation, CROSS_FIELD_CONSTRAINT_VIOLATION_ADVICE_TYPE => "cross_field_constraint_violation";
+    /// Attribute with a constant value across all samples
+    ConstantAttribute, CONSTANT_ATTRIBUTE_ADVICE_TYPE => "constant_attribute";
+    /// Inconsistent resource attributes within a service
+    InconsistentServiceResource, INCONSISTENT_SERVICE_RESOURCE_ADVICE_TYPE => "inconsistent_service_resource";
+    /// Value out of the annotated range
+    ValueOutOfRange, VALUE_OUT_OF_RANGE_ADVICE_TYPE => "value_out_of_range";
+    /// Value not matching the annotated pattern
+    ValueFormatMismatch, VALUE_FORMAT_MISMATCH_ADVICE_TYPE => "value_format_mismatch";
+    /// Attribute value duplicating the span name
+    AttributeDuplicatesSpanName, ATTRIBUTE_DUPLICATES_SPAN_NAME_ADVICE_TYPE => "attribute_duplicates_span_name";
+    /// Attribute name repeated within a sample
+    DuplicateAttribute, DUPLICATE_ATTRIBUTE_ADVICE_TYPE => "duplicate_attribute";
+    /// Attribute name longer than the maximum length
+    AttributeNameTooLong, ATTRIBUTE_NAME_TOO_LONG_ADVICE_TYPE => "attribute_name_too_long";
+    /// Attribute value looking like a secret
+    PossibleSecretValue, POSSIBLE_SECRET_VALUE_ADVICE_TYPE => "possible_secret_value";
+    /// Experimental attribute used without opt-in
+    ExperimentalAttributeNotOptedIn, EXPERIMENTAL_ATTRIBUTE_NOT_OPTED_IN_ADVICE_TYPE => "experimental_attribute_not_opted_in";
+    /// Conditionally required attribute missing while its condition holds
+    ConditionallyRequiredConditionMet, CONDITIONALLY_REQUIRED_CONDITION_MET_ADVICE_TYPE => "conditionally_required_condition_met";
+    /// Sample attribute not resolving to any registry attribute
+    AttributeNotInRegistry, ATTRIBUTE_NOT_IN_REGISTRY_ADVICE_TYPE => "attribute_not_in_registry";
+    /// Attribute stability lower than in the baseline registry
+    StabilityRegression, STABILITY_REGRESSION_ADVICE_TYPE => "stability_regression";
+    /// Metric name not following the naming rules
+    InvalidMetricName, INVALID_METRIC_NAME_ADVICE_TYPE => "invalid_metric_name";
+    /// Attribute value shaped unlike the examples of its registry attribute
+    ValueUnlikeExamples, VALUE_UNLIKE_EXAMPLES_ADVICE_TYPE => "value_unlike_examples";
+    /// Sample attribute close to the name of a registry attribute
+    PossibleMisspelling, POSSIBLE_MISSPELLING_ADVICE_TYPE => "possible_misspelling";
+    /// Signal attribute declared as a resource attribute
+    AttributeShouldBeResource, ATTRIBUTE_SHOULD_BE_RESOURCE_ADVICE_TYPE => "attribute_should_be_resource";
+    /// Metric unit inconsistent with its name and instrument
+    UnitInstrumentInconsistent, UNIT_INSTRUMENT_INCONSISTENT_ADVICE_TYPE => "unit_instrument_inconsistent";
+    /// Sample attribute value not of its declared type
+    SampleTypeValueMismatch, SAMPLE_TYPE_VALUE_MISMATCH_ADVICE_TYPE => "sample_type_value_mismatch";
+    /// None of a required set of attributes present
+    RequiredAttributeGroupMissing, REQUIRED_ATTRIBUTE_GROUP_MISSING_ADVICE_TYPE => "required_attribute_group_missing";
+    /// Attribute value outside the allowed values of its registry attribute
+    ValueNotInAllowedSet, VALUE_NOT_IN_ALLOWED_SET_ADVICE_TYPE => "value_not_in_allowed_set";
+    /// Signal attribute duplicating the value of the resource attribute of the same name
+    RedundantResourceAttribute, REDUNDANT_RESOURCE_ATTRIBUTE_ADVICE_TYPE => "redundant_resource_attribute";
+    /// Attribute value of a kind not modeled by semantic conventions, an OTLP kvlist or bytes
+    UnsupportedValueKind, UNSUPPORTED_VALUE_KIND_ADVICE_TYPE => "unsupported_value_kind";
+    /// Enum value type contradicting the type of the enum members
+    EnumValueTypeMismatch, ENUM_VALUE_TYPE_MISMATCH_ADVICE_TYPE => "enum_value_type_mismatch";
+    /// Data points of a metric sharing an identical attribute set
+    DuplicateTimeSeries, DUPLICATE_TIME_SERIES_ADVICE_TYPE => "duplicate_time_series";
+    /// Live check against a registry without any group
+    NoRegistryLoaded, NO_REGISTRY_LOADED_ADVICE_TYPE => "no_registry_loaded";
+    /// Instrumentation scope without a name
+    MissingScopeName, MISSING_SCOPE_NAME_ADVICE_TYPE => "missing_scope_name";
+    /// Instrumentation scope version not following semver
+    InvalidScopeVersion, INVALID_SCOPE_VERSION_ADVICE_TYPE => "invalid_scope_version";
+    /// Histogram data point with a min greater than its max
+    HistogramMinMaxInverted, HISTOGRAM_MIN_MAX_INVERTED_ADVICE_TYPE => "histogram_min_max_inverted";
+    /// Histogram data point sum not within its count times min and max
+    HistogramSumOutOfBounds, HISTOGRAM_SUM_OUT_OF_BOUNDS_ADVICE_TYPE => "histogram_sum_out_of_bounds";
+    /// An attribute typed `any` in the registry
+    AttributeTypeAny, ATTRIBUTE_TYPE_ANY_ADVICE_TYPE => "attribute_type_any";
+    /// A trace id of the wrong length or all zeros
+    InvalidTraceId, INVALID_TRACE_ID_ADVICE_TYPE => "invalid_trace_id";
+    /// A span id of the wrong length or all zeros
+    InvalidSpanId, INVALID_SPAN_ID_ADVICE_TYPE => "invalid_span_id";
+    /// An opt-in or conditionally required attribute present on almost all the samples of its group
+    RequirementLevelMismatchSuspected, REQUIREMENT_LEVEL_MISMATCH_SUSPECTED_ADVICE_TYPE => "requirement_level_mismatch_suspected";
+    /// An advice of the advice baseline no longer given
+    BaselineAdviceResolved, BASELINE_ADVICE_RESOLVED_ADVICE_TYPE => "baseline_advice_resolved";
+    /// A data point value of another numeric kind than the registry metric expects
+    DatapointValueTypeMismatch, DATAPOINT_VALUE_TYPE_MISMATCH_ADVICE_TYPE => "datapoint_value_type_mismatch";
+    /// A sample name matching the registry once normalized
+    NameNormalizedToMatch, NAME_NORMALIZED_TO_MATCH_ADVICE_TYPE => "name_normalized_to_match";
+    /// A metric data point carrying more attributes than the maximum
+    TooManyAttributes, TOO_MANY_ATTRIBUTES_ADVICE_TYPE => "too_many_attributes";
+    /// An attribute seen with several types across the samples
+    InconsistentAttributeType, INCONSISTENT_ATTRIBUTE_TYPE_ADVICE_TYPE => "inconsistent_attribute_type";
+    /// Attribute value is a placeholder
+    PlaceholderValue, PLACEHOLDER_VALUE_ADVICE_TYPE => "placeholder_value";
+    /// Double value with no fractional part accepted for an int attribute
+    NumericCoercionApplied, NUMERIC_COERCION_APPLIED_ADVICE_TYPE => "numeric_coercion_applied";
+    /// Per-second rate unit on a counter or up-down counter
+    RateUnitOnCumulativeInstrument, RATE_UNIT_ON_CUMULATIVE_INSTRUMENT_ADVICE_TYPE => "rate_unit_on_cumulative_instrument";
+    /// Delta sum drifting from the cumulative series
+    DeltaCumulativeDrift, DELTA_CUMULATIVE_DRIFT_ADVICE_TYPE => "delta_cumulative_drift";
+    /// Malformed live_check annotation in the registry
+    InvalidLiveCheckAnnotation, INVALID_LIVE_CHECK_ANNOTATION_ADVICE_TYPE => "invalid_live_check_annotation";
+    /// Required attribute not present
+    RequiredAttributeNotPresent, REQUIRED_ATTRIBUTE_NOT_PRESENT_ADVICE_TYPE => "required_attribute_not_present";
+    /// Recommended attribute not present
+    RecommendedAttributeNotPresent, RECOMMENDED_ATTRIBUTE_NOT_PRESENT_ADVICE_TYPE => "recommended_attribute_not_present";
+    /// Opt-in attribute not present
+    OptInAttributeNotPresent, OPT_IN_ATTRIBUTE_NOT_PRESENT_ADVICE_TYPE => "opt_in_attribute_not_present";
+    /// Conditionally required attribute not present
+    ConditionallyRequiredAttributeNotPresent, CONDITIONALLY_REQUIRED_ATTRIBUTE_NOT_PRESENT_ADVICE_TYPE => "conditionally_required_attribute_not_present";
+}
+
+impl From<String> for BuiltinAdviceType {
+    fn from(advice_type: String) -> Self {
+        match BuiltinAdviceType::from(advice_type.as_str()) {
+            BuiltinAdviceType::Custom(_) => BuiltinAdviceType::Custom(advice_type),
+            builtin => builtin,
+        }
+    }
+}
+
+impl From<BuiltinAdviceType> for String {
+    fn from(advice_type: BuiltinAdviceType) -> Self {
+        match advice_type {
+            BuiltinAdviceType::Custom(advice_type) => advice_type,
+            builtin => builtin.as_str().to_owned(),
+        }
+    }
+}
+
+impl Display for BuiltinAdviceType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Equality, hashing and ordering go through the advice type string so a
+// `Custom` holding a builtin string is the same as the builtin variant.
+impl PartialEq for BuiltinAdviceType {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for BuiltinAdviceType {}
+
+impl Hash for BuiltinAdviceType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for BuiltinAdviceType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BuiltinAdviceType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Borrow<str> for BuiltinAdviceType {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for BuiltinAdviceType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for BuiltinAdviceType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Serialize for BuiltinAdviceType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for BuiltinAdviceType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(BuiltinAdviceType::from)
+    }
+}
+
+impl JsonSchema for BuiltinAdviceType {
+    fn schema_name() -> String {
+        "BuiltinAdviceType".to_owned()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        // Any advice type string is accepted, `Custom` covering the non builtin ones
+        String::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::violation::{Advice, AdviceLevel};
+
+    #[test]
+    fn test_builtin_advice_type_serialization() {
+        // The advice types must keep serializing to the strings used before the enum
+        assert_eq!(
+            serde_json::to_value(BuiltinAdviceType::MissingAttribute).unwrap(),
+            json!("missing_attribute")
+        );
+        assert_eq!(
+            serde_json::to_value(BuiltinAdviceType::ScopeAttributeShouldBeResource).unwrap(),
+            json!("scope_attribute_should_be_resource")
+        );
+        assert_eq!(DEPRECATED_ADVICE_TYPE, "deprecated");
+
+        for advice_type in BuiltinAdviceType::ALL {
+            let expected = advice_type.as_str();
+            assert_eq!(serde_json::to_value(advice_type).unwrap(), json!(expected));
+            assert_eq!(advice_type.to_string(), expected);
+            let deserialized: BuiltinAdviceType = serde_json::from_value(json!(expected)).unwrap();
+            assert!(!matches!(deserialized, BuiltinAdviceType::Custom(_)));
+            assert_eq!(&deserialized, advice_type);
+        }
+
+        // Other advice types, e.g. from Rego policies, round trip as custom
+        let custom: BuiltinAdviceType = serde_json::from_value(json!("my_policy")).unwrap();
+        assert_eq!(custom, BuiltinAdviceType::Custom("my_policy".to_owned()));
+        assert_eq!(serde_json::to_value(&custom).unwrap(), json!("my_policy"));
+
+        // Advice keeps serializing its type as a plain string
+        let advice = Advice::new(
+            BuiltinAdviceType::TypeMismatch,
+            json!({}),
+            "Type mismatch".to_owned(),
+            AdviceLevel::Violation,
+        );
+        assert_eq!(
+            serde_json::to_value(&advice).unwrap()["advice_type"],
+            json!("type_mismatch")
+        );
+        assert_eq!(advice.advice_type, BuiltinAdviceType::TypeMismatch);
+        assert!(matches!(
+            advice.advice_type,
+            BuiltinAdviceType::TypeMismatch
+        ));
+
+        // Rego policies give their advice types as strings, kept as custom
+        let advice: Advice = serde_json::from_value(json!({
+            "advice_type": "my_policy",
+            "advice_context": {},
+            "message": "My policy",
+            "advice_level": "violation",
+            "signal_type": null,
+            "signal_name": null,
+        }))
+        .unwrap();
+        assert!(matches!(
+            advice.advice_type,
+            BuiltinAdviceType::Custom(ref advice_type) if advice_type == "my_policy"
+        ));
+    }
+}
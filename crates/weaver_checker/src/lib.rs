@@ -10,6 +10,7 @@ use std::path::Path;
 
 use globset::Glob;
 use miette::Diagnostic;
+use regorus::unstable::{Parser, Rule, RuleHead};
 use serde::Serialize;
 use serde_json::to_value;
 use walkdir::DirEntry;
@@ -20,6 +21,7 @@ use weaver_common::error::{format_errors, handle_errors, WeaverError};
 use crate::violation::Violation;
 use crate::Error::CompoundError;
 
+pub mod advice_type;
 pub mod violation;
 
 /// Default semconv rules/functions for the semantic convention registry.
@@ -109,6 +111,14 @@ pub enum Error {
         violation: Box<Violation>,
     },
 
+    /// An unknown advice level.
+    #[error("Unknown advice level `{level}`, expected information | improvement | violation")]
+    #[diagnostic()]
+    UnknownAdviceLevel {
+        /// The advice level that could not be parsed.
+        level: String,
+    },
+
     /// A container for multiple errors.
     #[error("{:?}", format_errors(.0))]
     #[diagnostic()]
@@ -145,6 +155,7 @@ impl From<Error> for DiagnosticMessages {
 }
 
 /// A list of supported policy stages.
+#[derive(Debug, Clone, Copy)]
 pub enum PolicyStage {
     /// Policies that are evaluated before resolution.
     BeforeResolution,
@@ -154,6 +165,8 @@ pub enum PolicyStage {
     ComparisonAfterResolution,
     /// Policies that are evaluated to provide advice on samples.
     LiveCheckAdvice,
+    /// Policies that are evaluated once to provide advice across a batch of samples.
+    LiveCheckBatchAdvice,
 }
 
 impl Display for PolicyStage {
@@ -172,6 +185,9 @@ impl Display for PolicyStage {
             PolicyStage::LiveCheckAdvice => {
                 write!(f, "live_check_advice")
             }
+            PolicyStage::LiveCheckBatchAdvice => {
+                write!(f, "live_check_batch_advice")
+            }
         }
     }
 }
@@ -203,6 +219,119 @@ impl Engine {
         self.coverage_enabled = true;
     }
 
+    /// Enables the tracking of the policy files producing violations, without printing
+    /// the coverage report. See [`Engine::unused_policies`].
+    pub fn enable_policy_usage(&mut self) {
+        self.engine.set_enable_coverage(true);
+    }
+
+    /// Returns the paths of the policy files of the given stage whose `deny` rules never
+    /// produced a violation since the policy usage tracking was enabled.
+    ///
+    /// The `deny` rules are found in the parsed policies. A `deny` rule head is only
+    /// evaluated once its body succeeded, so a policy file is used when one of its
+    /// `deny` rule heads was covered. Policy files without `deny`
+    /// rules, e.g. defining helper functions, are not reported.
+    pub fn unused_policies(&self, stage: PolicyStage) -> Result<Vec<String>, Error> {
+        let report =
+            self.engine
+                .get_coverage_report()
+                .map_err(|e| Error::ViolationEvaluationError {
+                    error: e.to_string(),
+                })?;
+        let path = |refr| {
+            Parser::get_path_ref_components(refr).map(|components| {
+                components
+                    .iter()
+                    .map(|component| component.text())
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+        };
+        let package = stage.to_string();
+        let mut unused = Vec::new();
+        // The modules are only exposed mutably, they are read from a copy of the engine
+        for module in self.engine.clone().get_modules() {
+            if path(&module.package.refr).ok().as_ref() != Some(&package) {
+                continue;
+            }
+            let deny_lines: Vec<u32> = module
+                .policy
+                .iter()
+                .filter_map(|rule| match rule.as_ref() {
+                    Rule::Spec {
+                        head: RuleHead::Compr { span, refr, .. } | RuleHead::Set { span, refr, .. },
+                        ..
+                    } => {
+                        let name = path(refr).ok()?;
+                        (name == "deny" || name.starts_with("deny.")).then_some(span.line)
+                    }
+                    _ => None,
+                })
+                .collect();
+            if deny_lines.is_empty() {
+                continue;
+            }
+            let file = module.package.span.source.file();
+            let used = report
+                .files
+                .iter()
+                .filter(|covered_file| &covered_file.path == file)
+                .any(|covered_file| {
+                    deny_lines
+                        .iter()
+                        .any(|line| covered_file.covered.contains(line))
+                });
+            if !used {
+                unused.push(file.clone());
+            }
+        }
+        Ok(unused)
+    }
+
+    /// Returns the evaluation trace of the policies of the given stage on the current
+    /// input and data, for debugging policies.
+    ///
+    /// The trace lists the policy lines evaluated, as `path:line: code`, followed by the
+    /// output of the `print` calls. The policies are evaluated on a copy of the engine so
+    /// the coverage report and the policy usage tracking are left untouched.
+    pub fn explain(&self, stage: PolicyStage) -> Result<Vec<String>, Error> {
+        if !self.policy_packages.contains(&format!("data.{stage}")) {
+            return Ok(vec![]);
+        }
+        let mut engine = self.engine.clone();
+        engine.set_enable_coverage(true);
+        engine.clear_coverage_data();
+        engine.set_gather_prints(true);
+        let _ = engine
+            .eval_rule(format!("data.{stage}.deny"))
+            .map_err(|e| Error::ViolationEvaluationError {
+                error: e.to_string(),
+            })?;
+        let report = engine
+            .get_coverage_report()
+            .map_err(|e| Error::ViolationEvaluationError {
+                error: e.to_string(),
+            })?;
+
+        let mut trace = Vec::new();
+        for file in report.files {
+            for (code, line) in file.code.lines().zip(1..) {
+                if file.covered.contains(&line) {
+                    trace.push(format!("{}:{}: {}", file.path, line, code.trim()));
+                }
+            }
+        }
+        trace.extend(
+            engine
+                .take_prints()
+                .map_err(|e| Error::ViolationEvaluationError {
+                    error: e.to_string(),
+                })?,
+        );
+        Ok(trace)
+    }
+
     /// Adds a rego policy (content) to the policy engine.
     ///
     /// # Arguments
@@ -469,7 +598,7 @@ mod tests {
 
     use weaver_common::error::format_errors;
 
-    use crate::violation::Violation;
+    use crate::violation::{AdviceLevel, Violation};
     use crate::{Engine, Error, PolicyStage};
 
     #[test]
@@ -520,6 +649,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_advice_level_str() {
+        for level in [
+            AdviceLevel::Information,
+            AdviceLevel::Improvement,
+            AdviceLevel::Violation,
+        ] {
+            assert_eq!(level.as_str().parse::<AdviceLevel>().unwrap(), level);
+            assert_eq!(
+                serde_json::to_value(&level).unwrap(),
+                serde_json::json!(level.to_string())
+            );
+        }
+        assert!(matches!(
+            "fatal".parse::<AdviceLevel>(),
+            Err(Error::UnknownAdviceLevel { level }) if level == "fatal"
+        ));
+    }
+
     #[test]
     fn test_invalid_policy() {
         let mut engine = Engine::new();
@@ -556,6 +704,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unused_policies() -> Result<(), Box<dyn std::error::Error>> {
+        let mut engine = Engine::new();
+        engine.enable_policy_usage();
+        let deny_policy = |name: &str| {
+            format!(
+                r#"package live_check_advice
+
+import rego.v1
+
+deny contains advice if {{
+	input.name == "{name}"
+	advice := {{
+		"type": "advice",
+		"advice_type": "{name}",
+		"advice_context": {{}},
+		"message": "{name}",
+		"advice_level": "information",
+		"signal_type": null,
+		"signal_name": null,
+	}}
+}}
+"#
+            )
+        };
+        let _ = engine.add_policy("fired.rego", &deny_policy("fired"))?;
+        let _ = engine.add_policy("dead.rego", &deny_policy("dead"))?;
+        // Found from the parsed rules, whatever the layout of the rule head
+        let _ = engine.add_policy(
+            "spaced.rego",
+            &deny_policy("spaced").replace("deny contains", "deny  contains"),
+        )?;
+        // Helpers only, never reported
+        let _ = engine.add_policy(
+            "helpers.rego",
+            r#"package live_check_advice
+
+import rego.v1
+
+is_fired(name) if name == "fired"
+"#,
+        )?;
+
+        engine.set_input(&serde_json::json!({"name": "fired"}))?;
+        let violations = engine.check(PolicyStage::LiveCheckAdvice)?;
+        assert_eq!(violations.len(), 1);
+
+        assert_eq!(
+            engine.unused_policies(PolicyStage::LiveCheckAdvice)?,
+            vec!["dead.rego".to_owned(), "spaced.rego".to_owned()]
+        );
+        // Other stages have no policies
+        assert!(engine
+            .unused_policies(PolicyStage::AfterResolution)?
+            .is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_add_policies() -> Result<(), Box<dyn std::error::Error>> {
         let mut engine = Engine::new();
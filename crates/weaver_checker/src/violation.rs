@@ -5,7 +5,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::advice_type::BuiltinAdviceType;
+use crate::Error;
 
 /// Enum representing the different types of violations.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -24,7 +29,7 @@ pub enum Violation {
         attr: String,
     },
     /// Advice related to a policy violation.
-    Advice(Advice),
+    Advice(Box<Advice>),
 }
 
 impl Display for Violation {
@@ -41,14 +46,16 @@ impl Display for Violation {
                     "id={id}, category={category}, group={group}, attr={attr}"
                 )
             }
-            Violation::Advice(Advice {
-                advice_type: r#type,
-                advice_context,
-                message,
-                advice_level,
-                signal_type,
-                signal_name,
-            }) => {
+            Violation::Advice(advice) => {
+                let Advice {
+                    advice_type: r#type,
+                    advice_context,
+                    message,
+                    advice_level,
+                    signal_type,
+                    signal_name,
+                    ..
+                } = advice.as_ref();
                 write!(
                     f,
                     "type={type}, context={advice_context}, message={message}, advice_level={advice_level:?}, signal_type={signal_type:?}, signal_name={signal_name:?}"
@@ -64,15 +71,16 @@ impl Violation {
     pub fn id(&self) -> &str {
         match self {
             Violation::SemconvAttribute { id, .. } => id,
-            Violation::Advice(Advice {
-                advice_type: r#type,
-                ..
-            }) => r#type,
+            Violation::Advice(advice) => advice.advice_type.as_str(),
         }
     }
 }
 
-/// The level of an advice
+/// The level of an advice, ordered by severity:
+/// `Information` < `Improvement` < `Violation`.
+///
+/// The derived ordering follows the declaration order of the variants, so new
+/// levels must be declared according to their severity.
 #[derive(
     Debug, Clone, PartialEq, Serialize, Deserialize, PartialOrd, Ord, Eq, Hash, JsonSchema,
 )]
@@ -86,12 +94,47 @@ pub enum AdviceLevel {
     Violation,
 }
 
+impl AdviceLevel {
+    /// Returns the advice level string e.g. "violation", as serialized.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdviceLevel::Information => "information",
+            AdviceLevel::Improvement => "improvement",
+            AdviceLevel::Violation => "violation",
+        }
+    }
+}
+
+impl Display for AdviceLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AdviceLevel {
+    type Err = Error;
+
+    /// Parses an advice level string e.g. "violation", as serialized.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "information" => Ok(AdviceLevel::Information),
+            "improvement" => Ok(AdviceLevel::Improvement),
+            "violation" => Ok(AdviceLevel::Violation),
+            _ => Err(Error::UnknownAdviceLevel {
+                level: s.to_owned(),
+            }),
+        }
+    }
+}
+
 /// Represents a live check advice
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Advice {
-    /// The type of advice e.g. "is_deprecated". This should be a short,
-    /// machine-readable string that categorizes the advice.
-    pub advice_type: String,
+    /// The type of advice e.g. "deprecated", one of the builtin advice types or a
+    /// `Custom` one e.g. from a Rego policy. Serialized as a short, machine-readable
+    /// string that categorizes the advice.
+    pub advice_type: BuiltinAdviceType,
 
     /// The context associated with the advice e.g. { "attribute_name": "foo.bar", "attribute_value": "bar" }
     /// The context should contain all dynamic parts of the message
@@ -112,4 +155,116 @@ pub struct Advice {
 
     /// The signal name the advice applies to e.g. "http.server.request.duration".
     pub signal_name: Option<String>,
+
+    /// The id of the advisor that emitted the advice e.g. "type" or "rego".
+    /// Set by the live checker as the advice flows out of each advisor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub advisor_id: Option<String>,
+
+    /// The number of advisors that gave this same advice, when the live checker
+    /// deduplicates advice e.g. `2` when both a builtin advisor and a Rego policy
+    /// reported it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+
+    /// The confidence, from 0.0 to 1.0, of an advice based on an approximate match
+    /// e.g. the suggestion of a misspelling advisor. Not set for exact advice.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+
+    /// Where the sample the advice applies to was read from e.g. the input file and
+    /// record index. Not set for samples constructed programmatically.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
+
+    /// The evaluation trace of the Rego policies that produced the advice, when the
+    /// explain mode of the Rego advisor is enabled. Meant for policy authors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Vec<String>>,
+
+    /// A concrete remediation that tooling can apply automatically, e.g. renaming a
+    /// deprecated attribute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<AdviceFix>,
+
+    /// The name of the registry defining the attribute or group the sample matched,
+    /// when checking against several registries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    /// The registry attribute the sample attribute was matched against, set by the
+    /// live checker for the advice given on a matched attribute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_ref: Option<MatchedRef>,
+
+    /// Facets for consumers to filter the advice on e.g. `{ "signal": "metric", "namespace": "http" }`.
+    /// The live checker sets `signal` from the signal type when not set by the advisor.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, Value>,
+}
+
+impl Advice {
+    /// Create a new advice, without signal nor any of the optional details.
+    /// Those are set with the struct update syntax e.g.
+    /// `Advice { signal_type, signal_name, ..Advice::new(advice_type, context, message, level) }`
+    #[must_use]
+    pub fn new(
+        advice_type: BuiltinAdviceType,
+        advice_context: Value,
+        message: String,
+        advice_level: AdviceLevel,
+    ) -> Self {
+        Advice {
+            advice_type,
+            advice_context,
+            message,
+            advice_level,
+            signal_type: None,
+            signal_name: None,
+            advisor_id: None,
+            count: None,
+            confidence: None,
+            source: None,
+            explanation: None,
+            fix: None,
+            registry: None,
+            matched_ref: None,
+            attributes: BTreeMap::new(),
+        }
+    }
+}
+
+/// A machine-readable remediation of an advice
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AdviceFix {
+    /// Rename the attribute or signal e.g. `{"action": "rename", "from": "db.system", "to": "db.system.name"}`
+    Rename {
+        /// The current name
+        from: String,
+        /// The name to use instead
+        to: String,
+    },
+}
+
+/// A registry attribute a sample was matched against
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct MatchedRef {
+    /// The name of the registry attribute e.g. "http.request.method"
+    pub attribute: String,
+    /// The id of the group defining the attribute e.g. "registry.http"
+    pub group_id: String,
+}
+
+/// The location of a sample in the live check input
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct SampleSource {
+    /// The input file, not set for standard input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The line of the sample in the input, starting at 1, for line based inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// The index of the sample in the input, starting at 0
+    pub record: usize,
 }
@@ -124,6 +124,12 @@ impl DiagnosticMessage {
         }
     }
 
+    /// Returns the displayed message of the diagnostic
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.diagnostic.message
+    }
+
     /// Returns true if the diagnostic message is a warning
     #[must_use]
     pub fn is_warning(&self) -> bool {
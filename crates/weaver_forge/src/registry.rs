@@ -8,13 +8,18 @@ use crate::error::Error;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use weaver_common::diagnostic::DiagnosticMessage;
+use weaver_common::result::WResult;
 use weaver_resolved_schema::attribute::Attribute;
 use weaver_resolved_schema::catalog::Catalog;
 use weaver_resolved_schema::lineage::GroupLineage;
 use weaver_resolved_schema::registry::{Group, Registry};
+use weaver_resolver::SchemaResolver;
 use weaver_semconv::any_value::AnyValueSpec;
 use weaver_semconv::deprecated::Deprecated;
 use weaver_semconv::group::{GroupType, InstrumentSpec, SpanKindSpec};
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::registry_repo::RegistryRepo;
 use weaver_semconv::stability::Stability;
 use weaver_semconv::YamlValue;
 
@@ -179,6 +184,51 @@ impl ResolvedGroup {
 }
 
 impl ResolvedRegistry {
+    /// Load and resolve the semantic convention registry of a registry repository,
+    /// with its dependencies, into a template registry. The non-fatal errors of the
+    /// loading and of the resolution are returned together.
+    pub fn try_from_registry_repo(
+        registry_repo: &RegistryRepo,
+        follow_symlinks: bool,
+        include_unreferenced: bool,
+    ) -> WResult<Self, DiagnosticMessage> {
+        let (semconv_specs, mut non_fatal_errors) =
+            match SchemaResolver::load_semconv_specs(registry_repo, true, follow_symlinks)
+                .into_result_with_non_fatal()
+            {
+                Ok((semconv_specs, errors)) => (
+                    semconv_specs,
+                    errors
+                        .into_iter()
+                        .map(DiagnosticMessage::new)
+                        .collect::<Vec<_>>(),
+                ),
+                Err(e) => return WResult::FatalErr(DiagnosticMessage::new(e)),
+            };
+        let mut registry = match SemConvRegistry::from_semconv_specs(registry_repo, semconv_specs) {
+            Ok(registry) => registry,
+            Err(e) => return WResult::FatalErr(DiagnosticMessage::new(e)),
+        };
+        let resolved_schema = match SchemaResolver::resolve_semantic_convention_registry(
+            &mut registry,
+            include_unreferenced,
+        )
+        .into_result_with_non_fatal()
+        {
+            Ok((resolved_schema, errors)) => {
+                non_fatal_errors.extend(errors.into_iter().map(DiagnosticMessage::new));
+                resolved_schema
+            }
+            Err(e) => return WResult::FatalErr(DiagnosticMessage::new(e)),
+        };
+        match Self::try_from_resolved_registry(&resolved_schema.registry, resolved_schema.catalog())
+        {
+            Ok(registry) if non_fatal_errors.is_empty() => WResult::Ok(registry),
+            Ok(registry) => WResult::OkWithNFEs(registry, non_fatal_errors),
+            Err(e) => WResult::FatalErr(DiagnosticMessage::new(e)),
+        }
+    }
+
     /// Create a new template registry from a resolved registry.
     pub fn try_from_resolved_registry(
         registry: &Registry,
@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compares the allocations of the `Vec`-returning `Advisor::advise` with the
+//! sink-based `Advisor::advise_into` on a data point missing many attributes.
+//!
+//! Run with `cargo bench -p weaver_live_check --bench advise_into`.
+
+#![allow(clippy::print_stdout)]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use serde_json::json;
+use weaver_forge::registry::ResolvedGroup;
+use weaver_live_check::{
+    advice::{Advisor, TypeAdvisor},
+    sample_metric::{SampleInstrument, SampleMetric, SampleNumberDataPoint},
+    Sample, SampleRef,
+};
+use weaver_semconv::group::InstrumentSpec;
+
+/// An allocator counting the allocations
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded to the system allocator with the caller's guarantees
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded to the system allocator with the caller's guarantees
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded to the system allocator with the caller's guarantees
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ATTRIBUTES: usize = 64;
+const ITERATIONS: usize = 1_000;
+
+/// Runs the function `ITERATIONS` times, printing the allocations and the time taken
+fn measure(label: &str, mut f: impl FnMut() -> usize) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut advice_count = 0;
+    for _ in 0..ITERATIONS {
+        advice_count += f();
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "{label:<12} {advice_count} advices, {:.1} allocations/iteration, {:?}/iteration",
+        allocations as f64 / ITERATIONS as f64,
+        elapsed / ITERATIONS as u32
+    );
+}
+
+fn main() {
+    let attributes: Vec<_> = (0..ATTRIBUTES)
+        .map(|i| {
+            json!({
+                "name": format!("bench.attribute_{i}"),
+                "type": "string",
+                "brief": "",
+                "requirement_level": "recommended",
+            })
+        })
+        .collect();
+    let group: ResolvedGroup = serde_json::from_value(json!({
+        "id": "metric.bench",
+        "type": "metric",
+        "brief": "",
+        "attributes": attributes,
+        "metric_name": "bench",
+        "instrument": "gauge",
+        "unit": "1",
+    }))
+    .expect("valid group");
+    let group = Some(Rc::new(group));
+    let signal = Sample::Metric(SampleMetric {
+        name: "bench".to_owned(),
+        instrument: SampleInstrument::Supported(InstrumentSpec::Gauge),
+        unit: "1".to_owned(),
+        temporality: None,
+        data_points: None,
+        live_check_result: None,
+        source: None,
+    });
+    let data_point = SampleNumberDataPoint {
+        attributes: vec![],
+        value: json!(0),
+        flags: 0,
+        exemplars: vec![],
+        live_check_result: None,
+    };
+    let sample = SampleRef::NumberDataPoint(&data_point);
+    let mut advisor = TypeAdvisor;
+
+    measure("advise", || {
+        let advice = advisor
+            .advise(sample.clone(), &signal, None, group.clone())
+            .expect("advice");
+        black_box(advice).len()
+    });
+    measure("advise_into", || {
+        let mut count = 0;
+        advisor
+            .advise_into(
+                sample.clone(),
+                &signal,
+                None,
+                group.clone(),
+                &mut |advice| {
+                    count += 1;
+                    drop(black_box(advice));
+                },
+            )
+            .expect("advice");
+        count
+    });
+}
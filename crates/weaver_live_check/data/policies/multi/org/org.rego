@@ -0,0 +1,23 @@
+package live_check_advice
+
+import rego.v1
+
+# checks attribute name contains the word "org"
+deny contains make_advice(advice_type, advice_level, advice_context, message) if {
+	input.sample.attribute
+	contains(input.sample.attribute.name, "org")
+	advice_type := "contains_org"
+	advice_level := "improvement"
+	advice_context := {
+		"attribute_name": input.sample.attribute.name
+	}
+	message := sprintf("Attribute name must not contain 'org', but was '%s'", [input.sample.attribute.name])
+}
+
+make_advice(advice_type, advice_level, advice_context, message) := {
+	"type": "advice",
+	"advice_type": advice_type,
+	"advice_level": advice_level,
+	"advice_context": advice_context,
+	"message": message,
+}
@@ -0,0 +1,15 @@
+package live_check_advice
+
+import rego.v1
+
+# checks attribute name contains the word "team", relies on make_advice from the org policies
+deny contains make_advice(advice_type, advice_level, advice_context, message) if {
+	input.sample.attribute
+	contains(input.sample.attribute.name, "team")
+	advice_type := "contains_team"
+	advice_level := "information"
+	advice_context := {
+		"attribute_name": input.sample.attribute.name
+	}
+	message := sprintf("Attribute name must not contain 'team', but was '%s'", [input.sample.attribute.name])
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! A baseline of previously accepted advice, to only surface the new findings of
+//! a run and report the accepted ones no longer given
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use weaver_checker::violation::Advice;
+
+use crate::Error;
+
+/// An advice accepted in a baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// The advice type e.g. "type_mismatch"
+    pub advice_type: String,
+    /// The advice message, for reviewers of the baseline file
+    pub message: String,
+}
+
+/// A set of accepted advice by fingerprint, saved as a JSON file.
+///
+/// The fingerprint of an advice is stable across runs: it is made of the advice
+/// type, the signal type and name, and the advice context holding the sample
+/// attribute name and value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdviceBaseline {
+    /// The accepted advice by fingerprint
+    pub advice: BTreeMap<String, BaselineEntry>,
+}
+
+impl AdviceBaseline {
+    /// Create a new empty AdviceBaseline
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a baseline from a JSON file
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let invalid_baseline = |error: String| Error::InvalidAdviceBaseline {
+            path: path.display().to_string(),
+            error,
+        };
+        let content = std::fs::read_to_string(path).map_err(|e| invalid_baseline(e.to_string()))?;
+        serde_json::from_str(&content).map_err(|e| invalid_baseline(e.to_string()))
+    }
+
+    /// Save the baseline to a JSON file
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| Error::OutputError {
+            error: e.to_string(),
+        })?;
+        std::fs::write(path, content).map_err(|e| Error::OutputError {
+            error: format!("Failed to write `{}`: {e}", path.display()),
+        })
+    }
+
+    /// Returns the stable fingerprint of an advice
+    #[must_use]
+    pub fn fingerprint(advice: &Advice) -> String {
+        format!(
+            "{}|{}|{}|{}",
+            advice.advice_type,
+            advice.signal_type.as_deref().unwrap_or_default(),
+            advice.signal_name.as_deref().unwrap_or_default(),
+            advice.advice_context
+        )
+    }
+
+    /// Add an advice to the baseline
+    pub fn add(&mut self, advice: &Advice) {
+        let _ = self
+            .advice
+            .entry(Self::fingerprint(advice))
+            .or_insert_with(|| BaselineEntry {
+                advice_type: advice.advice_type.to_string(),
+                message: advice.message.clone(),
+            });
+    }
+
+    /// Returns true if the advice is in the baseline
+    #[must_use]
+    pub fn contains(&self, advice: &Advice) -> bool {
+        self.advice.contains_key(&Self::fingerprint(advice))
+    }
+
+    /// Returns true if the fingerprint is in the baseline
+    #[must_use]
+    pub fn contains_fingerprint(&self, fingerprint: &str) -> bool {
+        self.advice.contains_key(fingerprint)
+    }
+
+    /// The number of advice in the baseline
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.advice.len()
+    }
+
+    /// Returns true if the baseline is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.advice.is_empty()
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Renderers writing a list of advice in a given output format
+
+use std::io::{IsTerminal, Write};
+
+use weaver_checker::violation::{Advice, AdviceLevel};
+
+use crate::Error;
+
+/// Renders a list of advice to a writer
+pub trait AdviceRenderer {
+    /// Write the advice to the writer
+    fn render(&self, advice: &[Advice], writer: &mut dyn Write) -> Result<(), Error>;
+}
+
+/// Converts an IO error into an output error
+fn output_error(error: std::io::Error) -> Error {
+    Error::OutputError {
+        error: error.to_string(),
+    }
+}
+
+/// Renders the advice as a pretty printed JSON array
+pub struct JsonRenderer;
+
+impl AdviceRenderer for JsonRenderer {
+    fn render(&self, advice: &[Advice], writer: &mut dyn Write) -> Result<(), Error> {
+        serde_json::to_writer_pretty(&mut *writer, advice).map_err(|e| Error::OutputError {
+            error: e.to_string(),
+        })?;
+        writeln!(writer).map_err(output_error)
+    }
+}
+
+/// Renders the advice as a YAML sequence
+pub struct YamlRenderer;
+
+impl AdviceRenderer for YamlRenderer {
+    fn render(&self, advice: &[Advice], writer: &mut dyn Write) -> Result<(), Error> {
+        serde_yaml::to_writer(writer, advice).map_err(|e| Error::OutputError {
+            error: e.to_string(),
+        })
+    }
+}
+
+/// Renders the advice as plain text grouped by advice level, most severe first.
+///
+/// The level headings are color-coded when color is enabled, see [`TextRenderer::for_writer`].
+#[derive(Default)]
+pub struct TextRenderer {
+    color: bool,
+}
+
+impl TextRenderer {
+    /// Create a new TextRenderer, without color
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new TextRenderer for a writer, with color only when the writer is a terminal
+    #[must_use]
+    pub fn for_writer(writer: &impl IsTerminal) -> Self {
+        TextRenderer {
+            color: writer.is_terminal(),
+        }
+    }
+
+    /// Enable or disable the color codes
+    #[must_use]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns the heading of an advice level, with its ANSI color code when enabled
+    fn heading(&self, level: &AdviceLevel, count: usize) -> String {
+        let name = level.as_str();
+        let color_code = match level {
+            AdviceLevel::Violation => "31",
+            AdviceLevel::Improvement => "33",
+            AdviceLevel::Information => "36",
+        };
+        if self.color {
+            format!("\x1b[{color_code}m{name}\x1b[0m ({count}):")
+        } else {
+            format!("{name} ({count}):")
+        }
+    }
+}
+
+impl AdviceRenderer for TextRenderer {
+    fn render(&self, advice: &[Advice], writer: &mut dyn Write) -> Result<(), Error> {
+        for level in [
+            AdviceLevel::Violation,
+            AdviceLevel::Improvement,
+            AdviceLevel::Information,
+        ] {
+            let level_advice: Vec<&Advice> = advice
+                .iter()
+                .filter(|advice| advice.advice_level == level)
+                .collect();
+            if level_advice.is_empty() {
+                continue;
+            }
+            writeln!(writer, "{}", self.heading(&level, level_advice.len()))
+                .map_err(output_error)?;
+            for advice in level_advice {
+                match &advice.signal_name {
+                    Some(signal_name) => writeln!(
+                        writer,
+                        "  - [{}] {} ({})",
+                        advice.advice_type, advice.message, signal_name
+                    ),
+                    None => writeln!(writer, "  - [{}] {}", advice.advice_type, advice.message),
+                }
+                .map_err(output_error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use weaver_checker::violation::{AdviceFix, MatchedRef};
+
+    fn mixed_advice() -> Vec<Advice> {
+        vec![
+            Advice {
+                signal_type: Some("span".to_owned()),
+                signal_name: Some("GET /users".to_owned()),
+                ..Advice::new(
+                    "missing_attribute".into(),
+                    json!({"attribute_name": "hello"}),
+                    "Attribute 'hello' does not exist in the registry.".to_owned(),
+                    AdviceLevel::Violation,
+                )
+            },
+            Advice::new(
+                "template_attribute".into(),
+                json!({"attribute_name": "test.template.key"}),
+                "Attribute 'test.template.key' is a template".to_owned(),
+                AdviceLevel::Information,
+            ),
+            Advice {
+                advisor_id: Some("stability".to_owned()),
+                ..Advice::new(
+                    "not_stable".into(),
+                    json!({"attribute_name": "test.experimental", "stability": "development"}),
+                    "Attribute 'test.experimental' is not stable; stability = development."
+                        .to_owned(),
+                    AdviceLevel::Improvement,
+                )
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json_renderer() {
+        let advice = mixed_advice();
+        let mut output = Vec::new();
+        JsonRenderer.render(&advice, &mut output).unwrap();
+
+        let parsed: Vec<Advice> = serde_json::from_slice(&output).unwrap();
+        assert_eq!(parsed, advice);
+    }
+
+    #[test]
+    fn test_yaml_renderer() {
+        let mut advice = mixed_advice();
+        advice[0].fix = Some(AdviceFix::Rename {
+            from: "hello".to_owned(),
+            to: "hello.world".to_owned(),
+        });
+        advice[0].matched_ref = Some(MatchedRef {
+            attribute: "hello".to_owned(),
+            group_id: "registry.hello".to_owned(),
+        });
+        advice[0].confidence = Some(0.5);
+        let _ = advice[0]
+            .attributes
+            .insert("namespace".to_owned(), json!("hello"));
+        let _ = advice[0]
+            .attributes
+            .insert("owners".to_owned(), json!({"teams": ["api", "web"]}));
+        let mut output = Vec::new();
+        YamlRenderer.render(&advice, &mut output).unwrap();
+
+        let yaml = String::from_utf8(output).unwrap();
+        assert!(yaml.contains("  action: rename\n"));
+        assert!(yaml.contains("  group_id: registry.hello\n"));
+        assert!(!yaml.contains("explanation"));
+        let parsed: Vec<Advice> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, advice);
+    }
+
+    #[test]
+    fn test_text_renderer() {
+        let mut output = Vec::new();
+        TextRenderer::new()
+            .render(&mixed_advice(), &mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "violation (1):
+  - [missing_attribute] Attribute 'hello' does not exist in the registry. (GET /users)
+improvement (1):
+  - [not_stable] Attribute 'test.experimental' is not stable; stability = development.
+information (1):
+  - [template_attribute] Attribute 'test.template.key' is a template
+"
+        );
+
+        // Color is enabled from the writer the advice is rendered to, a file is not a terminal
+        let mut file = tempfile::tempfile().unwrap();
+        let renderer = TextRenderer::for_writer(&file);
+        assert!(!renderer.color);
+        renderer.render(&mixed_advice(), &mut file).unwrap();
+        assert_eq!(
+            TextRenderer::new()
+                .with_color(true)
+                .heading(&AdviceLevel::Violation, 1),
+            "\x1b[31mviolation\x1b[0m (1):"
+        );
+    }
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The advice types of the builtin advisors, defined next to `Advice` in `weaver_checker`.
+
+#[allow(clippy::wildcard_imports)]
+pub use weaver_checker::advice_type::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_catalog::default_template;
+
+    #[test]
+    fn test_builtin_advice_types_have_templates() {
+        // Every builtin advice type has an English message template
+        for advice_type in BuiltinAdviceType::ALL {
+            assert!(
+                default_template(advice_type.as_str()).is_some(),
+                "no message template for {advice_type}"
+            );
+        }
+    }
+}
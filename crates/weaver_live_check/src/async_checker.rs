@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Async live check of samples received over a channel, for async pipelines such
+//! as a collector. The advisors stay sync and run in a blocking task.
+
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{live_checker::LiveChecker, Error, LiveCheckReport, LiveCheckStatistics, Sample};
+
+/// Spawn a blocking task running the live check on the samples received from
+/// `samples`, in order, as they arrive. Each checked sample, carrying its advice,
+/// is sent to `checked`.
+///
+/// The live checker is built by `build` within the task, as it is not `Send`.
+/// With bounded channels, back-pressure is respected: the task waits for room in
+/// `checked` before receiving the next sample.
+///
+/// The task ends once `samples` is closed, `checked` is dropped, or in fail fast
+/// mode at the first violation. It returns a report, without the samples, holding
+/// the batch advice and the statistics.
+pub fn spawn_live_check<F>(
+    build: F,
+    mut samples: mpsc::Receiver<Sample>,
+    checked: mpsc::Sender<Sample>,
+) -> JoinHandle<Result<LiveCheckReport, Error>>
+where
+    F: FnOnce() -> LiveChecker + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let mut live_checker = build();
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        let mut index = 0;
+        while let Some(mut sample) = samples.blocking_recv() {
+            let stop = live_checker.check_sample(index, &mut sample, &mut stats)?;
+            index += 1;
+            if checked.blocking_send(sample).is_err() || stop {
+                break;
+            }
+        }
+        let batch_advice = live_checker.finalize()?;
+        stats.add_batch_advice(&batch_advice);
+        stats.finalize();
+        let mut report = LiveCheckReport {
+            samples: Vec::new(),
+            batch_advice,
+            statistics: stats,
+        };
+        live_checker.post_process(&mut report);
+        Ok(report)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weaver_forge::registry::ResolvedRegistry;
+
+    use crate::{advice::default_advisors, sample_attribute::SampleAttribute};
+
+    #[tokio::test]
+    async fn test_spawn_live_check() {
+        let registry = ResolvedRegistry {
+            registry_url: "TEST".to_owned(),
+            groups: vec![],
+        };
+        // A capacity of one, the task waits for each checked sample to be received
+        let (samples_tx, samples_rx) = mpsc::channel(1);
+        let (checked_tx, mut checked_rx) = mpsc::channel(1);
+        let handle = spawn_live_check(
+            move || LiveChecker::new(registry, default_advisors()),
+            samples_rx,
+            checked_tx,
+        );
+
+        let names = ["http.method", "db.system", "custom.attribute"];
+        for name in names {
+            samples_tx
+                .send(Sample::Attribute(SampleAttribute::try_from(name).unwrap()))
+                .await
+                .unwrap();
+            let Some(Sample::Attribute(attribute)) = checked_rx.recv().await else {
+                panic!("Expected a checked attribute");
+            };
+            assert_eq!(attribute.name, name);
+            // Every attribute is missing from the empty registry
+            let advice = attribute.live_check_result.unwrap().all_advice;
+            assert_eq!(advice[0].advice_type, "missing_attribute");
+        }
+        drop(samples_tx);
+        assert!(checked_rx.recv().await.is_none());
+
+        let report = handle.await.unwrap().unwrap();
+        assert!(report.samples.is_empty());
+        assert_eq!(report.statistics.total_entities, names.len());
+        assert_eq!(report.batch_advice[0].advice_type, "no_registry_loaded");
+    }
+}
@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Group the advice of checked OTLP documents into a tree following the
+//! resource → scope → signal → data point hierarchy
+
+use serde::Serialize;
+use weaver_checker::violation::Advice;
+
+use crate::{
+    otlp_document::OtlpDocument,
+    sample_attribute::SampleAttribute,
+    sample_metric::{DataPoints, SampleExemplar},
+    LiveCheckResult, Sample,
+};
+
+/// The advice on a data point of a metric
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DataPointAdvice {
+    /// The index of the data point in the metric
+    pub index: usize,
+    /// The advice on the data point, its attributes and exemplars
+    pub advice: Vec<Advice>,
+}
+
+/// The advice on a signal, e.g. a metric or a span
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SignalAdvice {
+    /// The signal type e.g. "metric"
+    pub signal_type: Option<String>,
+    /// The signal name e.g. "http.server.request.duration"
+    pub signal_name: Option<String>,
+    /// The advice on the signal and its attributes, including the span events and links of a span
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub advice: Vec<Advice>,
+    /// The data points of a metric given advice
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub data_points: Vec<DataPointAdvice>,
+}
+
+/// The advice on an instrumentation scope and its signals
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScopeAdvice {
+    /// The name of the instrumentation scope
+    pub name: String,
+    /// The version of the instrumentation scope
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The advice on the instrumentation scope and its attributes
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub advice: Vec<Advice>,
+    /// The signals of the instrumentation scope
+    pub signals: Vec<SignalAdvice>,
+}
+
+/// The advice on a resource and its instrumentation scopes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceAdvice {
+    /// The `service.name` of the resource
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_name: Option<String>,
+    /// The advice on the resource and its attributes
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub advice: Vec<Advice>,
+    /// The instrumentation scopes of the resource
+    pub scopes: Vec<ScopeAdvice>,
+}
+
+/// The advice of checked OTLP documents grouped by resource, instrumentation scope,
+/// signal and data point, e.g. for UIs presenting the advice in context.
+///
+/// Documents are added once checked with [`crate::live_checker::LiveChecker::check_otlp_document`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GroupedAdvice {
+    /// The resources of the documents, in the order they were added
+    pub resources: Vec<ResourceAdvice>,
+}
+
+impl GroupedAdvice {
+    /// Create a new empty GroupedAdvice
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the advice of a checked document
+    pub fn add_document(&mut self, document: &OtlpDocument) {
+        for otlp_resource in &document.resources {
+            let resource = &otlp_resource.resource;
+            self.resources.push(ResourceAdvice {
+                service_name: resource.service_name().map(str::to_owned),
+                advice: entity_advice(resource.live_check_result.as_ref(), &resource.attributes),
+                scopes: otlp_resource
+                    .scopes
+                    .iter()
+                    .map(|otlp_scope| ScopeAdvice {
+                        name: otlp_scope.scope.name.clone(),
+                        version: otlp_scope.scope.version.clone(),
+                        advice: entity_advice(
+                            otlp_scope.scope.live_check_result.as_ref(),
+                            &otlp_scope.scope.attributes,
+                        ),
+                        signals: otlp_scope.signals.iter().map(signal_advice).collect(),
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
+
+/// Returns the advice on an entity and its attributes
+fn entity_advice(result: Option<&LiveCheckResult>, attributes: &[SampleAttribute]) -> Vec<Advice> {
+    result
+        .into_iter()
+        .chain(
+            attributes
+                .iter()
+                .filter_map(|attribute| attribute.live_check_result.as_ref()),
+        )
+        .flat_map(|result| result.all_advice.iter().cloned())
+        .collect()
+}
+
+/// Returns the advice on a data point, its attributes and exemplars
+fn point_advice(
+    result: Option<&LiveCheckResult>,
+    attributes: &[SampleAttribute],
+    exemplars: &[SampleExemplar],
+) -> Vec<Advice> {
+    let mut advice = entity_advice(result, attributes);
+    for exemplar in exemplars {
+        advice.extend(entity_advice(
+            exemplar.live_check_result.as_ref(),
+            &exemplar.filtered_attributes,
+        ));
+    }
+    advice
+}
+
+/// Returns all the advice on a checked sample, including the advice on its attributes
+/// and on the data points of a metric
+pub(crate) fn sample_advice(sample: &Sample) -> Vec<Advice> {
+    let signal = signal_advice(sample);
+    let mut advice = signal.advice;
+    for data_point in signal.data_points {
+        advice.extend(data_point.advice);
+    }
+    advice
+}
+
+/// Returns the advice on a signal, the advice on the data points of a metric apart
+fn signal_advice(signal: &Sample) -> SignalAdvice {
+    let mut data_points = Vec::new();
+    let advice = match signal {
+        Sample::Attribute(attribute) => entity_advice(attribute.live_check_result.as_ref(), &[]),
+        Sample::Span(span) => {
+            let mut advice = entity_advice(span.live_check_result.as_ref(), &span.attributes);
+            for span_event in &span.span_events {
+                advice.extend(entity_advice(
+                    span_event.live_check_result.as_ref(),
+                    &span_event.attributes,
+                ));
+            }
+            for span_link in &span.span_links {
+                advice.extend(entity_advice(
+                    span_link.live_check_result.as_ref(),
+                    &span_link.attributes,
+                ));
+            }
+            advice
+        }
+        Sample::SpanEvent(span_event) => entity_advice(
+            span_event.live_check_result.as_ref(),
+            &span_event.attributes,
+        ),
+        Sample::SpanLink(span_link) => {
+            entity_advice(span_link.live_check_result.as_ref(), &span_link.attributes)
+        }
+        Sample::Resource(resource) => {
+            entity_advice(resource.live_check_result.as_ref(), &resource.attributes)
+        }
+        Sample::Scope(scope) => entity_advice(scope.live_check_result.as_ref(), &scope.attributes),
+        Sample::Metric(metric) => {
+            let points: Vec<Vec<Advice>> = match &metric.data_points {
+                Some(DataPoints::Number(points)) => points
+                    .iter()
+                    .map(|p| {
+                        point_advice(p.live_check_result.as_ref(), &p.attributes, &p.exemplars)
+                    })
+                    .collect(),
+                Some(DataPoints::Histogram(points)) => points
+                    .iter()
+                    .map(|p| {
+                        point_advice(p.live_check_result.as_ref(), &p.attributes, &p.exemplars)
+                    })
+                    .collect(),
+                Some(DataPoints::ExponentialHistogram(points)) => points
+                    .iter()
+                    .map(|p| {
+                        point_advice(p.live_check_result.as_ref(), &p.attributes, &p.exemplars)
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+            data_points = points
+                .into_iter()
+                .enumerate()
+                .filter(|(_, advice)| !advice.is_empty())
+                .map(|(index, advice)| DataPointAdvice { index, advice })
+                .collect();
+            entity_advice(metric.live_check_result.as_ref(), &[])
+        }
+    };
+    SignalAdvice {
+        signal_type: signal.signal_type(),
+        signal_name: signal.signal_name(),
+        advice,
+        data_points,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_semconv::group::InstrumentSpec;
+
+    use crate::{
+        live_checker::LiveChecker,
+        otlp_document::{OtlpResource, OtlpScope},
+        sample_metric::{SampleInstrument, SampleMetric, SampleNumberDataPoint},
+        sample_resource::SampleResource,
+        sample_scope::SampleScope,
+        LiveCheckStatistics,
+    };
+
+    fn metric(name: &str, points: &[&[&str]]) -> Sample {
+        Sample::Metric(SampleMetric {
+            name: name.to_owned(),
+            instrument: SampleInstrument::Supported(InstrumentSpec::Gauge),
+            unit: "1".to_owned(),
+            temporality: None,
+            data_points: Some(DataPoints::Number(
+                points
+                    .iter()
+                    .map(|attributes| SampleNumberDataPoint {
+                        attributes: attributes
+                            .iter()
+                            .map(|attribute| SampleAttribute::try_from(*attribute).unwrap())
+                            .collect(),
+                        value: json!(1),
+                        flags: 0,
+                        exemplars: vec![],
+                        live_check_result: None,
+                    })
+                    .collect(),
+            )),
+            live_check_result: None,
+            source: None,
+        })
+    }
+
+    #[test]
+    fn test_grouped_advice() {
+        let registry = ResolvedRegistry {
+            registry_url: "TEST".to_owned(),
+            groups: vec![],
+        };
+        let mut document = OtlpDocument {
+            resources: vec![OtlpResource {
+                resource: SampleResource {
+                    attributes: vec![SampleAttribute::try_from("service.name=checkout").unwrap()],
+                    live_check_result: None,
+                    source: None,
+                },
+                scopes: vec![OtlpScope {
+                    scope: SampleScope {
+                        name: "checkout.orders".to_owned(),
+                        version: None,
+                        attributes: vec![],
+                        live_check_result: None,
+                        source: None,
+                    },
+                    signals: vec![
+                        metric("orders.pending", &[&[], &["orders.region=eu"]]),
+                        metric("orders.shipped", &[]),
+                    ],
+                }],
+            }],
+        };
+        let mut live_checker = LiveChecker::new(registry.clone(), vec![]);
+        let mut stats = LiveCheckStatistics::new(&registry);
+        assert!(!live_checker
+            .check_otlp_document(&mut document, &mut stats)
+            .unwrap());
+
+        let mut grouped = GroupedAdvice::new();
+        grouped.add_document(&document);
+
+        // Every attribute and metric is missing from the empty registry
+        let tree: Vec<_> = grouped
+            .resources
+            .iter()
+            .map(|resource| {
+                let scopes: Vec<_> = resource
+                    .scopes
+                    .iter()
+                    .map(|scope| {
+                        let signals: Vec<_> = scope
+                            .signals
+                            .iter()
+                            .map(|signal| {
+                                let points: Vec<_> = signal
+                                    .data_points
+                                    .iter()
+                                    .map(|point| (point.index, point.advice.len()))
+                                    .collect();
+                                (
+                                    signal.signal_name.clone().unwrap(),
+                                    signal.advice.len(),
+                                    points,
+                                )
+                            })
+                            .collect();
+                        (scope.name.as_str(), signals)
+                    })
+                    .collect();
+                (
+                    resource.service_name.as_deref(),
+                    resource.advice.len(),
+                    scopes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            tree,
+            vec![(
+                Some("checkout"),
+                1,
+                vec![(
+                    "checkout.orders",
+                    vec![
+                        ("orders.pending".to_owned(), 1, vec![(1, 1)]),
+                        ("orders.shipped".to_owned(), 1, vec![]),
+                    ]
+                )]
+            )]
+        );
+
+        // Serialized as nested JSON
+        let json = serde_json::to_value(&grouped).unwrap();
+        let point_advice = &json["resources"][0]["scopes"][0]["signals"][0]["data_points"][0];
+        assert_eq!(point_advice["index"], json!(1));
+        assert_eq!(
+            point_advice["advice"][0]["advice_context"]["attribute_name"],
+            json!("orders.region")
+        );
+    }
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! An advisor delegating to an external policy service over HTTP, e.g. an OPA
+//! server or a custom service, for centralized governance
+
+use std::{rc::Rc, time::Duration};
+
+use weaver_checker::violation::Advice;
+use weaver_forge::registry::ResolvedGroup;
+use weaver_resolved_schema::attribute::Attribute;
+
+use crate::{
+    advice::{Advisor, RegoInput},
+    live_checker::{LiveChecker, RegistryMeta},
+    Error, Sample, SampleRef,
+};
+
+/// The time the [`HttpAdvisor`] waits for the policy service by default
+pub const DEFAULT_HTTP_ADVISOR_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An advisor that POSTs each sample to a policy service, with the same shape as the
+/// `input` of the Rego policies: the `sample`, `registry_attribute`, `registry_group`
+/// and `registry_meta`. The service answers with a JSON array of advice, empty when
+/// the sample is compliant.
+///
+/// Failed requests, timeouts included, and invalid responses are an
+/// [`Error::AdviceError`].
+pub struct HttpAdvisor {
+    endpoint: String,
+    agent: ureq::Agent,
+    registry_meta: Option<Rc<RegistryMeta>>,
+}
+
+impl HttpAdvisor {
+    /// Create a new HttpAdvisor posting the samples to `endpoint`
+    #[must_use]
+    pub fn new(live_checker: &LiveChecker, endpoint: &str) -> Self {
+        HttpAdvisor {
+            endpoint: endpoint.to_owned(),
+            agent: agent(DEFAULT_HTTP_ADVISOR_TIMEOUT),
+            registry_meta: live_checker.registry_meta().cloned().map(Rc::new),
+        }
+    }
+
+    /// Set the time to wait for the policy service, for each sample
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = agent(timeout);
+        self
+    }
+
+    fn advice_error(&self, error: impl std::fmt::Display) -> Error {
+        Error::AdviceError {
+            error: format!("Policy service `{}` failed. {error}", self.endpoint),
+        }
+    }
+}
+
+fn agent(timeout: Duration) -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(timeout).build()
+}
+
+impl Advisor for HttpAdvisor {
+    fn id(&self) -> &str {
+        "http"
+    }
+
+    fn advise(
+        &mut self,
+        sample: SampleRef<'_>,
+        _signal: &Sample,
+        registry_attribute: Option<Rc<Attribute>>,
+        registry_group: Option<Rc<ResolvedGroup>>,
+    ) -> Result<Vec<Advice>, Error> {
+        let input = serde_json::to_string(&RegoInput {
+            sample,
+            registry_attribute,
+            registry_group,
+            registry_meta: self.registry_meta.clone(),
+        })
+        .map_err(|e| self.advice_error(e))?;
+        let response = self
+            .agent
+            .post(&self.endpoint)
+            .set("Content-Type", "application/json")
+            .send_string(&input)
+            .map_err(|e| self.advice_error(e))?
+            .into_string()
+            .map_err(|e| self.advice_error(e))?;
+        serde_json::from_str(&response).map_err(|e| self.advice_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rouille::{Response, Server};
+    use serde_json::{json, Value};
+    use weaver_forge::registry::ResolvedRegistry;
+
+    use crate::{sample_attribute::SampleAttribute, LiveCheckRunner, LiveCheckStatistics};
+
+    /// Serve `handler` on a local port until the returned sender is dropped
+    fn serve(
+        handler: impl Fn(&rouille::Request) -> Response + Send + Sync + 'static,
+    ) -> (String, std::sync::mpsc::Sender<()>) {
+        let server = Server::new("127.0.0.1:0", handler).unwrap();
+        let endpoint = format!("http://127.0.0.1:{}/advice", server.server_addr().port());
+        let (_, kill_switch) = server.stoppable();
+        (endpoint, kill_switch)
+    }
+
+    fn check(advisor: HttpAdvisor, live_checker: LiveChecker) -> Result<Vec<Advice>, Error> {
+        let mut live_checker = live_checker;
+        live_checker.add_advisor(Box::new(advisor));
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        let mut sample =
+            Sample::Attribute(SampleAttribute::try_from("custom.route=/users").unwrap());
+        sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone())?;
+        let Sample::Attribute(attribute) = sample else {
+            panic!("Expected an attribute sample");
+        };
+        Ok(attribute.live_check_result.unwrap().all_advice)
+    }
+
+    fn empty_live_checker() -> LiveChecker {
+        LiveChecker::new(
+            ResolvedRegistry {
+                registry_url: "TEST".to_owned(),
+                groups: vec![],
+            },
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_http_advisor() {
+        // Answers with canned advice naming the attribute of the sample it received
+        let (endpoint, _kill_switch) = serve(|request| {
+            let mut body = String::new();
+            let _ = std::io::Read::read_to_string(&mut request.data().unwrap(), &mut body);
+            let input: Value = serde_json::from_str(&body).unwrap();
+            let name = input["sample"]["attribute"]["name"].clone();
+            Response::json(&json!([{
+                "type": "advice",
+                "advice_type": "route_not_allowed",
+                "advice_context": {"attribute_name": name},
+                "message": "Routes are not allowed in attribute names.",
+                "advice_level": "violation",
+                "signal_type": null,
+                "signal_name": null,
+            }]))
+        });
+        let live_checker = empty_live_checker();
+        let advice = check(HttpAdvisor::new(&live_checker, &endpoint), live_checker).unwrap();
+        assert_eq!(advice.len(), 2);
+        assert_eq!(advice[0].advice_type, "missing_attribute");
+        assert_eq!(advice[1].advice_type, "route_not_allowed");
+        assert_eq!(
+            advice[1].advice_context,
+            json!({"attribute_name": "custom.route"})
+        );
+        assert_eq!(advice[1].advisor_id.as_deref(), Some("http"));
+    }
+
+    #[test]
+    fn test_http_advisor_errors() {
+        let is_advice_error = |result: Result<Vec<Advice>, Error>| matches!(result, Err(Error::AdviceError { error }) if error.contains("Policy service"));
+
+        // An error status
+        let (endpoint, _kill_switch) = serve(|_| Response::text("boom").with_status_code(500));
+        let live_checker = empty_live_checker();
+        let advisor = HttpAdvisor::new(&live_checker, &endpoint);
+        assert!(is_advice_error(check(advisor, live_checker)));
+
+        // Not an array of advice
+        let (endpoint, _kill_switch) = serve(|_| Response::json(&json!({"advice": []})));
+        let live_checker = empty_live_checker();
+        let advisor = HttpAdvisor::new(&live_checker, &endpoint);
+        assert!(is_advice_error(check(advisor, live_checker)));
+
+        // A service slower than the timeout
+        let (endpoint, _kill_switch) = serve(|_| {
+            std::thread::sleep(Duration::from_millis(500));
+            Response::json(&json!([]))
+        });
+        let live_checker = empty_live_checker();
+        let advisor =
+            HttpAdvisor::new(&live_checker, &endpoint).with_timeout(Duration::from_millis(50));
+        assert!(is_advice_error(check(advisor, live_checker)));
+    }
+}
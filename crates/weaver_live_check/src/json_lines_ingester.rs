@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! An ingester that streams newline-delimited JSON samples (JSONL) from a file or
+//! standard input. One sample is read and parsed per line so memory stays bounded.
+//! Malformed lines are collected as errors without stopping the stream.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use weaver_checker::violation::SampleSource;
+
+use crate::{Error, Ingester, Sample};
+
+/// Where the JSON lines are read from
+enum JsonLinesSource {
+    File(PathBuf),
+    Stdin,
+}
+
+/// An ingester that streams one JSON sample per line from a file or standard input.
+///
+/// Malformed lines are skipped and recorded, they can be retrieved with
+/// [`JsonLinesIngester::errors`] once the samples have been consumed.
+pub struct JsonLinesIngester {
+    source: JsonLinesSource,
+    errors: Rc<RefCell<Vec<Error>>>,
+}
+
+impl JsonLinesIngester {
+    /// Create a new JsonLinesIngester reading from a file
+    #[must_use]
+    pub fn from_file(path: &Path) -> Self {
+        JsonLinesIngester {
+            source: JsonLinesSource::File(path.to_path_buf()),
+            errors: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Create a new JsonLinesIngester reading from standard input
+    #[must_use]
+    pub fn from_stdin() -> Self {
+        JsonLinesIngester {
+            source: JsonLinesSource::Stdin,
+            errors: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Returns the errors of the lines read so far
+    #[must_use]
+    pub fn errors(&self) -> Vec<Error> {
+        self.errors.borrow().clone()
+    }
+}
+
+impl Ingester for JsonLinesIngester {
+    fn ingest(&self) -> Result<Box<dyn Iterator<Item = Sample>>, Error> {
+        let iterator: JsonLinesIterator<Box<dyn BufRead>> = match &self.source {
+            JsonLinesSource::File(path) => {
+                let file = File::open(path).map_err(|e| Error::IngestError {
+                    error: format!("Failed to open file {}: {}", path.display(), e),
+                })?;
+                let reader: Box<dyn BufRead> = Box::new(BufReader::new(file));
+                JsonLinesIterator::new(reader).with_file(path.display().to_string())
+            }
+            JsonLinesSource::Stdin => JsonLinesIterator::new(Box::new(io::stdin().lock())),
+        };
+        let errors = Rc::clone(&self.errors);
+        Ok(Box::new(iterator.filter_map(move |result| match result {
+            Ok(sample) => Some(sample),
+            Err(error) => {
+                errors.borrow_mut().push(error);
+                None
+            }
+        })))
+    }
+}
+
+/// Iterator parsing one JSON sample per line of any BufRead source.
+/// Blank lines are ignored.
+///
+/// Each sample records its [`SampleSource`]: the file, its line and its record
+/// index, counting the non-blank lines from 0 including the malformed ones.
+pub struct JsonLinesIterator<R: BufRead> {
+    lines: io::Lines<R>,
+    line_number: usize,
+    record: usize,
+    file: Option<String>,
+}
+
+impl<R: BufRead> JsonLinesIterator<R> {
+    /// Create a new JsonLinesIterator from a BufRead source
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_number: 0,
+            record: 0,
+            file: None,
+        }
+    }
+
+    /// Set the file the lines are read from, recorded in the sample sources
+    #[must_use]
+    pub fn with_file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+}
+
+impl<R: BufRead> Iterator for JsonLinesIterator<R> {
+    type Item = Result<Sample, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    return Some(Err(Error::IngestError {
+                        error: format!("Failed to read line {}: {}", self.line_number, e),
+                    }))
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = self.record;
+            self.record += 1;
+            return Some(
+                serde_json::from_str(&line)
+                    .map(|mut sample: Sample| {
+                        sample.set_source(SampleSource {
+                            file: self.file.clone(),
+                            line: Some(self.line_number),
+                            record,
+                        });
+                        sample
+                    })
+                    .map_err(|e| Error::InvalidSampleLine {
+                        line: self.line_number,
+                        error: e.to_string(),
+                    }),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    use crate::{
+        advice::TypeAdvisor, live_checker::LiveChecker, LiveCheckRunner, LiveCheckStatistics,
+    };
+    use weaver_forge::registry::ResolvedRegistry;
+
+    const INPUT: &str = r#"{"attribute": {"name": "first.attribute", "value": "a"}}
+{"attribute": {"name": "broken.attribute", "value":
+
+{"attribute": {"name": "third.attribute", "value": 3}}
+"#;
+
+    #[test]
+    fn test_malformed_line_does_not_stop_the_stream() {
+        let results: Vec<_> = JsonLinesIterator::new(Cursor::new(INPUT)).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(Error::InvalidSampleLine { line: 2, .. })
+        ));
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn test_json_lines_file_ingestion() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("samples.jsonl");
+        let mut file = File::create(&file_path).unwrap();
+        write!(file, "{INPUT}").unwrap();
+
+        let ingester = JsonLinesIngester::from_file(&file_path);
+        let mut live_checker = LiveChecker::new(
+            ResolvedRegistry {
+                registry_url: "TEST".to_owned(),
+                groups: vec![],
+            },
+            vec![Box::new(TypeAdvisor)],
+        );
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        let mut checked = Vec::new();
+        for mut sample in ingester.ingest().unwrap() {
+            sample
+                .run_live_check(&mut live_checker, &mut stats, None, &sample.clone())
+                .unwrap();
+            checked.push(sample);
+        }
+
+        // The lines around the malformed one are still checked
+        let names: Vec<_> = checked
+            .iter()
+            .map(|sample| match sample {
+                Sample::Attribute(attribute) => {
+                    assert!(attribute.live_check_result.is_some());
+                    attribute.name.as_str()
+                }
+                _ => panic!("Expected an attribute sample"),
+            })
+            .collect();
+        assert_eq!(names, vec!["first.attribute", "third.attribute"]);
+
+        let errors = ingester.errors();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            Error::InvalidSampleLine { line: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_advice_sample_source() {
+        let input = r#"{"attribute": {"name": "first.attribute", "value": "a"}}
+
+{"span": {"name": "GET /", "kind": "server", "attributes": [{"name": "second.attribute", "value": 2}]}}
+"#;
+        let mut live_checker = LiveChecker::new(
+            ResolvedRegistry {
+                registry_url: "TEST".to_owned(),
+                groups: vec![],
+            },
+            vec![],
+        );
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        let mut samples: Vec<Sample> = JsonLinesIterator::new(Cursor::new(input))
+            .with_file("samples.jsonl".to_owned())
+            .map(Result::unwrap)
+            .collect();
+        for sample in &mut samples {
+            sample
+                .run_live_check(&mut live_checker, &mut stats, None, &sample.clone())
+                .unwrap();
+        }
+
+        let Sample::Attribute(attribute) = &samples[0] else {
+            panic!("Expected an attribute sample");
+        };
+        let advice = &attribute.live_check_result.as_ref().unwrap().all_advice;
+        assert_eq!(advice[0].advice_type, "missing_attribute");
+        assert_eq!(
+            advice[0].source,
+            Some(SampleSource {
+                file: Some("samples.jsonl".to_owned()),
+                line: Some(1),
+                record: 0,
+            })
+        );
+
+        // The advice on the attributes of a span points to the span record
+        let Sample::Span(span) = &samples[1] else {
+            panic!("Expected a span sample");
+        };
+        let advice = &span.attributes[0]
+            .live_check_result
+            .as_ref()
+            .unwrap()
+            .all_advice;
+        assert_eq!(advice[0].advice_type, "missing_attribute");
+        let source = advice[0].source.as_ref().unwrap();
+        assert_eq!(source.line, Some(3));
+        assert_eq!(source.record, 1);
+        assert_eq!(
+            serde_json::to_value(&advice[0]).unwrap()["source"],
+            serde_json::json!({"file": "samples.jsonl", "line": 3, "record": 1})
+        );
+    }
+}
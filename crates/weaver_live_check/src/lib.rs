@@ -12,53 +12,71 @@ use sample_metric::{
     SampleNumberDataPoint,
 };
 use sample_resource::SampleResource;
+use sample_scope::SampleScope;
 use sample_span::{SampleSpan, SampleSpanEvent, SampleSpanLink};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use weaver_checker::violation::{Advice, AdviceLevel};
+use weaver_checker::violation::{Advice, AdviceLevel, SampleSource};
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
 use weaver_semconv::group::GroupType;
 
 /// Advisors for live checks
 pub mod advice;
+/// Baseline of accepted advice
+pub mod advice_baseline;
+/// Renderers for lists of advice
+pub mod advice_renderer;
+/// The advice types of the builtin advisors
+pub mod advice_type;
+/// Async live check of samples received over a channel
+#[cfg(feature = "tokio")]
+pub mod async_checker;
+/// Advice grouped by sample hierarchy
+pub mod grouped_advice;
+/// Advisor delegating to an external HTTP policy service
+#[cfg(feature = "http")]
+pub mod http_advisor;
 /// An ingester that reads samples from a JSON file.
 pub mod json_file_ingester;
+/// An ingester that streams newline-delimited JSON samples.
+pub mod json_lines_ingester;
 /// An ingester that reads samples from standard input.
 pub mod json_stdin_ingester;
+/// The `live_check` annotation of registry attributes and groups
+pub mod live_check_annotation;
 /// Live checker
 pub mod live_checker;
+/// Message templates of the advice
+pub mod message_catalog;
+/// Nested OTLP documents
+pub mod otlp_document;
+/// Protobuf encoded OTLP export requests
+#[cfg(feature = "otlp-protobuf")]
+pub mod otlp_protobuf;
 /// The intermediary format for attributes
 pub mod sample_attribute;
 /// The intermediary format for metrics
 pub mod sample_metric;
 /// An intermediary format for resources
 pub mod sample_resource;
+/// An intermediary format for instrumentation scopes
+pub mod sample_scope;
 /// The intermediary format for spans
 pub mod sample_span;
+/// Live check reports grouped by service
+pub mod service_report;
 /// An ingester that reads attribute names from a text file.
 pub mod text_file_ingester;
 /// An ingester that reads attribute names from standard input.
 pub mod text_stdin_ingester;
 
-/// Missing Attribute advice type
-pub const MISSING_ATTRIBUTE_ADVICE_TYPE: &str = "missing_attribute";
-/// Template Attribute advice type
-pub const TEMPLATE_ATTRIBUTE_ADVICE_TYPE: &str = "template_attribute";
-/// Missing Metric advice type
-pub const MISSING_METRIC_ADVICE_TYPE: &str = "missing_metric";
-/// Deprecated advice type
-pub const DEPRECATED_ADVICE_TYPE: &str = "deprecated";
-/// Type Mismatch advice type
-pub const TYPE_MISMATCH_ADVICE_TYPE: &str = "type_mismatch";
-/// Unstable advice type
-pub const NOT_STABLE_ADVICE_TYPE: &str = "not_stable";
-/// Unit mismatch advice type
-pub const UNIT_MISMATCH_ADVICE_TYPE: &str = "unit_mismatch";
-/// Instrument mismatch advice type
-pub const UNEXPECTED_INSTRUMENT_ADVICE_TYPE: &str = "unexpected_instrument";
-/// Undefined enum variant advice type
-pub const UNDEFINED_ENUM_VARIANT_ADVICE_TYPE: &str = "undefined_enum_variant";
+#[allow(clippy::wildcard_imports)]
+// The `*_ADVICE_TYPE` constants generated with the advice types
+pub use advice_type::*;
+
+/// Annotation holding the live check settings of a registry attribute
+pub const LIVE_CHECK_ANNOTATION: &str = "live_check";
 
 /// Attribute name key in advice context
 pub const ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: &str = "attribute_name";
@@ -78,6 +96,83 @@ pub const UNIT_ADVICE_CONTEXT_KEY: &str = "unit";
 pub const INSTRUMENT_ADVICE_CONTEXT_KEY: &str = "instrument";
 /// Expected value key in advice context
 pub const EXPECTED_VALUE_ADVICE_CONTEXT_KEY: &str = "expected";
+/// Baseline registry type key in advice context
+pub const BASELINE_TYPE_ADVICE_CONTEXT_KEY: &str = "baseline_type";
+/// Constraint key in advice context
+pub const CONSTRAINT_ADVICE_CONTEXT_KEY: &str = "constraint";
+/// Other attribute name key in advice context
+pub const OTHER_ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: &str = "other_attribute_name";
+/// Other attribute value key in advice context
+pub const OTHER_ATTRIBUTE_VALUE_ADVICE_CONTEXT_KEY: &str = "other_attribute_value";
+/// Range minimum key in advice context
+pub const MIN_ADVICE_CONTEXT_KEY: &str = "min";
+/// Range maximum key in advice context
+pub const MAX_ADVICE_CONTEXT_KEY: &str = "max";
+/// Reason key in advice context
+pub const REASON_ADVICE_CONTEXT_KEY: &str = "reason";
+/// Template name key in advice context
+pub const TEMPLATE_NAME_ADVICE_CONTEXT_KEY: &str = "template_name";
+/// Count key in advice context
+pub const COUNT_ADVICE_CONTEXT_KEY: &str = "count";
+/// Pattern key in advice context
+pub const PATTERN_ADVICE_CONTEXT_KEY: &str = "pattern";
+/// Service name key in advice context
+pub const SERVICE_NAME_ADVICE_CONTEXT_KEY: &str = "service_name";
+/// Distinct values key in advice context
+pub const VALUES_ADVICE_CONTEXT_KEY: &str = "values";
+/// Data point value key in advice context
+pub const VALUE_ADVICE_CONTEXT_KEY: &str = "value";
+/// Temporality key in advice context
+pub const TEMPORALITY_ADVICE_CONTEXT_KEY: &str = "temporality";
+/// Length key in advice context
+pub const LENGTH_ADVICE_CONTEXT_KEY: &str = "length";
+/// Condition key in advice context
+pub const CONDITION_ADVICE_CONTEXT_KEY: &str = "condition";
+/// Baseline registry stability key in advice context
+pub const BASELINE_STABILITY_ADVICE_CONTEXT_KEY: &str = "baseline_stability";
+/// Metric name key in advice context
+pub const METRIC_NAME_ADVICE_CONTEXT_KEY: &str = "metric_name";
+/// Value shape key in advice context
+pub const SHAPE_ADVICE_CONTEXT_KEY: &str = "shape";
+/// Example shapes key in advice context
+pub const EXAMPLE_SHAPES_ADVICE_CONTEXT_KEY: &str = "example_shapes";
+/// Suggested name key in advice context
+pub const SUGGESTION_ADVICE_CONTEXT_KEY: &str = "suggestion";
+/// Entity advice context key
+pub const ENTITY_ADVICE_CONTEXT_KEY: &str = "entity";
+/// Attribute names advice context key
+pub const ATTRIBUTE_NAMES_ADVICE_CONTEXT_KEY: &str = "attribute_names";
+/// Attributes advice context key
+pub const ATTRIBUTES_ADVICE_CONTEXT_KEY: &str = "attributes";
+/// Sum advice context key
+pub const SUM_ADVICE_CONTEXT_KEY: &str = "sum";
+/// Scope name advice context key
+pub const SCOPE_NAME_ADVICE_CONTEXT_KEY: &str = "scope_name";
+/// Scope version advice context key
+pub const SCOPE_VERSION_ADVICE_CONTEXT_KEY: &str = "scope_version";
+/// Trace id advice context key
+pub const TRACE_ID_ADVICE_CONTEXT_KEY: &str = "trace_id";
+/// Span id advice context key
+pub const SPAN_ID_ADVICE_CONTEXT_KEY: &str = "span_id";
+/// Group id advice context key
+pub const GROUP_ID_ADVICE_CONTEXT_KEY: &str = "group_id";
+/// Requirement level advice context key
+pub const REQUIREMENT_LEVEL_ADVICE_CONTEXT_KEY: &str = "requirement_level";
+/// Presence ratio advice context key
+pub const PRESENCE_ADVICE_CONTEXT_KEY: &str = "presence";
+/// Fingerprint advice context key
+pub const FINGERPRINT_ADVICE_CONTEXT_KEY: &str = "fingerprint";
+/// Advice type advice context key
+pub const ADVICE_TYPE_ADVICE_CONTEXT_KEY: &str = "advice_type";
+/// Message advice context key
+pub const MESSAGE_ADVICE_CONTEXT_KEY: &str = "message";
+/// Normalized name advice context key
+pub const NORMALIZED_NAME_ADVICE_CONTEXT_KEY: &str = "normalized_name";
+/// Types advice context key
+pub const TYPES_ADVICE_CONTEXT_KEY: &str = "types";
+
+/// Signal advice attribute, the signal type of the advice
+pub const SIGNAL_ADVICE_ATTRIBUTE: &str = "signal";
 
 /// Weaver live check errors
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Serialize, Diagnostic)]
@@ -94,6 +189,16 @@ pub enum Error {
     #[error("Attempt to ingest an empty line.")]
     IngestEmptyLine,
 
+    /// A line of a JSON lines input is not a valid sample.
+    #[error("Invalid sample on line {line}. {error}")]
+    #[diagnostic(severity(Warning))]
+    InvalidSampleLine {
+        /// The line number, starting at 1.
+        line: usize,
+        /// The parse error.
+        error: String,
+    },
+
     /// Advice error.
     #[error("Fatal error from Advisor. {error}")]
     AdviceError {
@@ -101,12 +206,64 @@ pub enum Error {
         error: String,
     },
 
+    /// A Rego policy failed to compile.
+    #[error("Failed to compile Rego policy `{policy_path}`. {message}")]
+    RegoCompileError {
+        /// The path of the policy file.
+        policy_path: String,
+        /// The compiler message.
+        message: String,
+    },
+
+    /// A Rego policy produced an advice type outside the allowed advice types.
+    #[error("Rego policy produced the advice type `{advice_type}`, which is not allowed.")]
+    UnknownAdviceType {
+        /// The advice type produced.
+        advice_type: String,
+    },
+
     /// Output error.
     #[error("Output error. {error}")]
     OutputError {
         /// The error that occurred.
         error: String,
     },
+
+    /// Invalid attribute or metric filter pattern.
+    #[error("Invalid filter pattern `{pattern}`. {error}")]
+    InvalidFilterPattern {
+        /// The invalid glob pattern.
+        pattern: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// Invalid suppression pattern.
+    #[error("Invalid suppression pattern `{pattern}`. {error}")]
+    InvalidSuppressionPattern {
+        /// The invalid glob pattern.
+        pattern: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// Invalid advice baseline file.
+    #[error("Invalid advice baseline `{path}`. {error}")]
+    InvalidAdviceBaseline {
+        /// The path of the baseline file.
+        path: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// Failed to load or resolve a semconv registry.
+    #[error("Failed to resolve the semconv registry `{path}`. {error}")]
+    RegistryResolutionError {
+        /// The path of the registry.
+        path: String,
+        /// The error that occurred.
+        error: String,
+    },
 }
 
 impl From<Error> for DiagnosticMessages {
@@ -136,6 +293,8 @@ pub enum Sample {
     SpanLink(SampleSpanLink),
     /// A sample resource
     Resource(SampleResource),
+    /// A sample instrumentation scope
+    Scope(SampleScope),
     /// A sample metric
     Metric(SampleMetric),
 }
@@ -155,6 +314,8 @@ pub enum SampleRef<'a> {
     SpanLink(&'a SampleSpanLink),
     /// A sample resource
     Resource(&'a SampleResource),
+    /// A sample instrumentation scope
+    Scope(&'a SampleScope),
     /// A sample metric
     Metric(&'a SampleMetric),
     /// A sample number data point
@@ -168,6 +329,35 @@ pub enum SampleRef<'a> {
     // TODO: add logs
 }
 
+/// The signal types advisors can be scoped to, named like the `signal_type` of the
+/// advice e.g. "span"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalType {
+    /// Metrics, their data points and exemplars
+    Metric,
+    /// Spans, their events and links
+    Span,
+    /// Logs, not produced by any sample yet
+    Log,
+    /// Resources
+    Resource,
+}
+
+impl SignalType {
+    /// Returns the signal type of a sample, or None for samples outside of any
+    /// signal such as standalone attributes and instrumentation scopes
+    #[must_use]
+    pub fn of(sample: &Sample) -> Option<Self> {
+        match sample {
+            Sample::Span(_) | Sample::SpanEvent(_) | Sample::SpanLink(_) => Some(Self::Span),
+            Sample::Metric(_) => Some(Self::Metric),
+            Sample::Resource(_) => Some(Self::Resource),
+            Sample::Attribute(_) | Sample::Scope(_) => None,
+        }
+    }
+}
+
 impl Sample {
     /// Returns the signal type as a string or None if sample
     /// does not capture a whole signal.
@@ -179,6 +369,7 @@ impl Sample {
             Sample::SpanEvent(_) => None,
             Sample::SpanLink(_) => None,
             Sample::Resource(_) => Some("resource".to_owned()),
+            Sample::Scope(_) => None,
             Sample::Metric(_) => Some("metric".to_owned()),
         }
     }
@@ -193,9 +384,58 @@ impl Sample {
             Sample::SpanEvent(_) => None,
             Sample::SpanLink(_) => None,
             Sample::Resource(_) => None,
+            Sample::Scope(_) => None,
             Sample::Metric(metric) => Some(metric.name.clone()),
         }
     }
+
+    /// Returns where the sample was read from, if known
+    #[must_use]
+    pub fn source(&self) -> Option<&SampleSource> {
+        match self {
+            Sample::Attribute(attribute) => attribute.source.as_ref(),
+            Sample::Span(span) => span.source.as_ref(),
+            Sample::SpanEvent(span_event) => span_event.source.as_ref(),
+            Sample::SpanLink(span_link) => span_link.source.as_ref(),
+            Sample::Resource(resource) => resource.source.as_ref(),
+            Sample::Scope(scope) => scope.source.as_ref(),
+            Sample::Metric(metric) => metric.source.as_ref(),
+        }
+    }
+
+    /// Set where the sample was read from
+    pub fn set_source(&mut self, source: SampleSource) {
+        let source = Some(source);
+        match self {
+            Sample::Attribute(attribute) => attribute.source = source,
+            Sample::Span(span) => span.source = source,
+            Sample::SpanEvent(span_event) => span_event.source = source,
+            Sample::SpanLink(span_link) => span_link.source = source,
+            Sample::Resource(resource) => resource.source = source,
+            Sample::Scope(scope) => scope.source = source,
+            Sample::Metric(metric) => metric.source = source,
+        }
+    }
+}
+
+impl<'a> SampleRef<'a> {
+    /// Returns the attributes of the sample entity or None if the
+    /// entity does not carry attributes.
+    #[must_use]
+    pub fn attributes(&self) -> Option<&'a [SampleAttribute]> {
+        match self {
+            SampleRef::Attribute(_) | SampleRef::Metric(_) => None,
+            SampleRef::Span(span) => Some(&span.attributes),
+            SampleRef::SpanEvent(span_event) => Some(&span_event.attributes),
+            SampleRef::SpanLink(span_link) => Some(&span_link.attributes),
+            SampleRef::Resource(resource) => Some(&resource.attributes),
+            SampleRef::Scope(scope) => Some(&scope.attributes),
+            SampleRef::NumberDataPoint(point) => Some(&point.attributes),
+            SampleRef::HistogramDataPoint(point) => Some(&point.attributes),
+            SampleRef::ExponentialHistogramDataPoint(point) => Some(&point.attributes),
+            SampleRef::Exemplar(exemplar) => Some(&exemplar.filtered_attributes),
+        }
+    }
 }
 
 // Dispatch the live check to the sample type
@@ -223,6 +463,9 @@ impl LiveCheckRunner for Sample {
             Sample::Resource(resource) => {
                 resource.run_live_check(live_checker, stats, parent_group, parent_signal)
             }
+            Sample::Scope(scope) => {
+                scope.run_live_check(live_checker, stats, parent_group, parent_signal)
+            }
             Sample::Metric(metric) => {
                 metric.run_live_check(live_checker, stats, parent_group, parent_signal)
             }
@@ -281,10 +524,28 @@ impl Default for LiveCheckResult {
 pub struct LiveCheckReport {
     /// The live check samples
     pub samples: Vec<Sample>,
+    /// Advice from batch advisors, given across all the samples
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub batch_advice: Vec<Advice>,
     /// The statistics for the report
     pub statistics: LiveCheckStatistics,
 }
 
+impl LiveCheckReport {
+    /// Returns all the advice of the report, the advice of each sample followed by
+    /// the batch advice, e.g. to render with an [`advice_renderer::AdviceRenderer`]
+    #[must_use]
+    pub fn all_advice(&self) -> Vec<Advice> {
+        let mut advice: Vec<Advice> = self
+            .samples
+            .iter()
+            .flat_map(grouped_advice::sample_advice)
+            .collect();
+        advice.extend(self.batch_advice.iter().cloned());
+        advice
+    }
+}
+
 /// The statistics for a live check report
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LiveCheckStatistics {
@@ -302,6 +563,8 @@ pub struct LiveCheckStatistics {
     pub no_advice_count: usize,
     /// The number of entities with each advice type
     pub advice_type_counts: HashMap<String, usize>,
+    /// The number of each advice level by advice type
+    pub advice_type_level_counts: HashMap<String, HashMap<AdviceLevel, usize>>,
     /// The number of entities with each advice message
     pub advice_message_counts: HashMap<String, usize>,
     /// The number of each attribute seen from the registry
@@ -314,6 +577,69 @@ pub struct LiveCheckStatistics {
     pub seen_non_registry_metrics: HashMap<String, usize>,
     /// Fraction of the registry covered by the attributes and metrics
     pub registry_coverage: f32,
+    /// Where the live check stopped, in fail fast mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_early: Option<FailFastStop>,
+    /// The advice counts against the severity budget, when one is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetResult>,
+    /// The first violation added to the statistics
+    #[serde(skip)]
+    first_violation: Option<Advice>,
+}
+
+/// The coverage of the attributes of a registry group, see [`LiveChecker::group_coverage`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GroupCoverage {
+    /// The number of attributes of the group seen in the samples
+    pub seen: usize,
+    /// The number of attributes of the group not seen in any sample
+    pub missing: usize,
+    /// The number of attributes of the group seen with a type mismatch at least once
+    pub type_mismatched: usize,
+}
+
+/// The advice counts of a run against a severity budget, see
+/// [`LiveChecker::check_budget`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetResult {
+    /// The advice levels given a maximum, from the most severe
+    pub levels: Vec<BudgetUsage>,
+}
+
+/// The advice count of an advice level against its maximum
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetUsage {
+    /// The advice level
+    pub level: AdviceLevel,
+    /// The number of advice of the level
+    pub count: usize,
+    /// The maximum number of advice allowed for the level
+    pub max: usize,
+    /// The number of advice over the maximum, 0 within the budget
+    pub excess: usize,
+}
+
+impl BudgetResult {
+    /// Returns true if any advice level is over its maximum
+    #[must_use]
+    pub fn is_exceeded(&self) -> bool {
+        self.levels.iter().any(|usage| usage.excess > 0)
+    }
+
+    /// Returns the advice levels over their maximum
+    pub fn exceeded(&self) -> impl Iterator<Item = &BudgetUsage> {
+        self.levels.iter().filter(|usage| usage.excess > 0)
+    }
+}
+
+/// Where a fail fast live check stopped
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailFastStop {
+    /// The index, in input order, of the sample that triggered the stop
+    pub sample_index: usize,
+    /// The first violation given on that sample
+    pub violation: Advice,
 }
 
 impl LiveCheckStatistics {
@@ -342,12 +668,16 @@ impl LiveCheckStatistics {
             highest_advice_level_counts: HashMap::new(),
             no_advice_count: 0,
             advice_type_counts: HashMap::new(),
+            advice_type_level_counts: HashMap::new(),
             advice_message_counts: HashMap::new(),
             seen_registry_attributes: seen_attributes,
             seen_non_registry_attributes: HashMap::new(),
             seen_registry_metrics: seen_metrics,
             seen_non_registry_metrics: HashMap::new(),
             registry_coverage: 0.0,
+            stopped_early: None,
+            budget: None,
+            first_violation: None,
         }
     }
 
@@ -373,6 +703,13 @@ impl LiveCheckStatistics {
         }
     }
 
+    /// Add the advice from batch advisors to the stats
+    pub fn add_batch_advice(&mut self, batch_advice: &[Advice]) {
+        for advice in batch_advice {
+            self.add_advice(advice);
+        }
+    }
+
     /// Increment the total number of entities by type
     pub fn inc_entity_count(&mut self, entity_type: &str) {
         *self
@@ -390,13 +727,28 @@ impl LiveCheckStatistics {
             .or_insert(0) += 1;
         *self
             .advice_type_counts
-            .entry(advice.advice_type.clone())
+            .entry(advice.advice_type.to_string())
+            .or_insert(0) += 1;
+        *self
+            .advice_type_level_counts
+            .entry(advice.advice_type.to_string())
+            .or_default()
+            .entry(advice.advice_level.clone())
             .or_insert(0) += 1;
         *self
             .advice_message_counts
             .entry(advice.message.clone())
             .or_insert(0) += 1;
         self.total_advisories += 1;
+        if advice.advice_level == AdviceLevel::Violation && self.first_violation.is_none() {
+            self.first_violation = Some(advice.clone());
+        }
+    }
+
+    /// Returns the first violation added to the statistics
+    #[must_use]
+    pub fn first_violation(&self) -> Option<&Advice> {
+        self.first_violation.as_ref()
     }
 
     /// Add a highest advice level to the statistics
@@ -447,6 +799,66 @@ impl LiveCheckStatistics {
             .contains_key(&AdviceLevel::Violation)
     }
 
+    /// Add the counts of other statistics to these statistics.
+    /// `finalize` should be called afterward to update the registry coverage.
+    pub fn merge(&mut self, other: &LiveCheckStatistics) {
+        fn merge_counts<K: Clone + Eq + std::hash::Hash>(
+            counts: &mut HashMap<K, usize>,
+            other: &HashMap<K, usize>,
+        ) {
+            for (key, count) in other {
+                *counts.entry(key.clone()).or_insert(0) += count;
+            }
+        }
+        self.total_entities += other.total_entities;
+        merge_counts(
+            &mut self.total_entities_by_type,
+            &other.total_entities_by_type,
+        );
+        self.total_advisories += other.total_advisories;
+        merge_counts(&mut self.advice_level_counts, &other.advice_level_counts);
+        merge_counts(
+            &mut self.highest_advice_level_counts,
+            &other.highest_advice_level_counts,
+        );
+        self.no_advice_count += other.no_advice_count;
+        merge_counts(&mut self.advice_type_counts, &other.advice_type_counts);
+        for (advice_type, level_counts) in &other.advice_type_level_counts {
+            merge_counts(
+                self.advice_type_level_counts
+                    .entry(advice_type.clone())
+                    .or_default(),
+                level_counts,
+            );
+        }
+        merge_counts(
+            &mut self.advice_message_counts,
+            &other.advice_message_counts,
+        );
+        merge_counts(
+            &mut self.seen_registry_attributes,
+            &other.seen_registry_attributes,
+        );
+        merge_counts(
+            &mut self.seen_non_registry_attributes,
+            &other.seen_non_registry_attributes,
+        );
+        merge_counts(
+            &mut self.seen_registry_metrics,
+            &other.seen_registry_metrics,
+        );
+        merge_counts(
+            &mut self.seen_non_registry_metrics,
+            &other.seen_non_registry_metrics,
+        );
+        if self.stopped_early.is_none() {
+            self.stopped_early.clone_from(&other.stopped_early);
+        }
+        if self.first_violation.is_none() {
+            self.first_violation.clone_from(&other.first_violation);
+        }
+    }
+
     /// Finalize the statistics
     pub fn finalize(&mut self) {
         // Calculate the registry coverage
@@ -474,6 +886,72 @@ impl LiveCheckStatistics {
             self.registry_coverage = 0.0;
         }
     }
+
+    /// Serialize the statistics as YAML, for tooling preferring it over JSON
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        serde_yaml::to_string(self).map_err(|e| Error::OutputError {
+            error: e.to_string(),
+        })
+    }
+
+    /// Render the statistics in the Prometheus text exposition format, e.g.
+    /// `weaver_live_check_advice_total{type="type_mismatch",level="violation"} 12`,
+    /// for conformance runs to be scraped. Series are sorted by their labels.
+    #[must_use]
+    pub fn to_prometheus_text(&self) -> String {
+        fn escape(label: &str) -> String {
+            label
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        }
+
+        let mut text = String::new();
+        text.push_str(
+            "# HELP weaver_live_check_advice_total The number of advice given by advice type and level.\n",
+        );
+        text.push_str("# TYPE weaver_live_check_advice_total counter\n");
+        let mut advice_counts: Vec<_> = self
+            .advice_type_level_counts
+            .iter()
+            .flat_map(|(advice_type, level_counts)| {
+                level_counts
+                    .iter()
+                    .map(move |(level, count)| (advice_type, level, count))
+            })
+            .collect();
+        advice_counts.sort();
+        for (advice_type, level, count) in advice_counts {
+            text.push_str(&format!(
+                "weaver_live_check_advice_total{{type=\"{}\",level=\"{}\"}} {count}\n",
+                escape(advice_type),
+                level.as_str()
+            ));
+        }
+
+        text.push_str(
+            "# HELP weaver_live_check_entities_total The number of sample entities checked by entity type.\n",
+        );
+        text.push_str("# TYPE weaver_live_check_entities_total counter\n");
+        let mut entity_counts: Vec<_> = self.total_entities_by_type.iter().collect();
+        entity_counts.sort();
+        for (entity_type, count) in entity_counts {
+            text.push_str(&format!(
+                "weaver_live_check_entities_total{{type=\"{}\"}} {count}\n",
+                escape(entity_type)
+            ));
+        }
+
+        text.push_str(
+            "# HELP weaver_live_check_registry_coverage The fraction of the registry covered by the samples.\n",
+        );
+        text.push_str("# TYPE weaver_live_check_registry_coverage gauge\n");
+        text.push_str(&format!(
+            "weaver_live_check_registry_coverage {}\n",
+            self.registry_coverage
+        ));
+        text
+    }
 }
 
 /// Samples implement this trait to run live checks on themselves
@@ -512,6 +990,11 @@ pub trait Advisable {
     /// Get entity type for statistics
     fn entity_type(&self) -> &str;
 
+    /// Advice given by the live checker itself on this entity, before the advisors run
+    fn builtin_advice(&self, _live_checker: &LiveChecker, _parent_signal: &Sample) -> Vec<Advice> {
+        Vec::new()
+    }
+
     /// Run advisors on this entity
     fn run_advisors(
         &mut self,
@@ -522,15 +1005,19 @@ pub trait Advisable {
     ) -> Result<LiveCheckResult, Error> {
         let mut result = LiveCheckResult::new();
 
-        for advisor in live_checker.advisors.iter_mut() {
-            let advice_list = advisor.advise(
-                self.as_sample_ref(),
-                parent_signal,
-                None,
-                parent_group.clone(),
-            )?;
-            result.add_advice_list(advice_list);
-        }
+        let mut advice_list =
+            live_checker.template_aggregation_advice(self.as_sample_ref(), parent_signal);
+        advice_list.extend(self.builtin_advice(live_checker, parent_signal));
+        let mut advice_list =
+            live_checker.process_advice(self.as_sample_ref(), parent_signal.source(), advice_list);
+        advice_list.extend(live_checker.run_advisors(
+            self.as_sample_ref(),
+            parent_signal,
+            None,
+            parent_group,
+        )?);
+        live_checker.order_advice(&mut advice_list);
+        result.add_advice_list(advice_list);
 
         stats.inc_entity_count(self.entity_type());
         stats.maybe_add_live_check_result(Some(&result));
@@ -546,3 +1033,11 @@ pub fn get_json_schema() -> Result<String, Error> {
         error: e.to_string(),
     })
 }
+
+/// Get the JSON schema for the Advice struct, as found in the live check reports
+pub fn get_advice_json_schema() -> Result<String, Error> {
+    let schema = schemars::schema_for!(Advice);
+    serde_json::to_string_pretty(&schema).map_err(|e| Error::OutputError {
+        error: e.to_string(),
+    })
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `live_check` annotation of registry attributes and groups, tuning the live
+//! check of their samples beyond what semantic conventions express. It is not part
+//! of the semantic conventions and is only read by the live check.
+//!
+//! On an attribute:
+//!
+//! ```yaml
+//! annotations:
+//!   live_check:
+//!     condition: '."http.request.method" == "GET"'  # jq condition making a conditionally required attribute required
+//!     open_enum: true                                 # the enum accepts custom values
+//!     identifying: true                               # a metric attribute identifying the time series
+//!     range: { min: 0, max: 65535 }                   # inclusive numeric bounds, either may be omitted
+//!     allowed_values: [GET, POST]                     # soft-constrained values of a non enum attribute
+//!     pattern: '^[a-z]+$'                             # regular expression string values must match
+//! ```
+//!
+//! On a group:
+//!
+//! ```yaml
+//! annotations:
+//!   live_check:
+//!     any_of:                                         # at least one attribute of each set is present
+//!       - [net.peer.name, net.peer.ip]
+//!     temporality: cumulative                         # expected temporality of a metric, `delta` or `cumulative`
+//!     value_type: int                                 # expected kind of number data points, `int` or `double`
+//! ```
+//!
+//! Unknown keys and values of the wrong shape make the whole annotation malformed.
+//! A malformed annotation is ignored and reported once by the live checker with an
+//! `invalid_live_check_annotation` advice.
+
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Number, Value};
+use weaver_semconv::YamlValue;
+
+use crate::{sample_metric::SampleTemporality, LIVE_CHECK_ANNOTATION};
+
+/// The `live_check` annotation of a registry attribute
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeAnnotation {
+    /// The jq condition under which a conditionally required attribute is required
+    pub condition: Option<String>,
+    /// Whether the enum accepts custom values
+    #[serde(default)]
+    pub open_enum: bool,
+    /// Whether the metric attribute is an identifying dimension of the time series
+    #[serde(default)]
+    pub identifying: bool,
+    /// The inclusive bounds of numeric values
+    pub range: Option<ValueRange>,
+    /// The soft-constrained values of an attribute that is not an enum
+    pub allowed_values: Option<Vec<Value>>,
+    /// The regular expression string values must match
+    pub pattern: Option<String>,
+}
+
+/// The `live_check` annotation of a registry group
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GroupAnnotation {
+    /// Sets of attributes of which at least one must be present
+    pub any_of: Option<Vec<Vec<String>>>,
+    /// The expected aggregation temporality of a metric
+    pub temporality: Option<SampleTemporality>,
+    /// The expected kind of the number data points of a metric
+    pub value_type: Option<NumberValueType>,
+}
+
+/// The inclusive bounds of numeric attribute values
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValueRange {
+    /// The minimum value, unbounded if not set
+    pub min: Option<Number>,
+    /// The maximum value, unbounded if not set
+    pub max: Option<Number>,
+}
+
+/// The kind of the number data points of a metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberValueType {
+    /// Integer values
+    Int,
+    /// Floating point values
+    Double,
+}
+
+/// Parse the `live_check` annotation from a set of annotations.
+///
+/// Returns `None` when there is no such annotation, an error describing the issue
+/// when it is malformed.
+pub fn parse_annotation<T: DeserializeOwned>(
+    annotations: Option<&BTreeMap<String, YamlValue>>,
+) -> Result<Option<T>, String> {
+    let Some(annotation) = annotations.and_then(|a| a.get(LIVE_CHECK_ANNOTATION)) else {
+        return Ok(None);
+    };
+    let value = serde_json::to_value(annotation).map_err(|e| e.to_string())?;
+    serde_json::from_value(value)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotations(yaml: &str) -> BTreeMap<String, YamlValue> {
+        BTreeMap::from([(
+            LIVE_CHECK_ANNOTATION.to_owned(),
+            YamlValue(serde_yaml::from_str(yaml).unwrap()),
+        )])
+    }
+
+    #[test]
+    fn test_parse_annotation() {
+        assert_eq!(parse_annotation::<AttributeAnnotation>(None), Ok(None));
+
+        let annotation: AttributeAnnotation =
+            parse_annotation(Some(&annotations("{open_enum: true, range: {max: 10}}")))
+                .unwrap()
+                .unwrap();
+        assert!(annotation.open_enum);
+        assert_eq!(annotation.range.unwrap().max, Some(Number::from(10)));
+
+        let annotation: GroupAnnotation = parse_annotation(Some(&annotations(
+            "{temporality: delta, value_type: double}",
+        )))
+        .unwrap()
+        .unwrap();
+        assert_eq!(annotation.temporality, Some(SampleTemporality::Delta));
+        assert_eq!(annotation.value_type, Some(NumberValueType::Double));
+
+        // Unknown keys, group keys on an attribute included, and wrong shapes are errors
+        for yaml in [
+            "{open_enums: true}",
+            "{any_of: [[a, b]]}",
+            "{open_enum: 'yes'}",
+            "{range: {min: a}}",
+        ] {
+            assert!(
+                parse_annotation::<AttributeAnnotation>(Some(&annotations(yaml))).is_err(),
+                "{yaml}"
+            );
+        }
+        assert!(
+            parse_annotation::<GroupAnnotation>(Some(&annotations("{temporality: both}"))).is_err()
+        );
+    }
+}
@@ -2,15 +2,223 @@
 
 //! Holds the registry, helper structs, and the advisors for the live check
 
-use serde::Serialize;
-use std::collections::HashMap;
+use globset::{Glob, GlobMatcher};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::path::Path;
 use std::rc::Rc;
-use weaver_semconv::{attribute::AttributeType, group::GroupType};
-
+use std::time::{Duration, Instant};
+use weaver_common::diagnostic::DiagnosticMessage;
+use weaver_common::result::WResult;
+use weaver_common::vdir::VirtualDirectoryPath;
+use weaver_semconv::{
+    attribute::{AttributeType, PrimitiveOrArrayTypeSpec, TemplateTypeSpec},
+    group::GroupType,
+    manifest::RegistryManifest,
+    registry_repo::RegistryRepo,
+    stability::Stability,
+};
+
+use weaver_checker::violation::{Advice, AdviceLevel, MatchedRef, SampleSource};
 use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
 use weaver_resolved_schema::attribute::Attribute;
 
-use crate::advice::Advisor;
+use crate::{
+    advice::{default_advisors, Advisor, CoercingTypeAdvisor, NumericCoercion, ScopedAdvisor},
+    advice_baseline::AdviceBaseline,
+    advice_type::BuiltinAdviceType,
+    live_check_annotation::{parse_annotation, AttributeAnnotation, GroupAnnotation},
+    message_catalog::{builtin_advice, builtin_advice_form, MessageCatalog},
+    otlp_document::{OtlpContext, OtlpDocument},
+    sample_attribute::SampleAttribute,
+    service_report::LiveCheckServiceReport,
+    BudgetResult, BudgetUsage, Error, FailFastStop, GroupCoverage, LiveCheckReport,
+    LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef, SignalType,
+    ADVICE_TYPE_ADVICE_CONTEXT_KEY, ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY, COUNT_ADVICE_CONTEXT_KEY,
+    FINGERPRINT_ADVICE_CONTEXT_KEY, GROUP_ID_ADVICE_CONTEXT_KEY, MESSAGE_ADVICE_CONTEXT_KEY,
+    REASON_ADVICE_CONTEXT_KEY, SIGNAL_ADVICE_ATTRIBUTE, TEMPLATE_NAME_ADVICE_CONTEXT_KEY,
+};
+
+/// Returns the element type of an array template type, `None` for scalar templates
+fn template_element_type(template_type: &TemplateTypeSpec) -> Option<PrimitiveOrArrayTypeSpec> {
+    match template_type {
+        TemplateTypeSpec::Strings => Some(PrimitiveOrArrayTypeSpec::String),
+        TemplateTypeSpec::Ints => Some(PrimitiveOrArrayTypeSpec::Int),
+        TemplateTypeSpec::Doubles => Some(PrimitiveOrArrayTypeSpec::Double),
+        TemplateTypeSpec::Booleans => Some(PrimitiveOrArrayTypeSpec::Boolean),
+        TemplateTypeSpec::Boolean
+        | TemplateTypeSpec::Int
+        | TemplateTypeSpec::Double
+        | TemplateTypeSpec::String
+        | TemplateTypeSpec::Any => None,
+    }
+}
+
+/// Returns the maturity rank of a stability level, the higher the more mature
+#[allow(deprecated)]
+fn stability_rank(stability: &Stability) -> u8 {
+    match stability {
+        Stability::Deprecated => 0,
+        Stability::Development => 1,
+        Stability::Alpha => 2,
+        Stability::Beta => 3,
+        Stability::ReleaseCandidate => 4,
+        Stability::Stable => 5,
+    }
+}
+
+/// Returns the stability of the attributes of a baseline registry by name
+fn baseline_stability(baseline_registry: &ResolvedRegistry) -> HashMap<String, Stability> {
+    baseline_registry
+        .groups
+        .iter()
+        .flat_map(|group| &group.attributes)
+        .filter_map(|attribute| {
+            attribute
+                .stability
+                .clone()
+                .map(|stability| (attribute.name.clone(), stability))
+        })
+        .collect()
+}
+
+/// Returns an `invalid_live_check_annotation` advice for each malformed `live_check`
+/// annotation of the registry, once per group and once per attribute name
+fn annotation_advice(registry: &ResolvedRegistry) -> Vec<Advice> {
+    let mut advice_list = Vec::new();
+    let mut seen_attributes = HashSet::new();
+    for group in &registry.groups {
+        if let Err(error) = parse_annotation::<GroupAnnotation>(group.annotations.as_ref()) {
+            advice_list.push(builtin_advice(
+                BuiltinAdviceType::InvalidLiveCheckAnnotation,
+                json!({
+                    GROUP_ID_ADVICE_CONTEXT_KEY: group.id,
+                    REASON_ADVICE_CONTEXT_KEY: error,
+                }),
+                AdviceLevel::Improvement,
+            ));
+        }
+        for attribute in &group.attributes {
+            if !seen_attributes.insert(attribute.name.as_str()) {
+                continue;
+            }
+            if let Err(error) =
+                parse_annotation::<AttributeAnnotation>(attribute.annotations.as_ref())
+            {
+                advice_list.push(builtin_advice_form(
+                    BuiltinAdviceType::InvalidLiveCheckAnnotation,
+                    "attribute",
+                    json!({
+                        ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: attribute.name,
+                        GROUP_ID_ADVICE_CONTEXT_KEY: group.id,
+                        REASON_ADVICE_CONTEXT_KEY: error,
+                    }),
+                    AdviceLevel::Improvement,
+                ));
+            }
+        }
+    }
+    advice_list
+}
+
+/// Tag each advice with the id of the advisor that emitted it
+fn with_advisor_id(advisor_id: &str, advice: Vec<Advice>) -> impl Iterator<Item = Advice> + '_ {
+    advice.into_iter().map(move |mut advice| {
+        advice.advisor_id = Some(advisor_id.to_owned());
+        advice
+    })
+}
+
+/// Tag the advice with its signal type in the `signal` attribute, unless already set
+fn with_signal_attribute(mut advice: Advice) -> Advice {
+    if let Some(signal_type) = &advice.signal_type {
+        let _ = advice
+            .attributes
+            .entry(SIGNAL_ADVICE_ATTRIBUTE.to_owned())
+            .or_insert_with(|| json!(signal_type));
+    }
+    advice
+}
+
+/// Collapse the advice with the same advice type, context and level into the first
+/// one, counting how many advisors gave it
+fn dedup_advice(advice_list: Vec<Advice>) -> Vec<Advice> {
+    let mut deduped: Vec<Advice> = Vec::new();
+    for advice in advice_list {
+        if let Some(existing) = deduped.iter_mut().find(|existing| {
+            existing.advice_type == advice.advice_type
+                && existing.advice_context == advice.advice_context
+                && existing.advice_level == advice.advice_level
+        }) {
+            existing.count = Some(existing.count.unwrap_or(1) + 1);
+        } else {
+            deduped.push(Advice {
+                count: Some(1),
+                ..advice
+            });
+        }
+    }
+    deduped
+}
+
+/// Returns the name of the sample an advice is about: the attribute name from the
+/// advice context, else the signal name
+fn advice_sample_name(advice: &Advice) -> &str {
+    advice
+        .advice_context
+        .get(ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY)
+        .and_then(|name| name.as_str())
+        .or(advice.signal_name.as_deref())
+        .unwrap_or_default()
+}
+
+/// Sort advice by decreasing level, then by advice type and sample name, for a
+/// deterministic output e.g. in golden files. The sort is stable: advice equal on
+/// these keys stays in the order it was given.
+pub fn sort_advice_list(advice_list: &mut [Advice]) {
+    advice_list.sort_by(|a, b| {
+        b.advice_level
+            .cmp(&a.advice_level)
+            .then_with(|| a.advice_type.cmp(&b.advice_type))
+            .then_with(|| advice_sample_name(a).cmp(advice_sample_name(b)))
+    });
+}
+
+/// The advice given on a sample by each advisor, `None` for the advisors that are not
+/// cacheable or did not run
+type CachedAdvice = Vec<Option<Vec<Advice>>>;
+
+/// Returns the key of a sample in the advice cache: the serialized sample fields
+/// (name, type, value, attributes...) except where it was read from, the type and
+/// name of the signal it belongs to and the registry attribute and group it matched.
+/// `None` if the sample can't be serialized.
+fn advice_cache_key(
+    sample: &SampleRef<'_>,
+    signal: &Sample,
+    registry_attribute: Option<&Attribute>,
+    registry_group: Option<&ResolvedGroup>,
+) -> Option<String> {
+    // The sample is serialized as `{"<sample type>": {<fields>}}`
+    let mut sample = serde_json::to_value(sample).ok()?;
+    if let Some(fields) = sample
+        .as_object_mut()
+        .and_then(|sample| sample.values_mut().next())
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        let _ = fields.remove("source");
+    }
+    serde_json::to_string(&(
+        sample,
+        signal.signal_type(),
+        signal.signal_name(),
+        registry_attribute.map(|attribute| &attribute.name),
+        registry_group.map(|group| &group.id),
+    ))
+    .ok()
+}
 
 /// Holds the registry, helper structs, and the advisors for the live check
 #[derive(Serialize)]
@@ -20,11 +228,804 @@ pub struct LiveChecker {
     semconv_attributes: HashMap<String, Rc<Attribute>>,
     semconv_templates: HashMap<String, Rc<Attribute>>,
     semconv_metrics: HashMap<String, Rc<ResolvedGroup>>,
+    #[serde(skip)]
+    semconv_resources: Vec<Rc<ResolvedGroup>>,
+    #[serde(skip)]
+    semconv_events: HashMap<String, Rc<ResolvedGroup>>,
+    /// The id of the first group listing each attribute
+    #[serde(skip)]
+    attribute_groups: HashMap<String, String>,
     /// The advisors to run
     #[serde(skip)]
     pub advisors: Vec<Box<dyn Advisor>>,
     #[serde(skip)]
     templates_by_length: Vec<(String, Rc<Attribute>)>,
+    #[serde(skip)]
+    suppressions: Vec<Suppression>,
+    #[serde(skip)]
+    experimental_opt_in: Option<HashSet<String>>,
+    #[serde(skip)]
+    strict: bool,
+    #[serde(skip)]
+    advice_level_override: AdviceLevelOverride,
+    #[serde(skip)]
+    message_catalog: MessageCatalog,
+    #[serde(skip)]
+    post_process: Vec<PostProcessHook>,
+    #[serde(skip)]
+    filter: NameFilter,
+    #[serde(skip)]
+    dedup: bool,
+    #[serde(skip)]
+    sort_advice: bool,
+    #[serde(skip)]
+    baseline_stability: HashMap<String, Stability>,
+    #[serde(skip)]
+    fail_fast: bool,
+    #[serde(skip)]
+    budget: Option<SeverityBudget>,
+    #[serde(skip)]
+    registry_meta: Option<RegistryMeta>,
+    #[serde(skip)]
+    advice_cache: Option<LruCache<String, CachedAdvice>>,
+    #[serde(skip)]
+    max_level: Option<AdviceLevel>,
+    #[serde(skip)]
+    match_strategy: MatchStrategy,
+    #[serde(skip)]
+    name_normalizer: NameNormalizer,
+    #[serde(skip)]
+    otlp_context: OtlpContext,
+    #[serde(skip)]
+    progress: Option<Progress>,
+    #[serde(skip)]
+    violation_count: usize,
+    #[serde(skip)]
+    seen_attributes: HashSet<String>,
+    #[serde(skip)]
+    type_mismatched_attributes: HashSet<String>,
+    #[serde(skip)]
+    advisor_timings: Option<AdvisorTimings>,
+    #[serde(skip)]
+    registry_origins: Option<RegistryOrigins>,
+    #[serde(skip)]
+    advice_baseline: Option<AdviceBaseline>,
+    #[serde(skip)]
+    observed_advice: Option<AdviceBaseline>,
+}
+
+/// A hook receiving the complete report once all the samples have been checked,
+/// before it is output. It can add derived advice, redact values or attach metadata.
+pub type PostProcessHook = Box<dyn Fn(&mut LiveCheckReport)>;
+
+/// A callback reporting the progress of a long run, given the number of top-level
+/// samples checked and the number of violations given so far.
+pub type ProgressCallback = Box<dyn FnMut(usize, usize)>;
+
+/// Invokes the progress callback every `every` checked samples
+struct Progress {
+    every: usize,
+    callback: ProgressCallback,
+    processed: usize,
+}
+
+impl Progress {
+    fn new(every: usize, callback: ProgressCallback) -> Self {
+        Progress {
+            every: every.max(1),
+            callback,
+            processed: 0,
+        }
+    }
+
+    fn sample_checked(&mut self, violations: usize) {
+        self.processed += 1;
+        if self.processed % self.every == 0 {
+            (self.callback)(self.processed, violations);
+        }
+    }
+}
+
+/// How a sample attribute is matched to a registry attribute.
+#[derive(Default)]
+pub enum MatchStrategy {
+    /// The registry attribute with the same name, then the longest template attribute
+    /// prefixing the name e.g. `http.request.header.accept` for `http.request.header`.
+    #[default]
+    Exact,
+    /// As [`MatchStrategy::Exact`], then the registry attribute with the longest name
+    /// prefixing the name at a `.` boundary, as if every attribute was a template
+    /// e.g. `db.system.name.suffix` for `db.system.name`. Useful for registries
+    /// sharing attribute namespaces.
+    Prefix,
+    /// A function mapping the sample attribute name to the name of the registry
+    /// attribute it matches, looked up as with [`MatchStrategy::Exact`].
+    /// For custom naming schemes.
+    Custom(Box<dyn Fn(&str) -> Option<String>>),
+}
+
+/// How a sample attribute name not matching any registry attribute is normalized
+/// before it is matched again, for producers not following the naming of the
+/// semantic conventions. Such matches get a `name_normalized_to_match` improvement
+/// advice.
+#[derive(Default)]
+pub enum NameNormalizer {
+    /// Names are not normalized
+    #[default]
+    None,
+    /// Hyphens become dots and camelCase becomes snake_case, e.g. `http-request-method`
+    /// is normalized to `http.request.method` and `http.requestMethod` to
+    /// `http.request_method`. Only ASCII letters are changed, whatever the locale.
+    Semconv,
+    /// A function normalizing the name, for custom naming schemes
+    Custom(Box<dyn Fn(&str) -> String>),
+}
+
+impl NameNormalizer {
+    /// Returns the normalized name, or None when normalization leaves it unchanged
+    #[must_use]
+    pub fn normalize(&self, name: &str) -> Option<String> {
+        let normalized_name = match self {
+            NameNormalizer::None => return None,
+            NameNormalizer::Semconv => {
+                let mut normalized_name = String::with_capacity(name.len());
+                let mut previous: Option<char> = None;
+                for c in name.chars() {
+                    if c == '-' {
+                        normalized_name.push('.');
+                    } else if c.is_ascii_uppercase() {
+                        if previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit()) {
+                            normalized_name.push('_');
+                        }
+                        normalized_name.push(c.to_ascii_lowercase());
+                    } else {
+                        normalized_name.push(c);
+                    }
+                    previous = Some(c);
+                }
+                normalized_name
+            }
+            NameNormalizer::Custom(normalize) => normalize(name),
+        };
+        (normalized_name != name).then_some(normalized_name)
+    }
+}
+
+/// Overrides the level of advice by advice type, whichever advisor gave it.
+///
+/// For example, to gate on recommended attributes:
+///
+/// ```yaml
+/// recommended_attribute_not_present: violation
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AdviceLevelOverride {
+    levels: HashMap<String, AdviceLevel>,
+}
+
+impl AdviceLevelOverride {
+    /// Create a new empty AdviceLevelOverride
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the level of an advice type
+    #[must_use]
+    pub fn with(mut self, advice_type: &str, level: AdviceLevel) -> Self {
+        let _ = self.levels.insert(advice_type.to_owned(), level);
+        self
+    }
+
+    /// Returns the level overriding the advice type, if any
+    #[must_use]
+    pub fn level(&self, advice_type: &str) -> Option<&AdviceLevel> {
+        self.levels.get(advice_type)
+    }
+}
+
+/// The maximum number of advice allowed by advice level over a run, for CI gating
+/// e.g. up to 5 improvements but no violation. See [`LiveChecker::check_budget`].
+///
+/// Violations are allowed none unless given a maximum, matching the failure of a run
+/// on any violation without a budget. The other levels are unlimited unless given one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeverityBudget {
+    max: BTreeMap<AdviceLevel, usize>,
+}
+
+impl Default for SeverityBudget {
+    fn default() -> Self {
+        SeverityBudget {
+            max: BTreeMap::from([(AdviceLevel::Violation, 0)]),
+        }
+    }
+}
+
+impl SeverityBudget {
+    /// Create a new SeverityBudget allowing no violation
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of advice allowed for an advice level
+    #[must_use]
+    pub fn with(mut self, level: AdviceLevel, max: usize) -> Self {
+        let _ = self.max.insert(level, max);
+        self
+    }
+
+    /// Check the advice counts of the statistics against the budget
+    #[must_use]
+    pub fn check(&self, stats: &LiveCheckStatistics) -> BudgetResult {
+        BudgetResult {
+            levels: self
+                .max
+                .iter()
+                .rev()
+                .map(|(level, &max)| {
+                    let count = stats
+                        .advice_level_counts
+                        .get(level)
+                        .copied()
+                        .unwrap_or_default();
+                    BudgetUsage {
+                        level: level.clone(),
+                        count,
+                        max,
+                        excess: count.saturating_sub(max),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The cumulative wall time spent in each advisor over a run, by advisor id,
+/// see [`LiveChecker::set_advisor_timing`]. Serialized in seconds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AdvisorTimings {
+    timings: BTreeMap<String, Duration>,
+}
+
+impl AdvisorTimings {
+    /// Add time spent in an advisor
+    pub fn add(&mut self, advisor_id: &str, elapsed: Duration) {
+        *self.timings.entry(advisor_id.to_owned()).or_default() += elapsed;
+    }
+
+    /// Returns the time spent in an advisor
+    #[must_use]
+    pub fn get(&self, advisor_id: &str) -> Option<Duration> {
+        self.timings.get(advisor_id).copied()
+    }
+
+    /// Iterate over the advisor ids and their time, by advisor id
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Duration)> {
+        self.timings.iter()
+    }
+}
+
+impl Serialize for AdvisorTimings {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.timings
+                .iter()
+                .map(|(advisor_id, elapsed)| (advisor_id, elapsed.as_secs_f64())),
+        )
+    }
+}
+
+/// The name of the registry defining each attribute, metric and group of registries
+/// merged with [`merge_registries`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegistryOrigins {
+    attributes: HashMap<String, String>,
+    groups: HashMap<String, String>,
+}
+
+impl RegistryOrigins {
+    /// Returns the name of the registry defining the attribute
+    #[must_use]
+    pub fn attribute(&self, attribute_name: &str) -> Option<&str> {
+        self.attributes.get(attribute_name).map(String::as_str)
+    }
+
+    /// Returns the name of the registry defining the group
+    #[must_use]
+    pub fn group(&self, group_id: &str) -> Option<&str> {
+        self.groups.get(group_id).map(String::as_str)
+    }
+}
+
+/// Load and resolve the semconv registry of a local YAML directory, as done by
+/// `weaver registry resolve`. Resolution warnings are ignored; other non-fatal
+/// errors fail the resolution.
+pub fn resolve_registry_dir(path: &Path) -> Result<ResolvedRegistry, Error> {
+    let resolution_error = |error: String| Error::RegistryResolutionError {
+        path: path.display().to_string(),
+        error,
+    };
+    let registry_path = VirtualDirectoryPath::LocalFolder {
+        path: path.display().to_string(),
+    };
+    let registry_repo = RegistryRepo::try_new("main", &registry_path)
+        .map_err(|e| resolution_error(e.to_string()))?;
+    match ResolvedRegistry::try_from_registry_repo(&registry_repo, false, false) {
+        WResult::Ok(registry) => Ok(registry),
+        WResult::OkWithNFEs(registry, non_fatal_errors) => {
+            let errors: Vec<&str> = non_fatal_errors
+                .iter()
+                .filter(|error| !error.is_warning())
+                .map(DiagnosticMessage::message)
+                .collect();
+            if errors.is_empty() {
+                Ok(registry)
+            } else {
+                Err(resolution_error(errors.join("\n")))
+            }
+        }
+        WResult::FatalErr(error) => Err(resolution_error(error.message().to_owned())),
+    }
+}
+
+/// Merge named registries into one to check samples spanning several registries,
+/// e.g. a private extension of the public semantic conventions.
+///
+/// The registries are given by decreasing precedence: an attribute, metric or group
+/// defined in several registries resolves to the definition of the first. Groups of
+/// the other registries with the same id are dropped. Returns the merged registry
+/// with the origins to give [`LiveCheckerBuilder::registry_origins`].
+#[must_use]
+pub fn merge_registries(
+    registries: Vec<(String, ResolvedRegistry)>,
+) -> (ResolvedRegistry, RegistryOrigins) {
+    let mut origins = RegistryOrigins::default();
+    let mut layers = Vec::new();
+    for (name, registry) in registries {
+        let groups: Vec<ResolvedGroup> = registry
+            .groups
+            .into_iter()
+            .filter(|group| !origins.groups.contains_key(&group.id))
+            .collect();
+        for group in &groups {
+            let _ = origins.groups.insert(group.id.clone(), name.clone());
+            for attribute in &group.attributes {
+                let _ = origins
+                    .attributes
+                    .entry(attribute.name.clone())
+                    .or_insert_with(|| name.clone());
+            }
+        }
+        layers.push((registry.registry_url, groups));
+    }
+    // The checker keeps the last definition of a name, the first registry goes last
+    let registry_url = layers
+        .first()
+        .map(|(registry_url, _)| registry_url.clone())
+        .unwrap_or_default();
+    let groups = layers
+        .into_iter()
+        .rev()
+        .flat_map(|(_, groups)| groups)
+        .collect();
+    (
+        ResolvedRegistry {
+            registry_url,
+            groups,
+        },
+        origins,
+    )
+}
+
+/// Builds a [`LiveChecker`], starting from the default advisors
+pub struct LiveCheckerBuilder {
+    registry: ResolvedRegistry,
+    advisors: Vec<Box<dyn Advisor>>,
+    suppressions: Vec<Suppression>,
+    experimental_opt_in: Option<HashSet<String>>,
+    strict: bool,
+    advice_level_override: AdviceLevelOverride,
+    message_catalog: MessageCatalog,
+    post_process: Vec<PostProcessHook>,
+    filter: NameFilter,
+    dedup: bool,
+    sort_advice: bool,
+    baseline_stability: HashMap<String, Stability>,
+    fail_fast: bool,
+    budget: Option<SeverityBudget>,
+    registry_meta: Option<RegistryMeta>,
+    advice_cache_capacity: Option<usize>,
+    match_strategy: MatchStrategy,
+    name_normalizer: NameNormalizer,
+    progress: Option<Progress>,
+    advisor_timing: bool,
+    registry_origins: Option<RegistryOrigins>,
+    advice_baseline: Option<AdviceBaseline>,
+    record_advice_baseline: bool,
+}
+
+impl LiveCheckerBuilder {
+    /// Create a new LiveCheckerBuilder with the default advisors
+    #[must_use]
+    pub fn new(registry: ResolvedRegistry) -> Self {
+        LiveCheckerBuilder {
+            registry,
+            advisors: default_advisors(),
+            suppressions: Vec::new(),
+            experimental_opt_in: None,
+            strict: false,
+            advice_level_override: AdviceLevelOverride::default(),
+            message_catalog: MessageCatalog::default(),
+            post_process: Vec::new(),
+            filter: NameFilter::default(),
+            dedup: false,
+            sort_advice: false,
+            baseline_stability: HashMap::new(),
+            fail_fast: false,
+            budget: None,
+            registry_meta: None,
+            advice_cache_capacity: None,
+            match_strategy: MatchStrategy::default(),
+            name_normalizer: NameNormalizer::default(),
+            progress: None,
+            advisor_timing: false,
+            registry_origins: None,
+            advice_baseline: None,
+            record_advice_baseline: false,
+        }
+    }
+
+    /// Remove the default advisors, only the advisors added afterwards are run
+    #[must_use]
+    pub fn without_default_advisors(mut self) -> Self {
+        self.advisors.clear();
+        self
+    }
+
+    /// Add an advisor
+    #[must_use]
+    pub fn with_advisor(mut self, advisor: Box<dyn Advisor>) -> Self {
+        self.advisors.push(advisor);
+        self
+    }
+
+    /// Add an advisor only running on the samples of `signal_types`
+    #[must_use]
+    pub fn with_scoped_advisor(
+        mut self,
+        signal_types: Vec<SignalType>,
+        advisor: Box<dyn Advisor>,
+    ) -> Self {
+        self.advisors
+            .push(Box::new(ScopedAdvisor::new(advisor, signal_types)));
+        self
+    }
+
+    /// Set how double values with no fractional part are treated for int attributes,
+    /// replacing the `type` advisors added so far with a [`CoercingTypeAdvisor`]
+    #[must_use]
+    pub fn numeric_coercion(mut self, numeric_coercion: NumericCoercion) -> Self {
+        for advisor in &mut self.advisors {
+            if advisor.id() == "type" {
+                *advisor = Box::new(CoercingTypeAdvisor::new(numeric_coercion));
+            }
+        }
+        self
+    }
+
+    /// Add a suppression
+    #[must_use]
+    pub fn suppress(mut self, suppression: Suppression) -> Self {
+        self.suppressions.push(suppression);
+        self
+    }
+
+    /// Set the experimental attributes opted in to
+    #[must_use]
+    pub fn experimental_opt_in(mut self, attribute_names: HashSet<String>) -> Self {
+        self.experimental_opt_in = Some(attribute_names);
+        self
+    }
+
+    /// In strict mode `improvement` advice is reported as `violation`
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Override the level of advice by advice type
+    #[must_use]
+    pub fn advice_level_override(mut self, advice_level_override: AdviceLevelOverride) -> Self {
+        self.advice_level_override = advice_level_override;
+        self
+    }
+
+    /// Replace the messages of advice by advice type
+    #[must_use]
+    pub fn message_catalog(mut self, message_catalog: MessageCatalog) -> Self {
+        self.message_catalog = message_catalog;
+        self
+    }
+
+    /// Add a hook run on the final report, hooks are run in the order they are added
+    #[must_use]
+    pub fn post_process(mut self, hook: PostProcessHook) -> Self {
+        self.post_process.push(hook);
+        self
+    }
+
+    /// Only run the attributes and metrics selected by the filter through the advisors
+    #[must_use]
+    pub fn filter(mut self, filter: NameFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Collapse identical advice given on a sample by several advisors into one,
+    /// with a `count` of the advisors that gave it. Disabled by default.
+    #[must_use]
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Sort the advice given on each sample, and the batch advice, see
+    /// [`sort_advice_list`]. Disabled by default, advice is then in the order the
+    /// advisors gave it.
+    #[must_use]
+    pub fn sort_advice(mut self, sort_advice: bool) -> Self {
+        self.sort_advice = sort_advice;
+        self
+    }
+
+    /// Compare the attribute stability with a baseline registry, attributes whose
+    /// stability was lowered get a `stability_regression` violation
+    #[must_use]
+    pub fn baseline_registry(mut self, baseline_registry: &ResolvedRegistry) -> Self {
+        self.baseline_stability = baseline_stability(baseline_registry);
+        self
+    }
+
+    /// Gate the run on a budget of advice by level, see [`LiveChecker::check_budget`]
+    #[must_use]
+    pub fn budget(mut self, budget: SeverityBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Stop the sample loop at the first sample given a violation, see
+    /// [`LiveChecker::check_sample`]. Disabled by default.
+    #[must_use]
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Set the registry metadata given to the Rego policies
+    #[must_use]
+    pub fn registry_meta(mut self, registry_meta: RegistryMeta) -> Self {
+        self.registry_meta = Some(registry_meta);
+        self
+    }
+
+    /// Reuse the advice computed for identical samples, keeping the advice of up to
+    /// `capacity` distinct samples, see [`LiveChecker::set_advice_cache`]
+    #[must_use]
+    pub fn advice_cache(mut self, capacity: usize) -> Self {
+        self.advice_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Set how sample attributes are matched to registry attributes, exact by default
+    #[must_use]
+    pub fn match_strategy(mut self, match_strategy: MatchStrategy) -> Self {
+        self.match_strategy = match_strategy;
+        self
+    }
+
+    /// Set how sample attribute names not in the registry are normalized before
+    /// being matched again, not normalized by default
+    #[must_use]
+    pub fn name_normalizer(mut self, name_normalizer: NameNormalizer) -> Self {
+        self.name_normalizer = name_normalizer;
+        self
+    }
+
+    /// Invoke `callback` every `every` checked samples, see [`LiveChecker::set_progress`]
+    #[must_use]
+    pub fn progress(mut self, every: usize, callback: ProgressCallback) -> Self {
+        self.progress = Some(Progress::new(every, callback));
+        self
+    }
+
+    /// Record the registry matched by the advice of merged registries, see
+    /// [`merge_registries`]
+    #[must_use]
+    pub fn registry_origins(mut self, registry_origins: RegistryOrigins) -> Self {
+        self.registry_origins = Some(registry_origins);
+        self
+    }
+
+    /// Record the time spent in each advisor, see [`LiveChecker::set_advisor_timing`]
+    #[must_use]
+    pub fn advisor_timing(mut self, advisor_timing: bool) -> Self {
+        self.advisor_timing = advisor_timing;
+        self
+    }
+
+    /// Only surface the advice not in the baseline, see [`LiveChecker::set_advice_baseline`]
+    #[must_use]
+    pub fn advice_baseline(mut self, advice_baseline: AdviceBaseline) -> Self {
+        self.advice_baseline = Some(advice_baseline);
+        self
+    }
+
+    /// Record the advice given, see [`LiveChecker::set_record_advice_baseline`]
+    #[must_use]
+    pub fn record_advice_baseline(mut self, record_advice_baseline: bool) -> Self {
+        self.record_advice_baseline = record_advice_baseline;
+        self
+    }
+
+    /// Build the LiveChecker
+    #[must_use]
+    pub fn build(self) -> LiveChecker {
+        let mut live_checker = LiveChecker::new(self.registry, self.advisors);
+        live_checker.suppressions = self.suppressions;
+        live_checker.experimental_opt_in = self.experimental_opt_in;
+        live_checker.strict = self.strict;
+        live_checker.advice_level_override = self.advice_level_override;
+        live_checker.message_catalog = self.message_catalog;
+        live_checker.post_process = self.post_process;
+        live_checker.filter = self.filter;
+        live_checker.dedup = self.dedup;
+        live_checker.sort_advice = self.sort_advice;
+        live_checker.baseline_stability = self.baseline_stability;
+        live_checker.fail_fast = self.fail_fast;
+        live_checker.budget = self.budget;
+        live_checker.registry_meta = self.registry_meta;
+        if let Some(capacity) = self.advice_cache_capacity {
+            live_checker.set_advice_cache(capacity);
+        }
+        live_checker.match_strategy = self.match_strategy;
+        live_checker.name_normalizer = self.name_normalizer;
+        live_checker.progress = self.progress;
+        live_checker.set_advisor_timing(self.advisor_timing);
+        live_checker.registry_origins = self.registry_origins;
+        live_checker.set_record_advice_baseline(self.record_advice_baseline);
+        if let Some(advice_baseline) = self.advice_baseline {
+            live_checker.set_advice_baseline(advice_baseline);
+        }
+        live_checker
+    }
+}
+
+/// Suppresses an advice type for the attributes or metrics matching a name pattern
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    name_matcher: GlobMatcher,
+    advice_type: String,
+}
+
+impl Suppression {
+    /// Create a new Suppression from a glob pattern (e.g. `legacy.*`) on the
+    /// attribute or metric name and the advice type to suppress
+    pub fn new(name_pattern: &str, advice_type: &str) -> Result<Self, Error> {
+        let name_matcher = Glob::new(name_pattern)
+            .map_err(|e| Error::InvalidSuppressionPattern {
+                pattern: name_pattern.to_owned(),
+                error: e.to_string(),
+            })?
+            .compile_matcher();
+        Ok(Suppression {
+            name_matcher,
+            advice_type: advice_type.to_owned(),
+        })
+    }
+
+    /// Returns true if the advice given on the named attribute or metric is suppressed
+    #[must_use]
+    pub fn matches(&self, name: &str, advice: &Advice) -> bool {
+        advice.advice_type == self.advice_type.as_str() && self.name_matcher.is_match(name)
+    }
+}
+
+/// Registry level metadata given to the Rego policies as `input.registry_meta`,
+/// e.g. to branch on the semantic conventions version
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegistryMeta {
+    /// The schema url of the registry
+    pub schema_url: Option<String>,
+    /// The semantic conventions version of the registry
+    pub version: Option<String>,
+}
+
+impl From<&RegistryManifest> for RegistryMeta {
+    fn from(manifest: &RegistryManifest) -> Self {
+        let version =
+            (!manifest.semconv_version.is_empty()).then(|| manifest.semconv_version.clone());
+        let schema_url = match &version {
+            Some(version) if !manifest.schema_base_url.is_empty() => Some(format!(
+                "{}/{}",
+                manifest.schema_base_url.trim_end_matches('/'),
+                version
+            )),
+            _ => None,
+        };
+        RegistryMeta {
+            schema_url,
+            version,
+        }
+    }
+}
+
+/// Selects the attributes and metrics checked by name, with glob patterns
+/// (e.g. `db.*`). Samples not selected are skipped entirely: they get no advice
+/// and are not counted in the statistics.
+#[derive(Debug, Clone, Default)]
+pub struct NameFilter {
+    include: Vec<GlobMatcher>,
+    exclude: Vec<GlobMatcher>,
+}
+
+impl NameFilter {
+    /// Create a new NameFilter selecting every name
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include the names matching the pattern. Once a pattern is included, only
+    /// the names matching an included pattern are selected.
+    pub fn include(mut self, name_pattern: &str) -> Result<Self, Error> {
+        self.include.push(Self::matcher(name_pattern)?);
+        Ok(self)
+    }
+
+    /// Exclude the names matching the pattern, even if they are included
+    pub fn exclude(mut self, name_pattern: &str) -> Result<Self, Error> {
+        self.exclude.push(Self::matcher(name_pattern)?);
+        Ok(self)
+    }
+
+    /// Returns true if the attribute or metric name is selected
+    #[must_use]
+    pub fn is_selected(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|m| m.is_match(name)))
+            && !self.exclude.iter().any(|m| m.is_match(name))
+    }
+
+    fn matcher(name_pattern: &str) -> Result<GlobMatcher, Error> {
+        Ok(Glob::new(name_pattern)
+            .map_err(|e| Error::InvalidFilterPattern {
+                pattern: name_pattern.to_owned(),
+                error: e.to_string(),
+            })?
+            .compile_matcher())
+    }
+}
+
+/// The name of the attribute or metric an advice applies to
+fn advice_subject_name<'a>(
+    sample: Option<&'a SampleRef<'_>>,
+    advice: &'a Advice,
+) -> Option<&'a str> {
+    if let Some(attribute_name) = advice
+        .advice_context
+        .get(ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY)
+        .and_then(|name| name.as_str())
+    {
+        return Some(attribute_name);
+    }
+    match sample {
+        Some(SampleRef::Attribute(attribute)) => Some(&attribute.name),
+        Some(SampleRef::Metric(metric)) => Some(&metric.name),
+        _ => advice.signal_name.as_deref(),
+    }
 }
 
 impl LiveChecker {
@@ -37,6 +1038,12 @@ impl LiveChecker {
         let mut templates_by_length = Vec::new();
         // Hashmap of metrics by name
         let mut semconv_metrics = HashMap::new();
+        // Resource groups
+        let mut semconv_resources = Vec::new();
+        // Hashmap of events by name
+        let mut semconv_events = HashMap::new();
+        // Hashmap of the first group listing each attribute
+        let mut attribute_groups = HashMap::new();
 
         for group in &registry.groups {
             if group.r#type == GroupType::Metric {
@@ -45,7 +1052,18 @@ impl LiveChecker {
                     let _ = semconv_metrics.insert(metric_name.clone(), group_rc);
                 }
             }
+            if group.r#type == GroupType::Entity {
+                semconv_resources.push(Rc::new(group.clone()));
+            }
+            if group.r#type == GroupType::Event {
+                if let Some(event_name) = &group.name {
+                    let _ = semconv_events.insert(event_name.clone(), Rc::new(group.clone()));
+                }
+            }
             for attribute in &group.attributes {
+                let _ = attribute_groups
+                    .entry(attribute.name.clone())
+                    .or_insert_with(|| group.id.clone());
                 let attribute_rc = Rc::new(attribute.clone());
                 match attribute.r#type {
                     AttributeType::Template(_) => {
@@ -67,1104 +1085,871 @@ impl LiveChecker {
             semconv_attributes,
             semconv_templates,
             semconv_metrics,
+            semconv_resources,
+            semconv_events,
+            attribute_groups,
             advisors,
             templates_by_length,
+            suppressions: Vec::new(),
+            experimental_opt_in: None,
+            strict: false,
+            advice_level_override: AdviceLevelOverride::default(),
+            message_catalog: MessageCatalog::default(),
+            post_process: Vec::new(),
+            filter: NameFilter::default(),
+            dedup: false,
+            sort_advice: false,
+            baseline_stability: HashMap::new(),
+            fail_fast: false,
+            budget: None,
+            registry_meta: None,
+            advice_cache: None,
+            max_level: None,
+            match_strategy: MatchStrategy::default(),
+            name_normalizer: NameNormalizer::default(),
+            otlp_context: OtlpContext::default(),
+            progress: None,
+            violation_count: 0,
+            seen_attributes: HashSet::new(),
+            type_mismatched_attributes: HashSet::new(),
+            advisor_timings: None,
+            registry_origins: None,
+            advice_baseline: None,
+            observed_advice: None,
         }
     }
 
+    /// Create a LiveCheckerBuilder for the registry, starting from the default advisors
+    #[must_use]
+    pub fn builder(registry: ResolvedRegistry) -> LiveCheckerBuilder {
+        LiveCheckerBuilder::new(registry)
+    }
+
+    /// Create a new LiveChecker with the default advisors for the semconv registry
+    /// of a local YAML directory, resolved with [`resolve_registry_dir`]. For one-shot
+    /// checks without an already resolved registry.
+    pub fn from_registry_dir(path: &Path) -> Result<Self, Error> {
+        Ok(LiveChecker::new(
+            resolve_registry_dir(path)?,
+            default_advisors(),
+        ))
+    }
+
     /// Add an advisor
     pub fn add_advisor(&mut self, advisor: Box<dyn Advisor>) {
         self.advisors.push(advisor);
     }
 
-    /// Find an attribute in the registry
-    #[must_use]
-    pub fn find_attribute(&self, name: &str) -> Option<Rc<Attribute>> {
-        self.semconv_attributes.get(name).map(Rc::clone)
+    /// Add a suppression, matching advice is dropped before it is returned
+    pub fn add_suppression(&mut self, suppression: Suppression) {
+        self.suppressions.push(suppression);
     }
 
-    /// Find a metric in the registry
-    #[must_use]
-    pub fn find_metric(&self, name: &str) -> Option<Rc<ResolvedGroup>> {
-        self.semconv_metrics.get(name).map(Rc::clone)
+    /// Set the advice level overrides, applied to the advice of all the advisors
+    pub fn set_advice_level_override(&mut self, advice_level_override: AdviceLevelOverride) {
+        self.advice_level_override = advice_level_override;
     }
 
-    /// Find a template in the registry
-    #[must_use]
-    pub fn find_template(&self, attribute_name: &str) -> Option<Rc<Attribute>> {
-        // Use the pre-sorted list to find the first (longest) matching template
-        for (template_name, attribute) in &self.templates_by_length {
-            if attribute_name.starts_with(template_name) {
-                return Some(Rc::clone(attribute));
-            }
-        }
-        None
+    /// Collapse identical advice given on a sample by several advisors into one,
+    /// with a `count` of the advisors that gave it
+    pub fn set_dedup(&mut self, dedup: bool) {
+        self.dedup = dedup;
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::fs::File;
-
-    use crate::{
-        advice::{DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, StabilityAdvisor, TypeAdvisor},
-        sample_attribute::SampleAttribute,
-        sample_metric::{
-            DataPoints, SampleExemplar, SampleExponentialHistogramDataPoint, SampleInstrument,
-            SampleMetric, SampleNumberDataPoint,
-        },
-        LiveCheckRunner, LiveCheckStatistics, Sample,
-    };
 
-    use super::*;
-    use serde_json::json;
-    use weaver_checker::violation::{Advice, AdviceLevel};
-    use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
-    use weaver_resolved_schema::attribute::Attribute;
-    use weaver_semconv::{
-        attribute::{
-            AttributeType, EnumEntriesSpec, Examples, PrimitiveOrArrayTypeSpec, RequirementLevel,
-            TemplateTypeSpec, ValueSpec,
-        },
-        group::{GroupType, InstrumentSpec, SpanKindSpec},
-        stability::Stability,
-    };
+    /// Sort the advice given on each sample, and the batch advice, see
+    /// [`sort_advice_list`]
+    pub fn set_sort_advice(&mut self, sort_advice: bool) {
+        self.sort_advice = sort_advice;
+    }
 
-    fn get_all_advice(sample: &mut Sample) -> &mut [Advice] {
-        match sample {
-            Sample::Attribute(sample_attribute) => sample_attribute
-                .live_check_result
-                .as_mut() // Change to as_mut() to get a mutable reference
-                .map(|result| &mut result.all_advice)
-                .map_or(&mut [], |v| v),
-            _ => &mut [],
+    /// Sort the final advice list of a sample when sorting is enabled
+    pub(crate) fn order_advice(&self, advice_list: &mut [Advice]) {
+        if self.sort_advice {
+            sort_advice_list(advice_list);
         }
     }
 
-    #[test]
-    fn test_attribute_live_checker() {
-        let registry = make_registry();
-
-        let mut samples = vec![
-            Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("testString2").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.deprecated=42").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("aws.s3.bucket.name").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.enum=foo").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.enum=example_variant1").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.enum=42.42").unwrap()),
-            Sample::Attribute(
-                SampleAttribute::try_from("test.string.not.allowed=example_value").unwrap(),
-            ),
-            Sample::Attribute(SampleAttribute::try_from("test.extends=new_value").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.template.my.key=42").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.deprecated.allowed=42").unwrap()),
-        ];
-
-        let advisors: Vec<Box<dyn Advisor>> = vec![
-            Box::new(DeprecatedAdvisor),
-            Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
-            Box::new(EnumAdvisor),
-        ];
-
-        let mut live_checker = LiveChecker::new(registry, advisors);
-        let rego_advisor =
-            RegoAdvisor::new(&live_checker, &None, &None).expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
-        }
-        stats.finalize();
-
-        let all_advice = get_all_advice(&mut samples[0]);
-        assert!(all_advice.is_empty());
-
-        let all_advice = get_all_advice(&mut samples[1]);
-        assert_eq!(all_advice.len(), 3);
-        // make a sort of the advice
-        all_advice.sort_by(|a, b| a.advice_type.cmp(&b.advice_type));
-        assert_eq!(all_advice[0].advice_type, "invalid_format");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "testString2" })
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'testString2' does not match name formatting rules."
-        );
-        assert_eq!(all_advice[1].advice_type, "missing_attribute");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "testString2"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Attribute 'testString2' does not exist in the registry."
-        );
-        assert_eq!(all_advice[2].advice_type, "missing_namespace");
-        assert_eq!(
-            all_advice[2].advice_context,
-            json!({"attribute_name": "testString2"})
-        );
-        assert_eq!(all_advice[2].message, "Attribute name 'testString2' must include a namespace (e.g. '{namespace}.{attribute_key}')");
-
-        let all_advice = get_all_advice(&mut samples[2]);
-        assert_eq!(all_advice.len(), 3);
-        assert_eq!(all_advice[0].advice_type, "deprecated");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.deprecated", "deprecation_reason": "uncategorized", "deprecation_note": "note"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'test.deprecated' is deprecated; reason = 'uncategorized', note = 'note'."
-        );
+    /// Set the message templates replacing the messages of advice by advice type
+    pub fn set_message_catalog(&mut self, message_catalog: MessageCatalog) {
+        self.message_catalog = message_catalog;
+    }
 
-        assert_eq!(all_advice[1].advice_type, "not_stable");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "test.deprecated", "stability": "development"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Attribute 'test.deprecated' is not stable; stability = development."
-        );
+    /// Set how sample attributes are matched to registry attributes
+    pub fn set_match_strategy(&mut self, match_strategy: MatchStrategy) {
+        self.match_strategy = match_strategy;
+    }
 
-        assert_eq!(all_advice[2].advice_type, "type_mismatch");
-        assert_eq!(
-            all_advice[2].advice_context,
-            json!({"attribute_name": "test.deprecated", "attribute_type": "int", "expected": "string"})
-        );
-        assert_eq!(
-            all_advice[2].message,
-            "Attribute 'test.deprecated' has type 'int'. Type should be 'string'."
-        );
+    /// Set how sample attribute names not in the registry are normalized before
+    /// being matched again
+    pub fn set_name_normalizer(&mut self, name_normalizer: NameNormalizer) {
+        self.name_normalizer = name_normalizer;
+    }
 
-        let all_advice = get_all_advice(&mut samples[3]);
-        assert_eq!(all_advice.len(), 1);
-        assert_eq!(all_advice[0].advice_type, "missing_attribute");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "aws.s3.bucket.name"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'aws.s3.bucket.name' does not exist in the registry."
-        );
+    /// Set the origins of the attributes and groups of merged registries. The advice
+    /// given on a sample matching a registry attribute or group then carries the
+    /// name of the registry defining it.
+    pub fn set_registry_origins(&mut self, registry_origins: RegistryOrigins) {
+        self.registry_origins = Some(registry_origins);
+    }
 
-        let all_advice = get_all_advice(&mut samples[4]);
-        assert_eq!(all_advice.len(), 1);
-        assert_eq!(all_advice[0].advice_type, "undefined_enum_variant");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.enum", "attribute_value": "foo"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Enum attribute 'test.enum' has value 'foo' which is not documented."
-        );
+    /// Set the registry metadata given to the Rego policies
+    pub fn set_registry_meta(&mut self, registry_meta: RegistryMeta) {
+        self.registry_meta = Some(registry_meta);
+    }
 
-        let all_advice = get_all_advice(&mut samples[6]);
-        assert_eq!(all_advice.len(), 1);
-        assert_eq!(all_advice[0].advice_type, "type_mismatch");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.enum", "attribute_type": "double"})
-        );
-        assert_eq!(all_advice[0].message, "Enum attribute 'test.enum' has type 'double'. Enum value type should be 'string' or 'int'.");
+    /// Returns the registry metadata given to the Rego policies, if known
+    #[must_use]
+    pub fn registry_meta(&self) -> Option<&RegistryMeta> {
+        self.registry_meta.as_ref()
+    }
+
+    /// Returns the worst advice level given so far, after suppressions and level
+    /// overrides, following the [`AdviceLevel`] ordering. `None` when no advice was given.
+    #[must_use]
+    pub fn max_level(&self) -> Option<&AdviceLevel> {
+        self.max_level.as_ref()
+    }
 
-        let all_advice = get_all_advice(&mut samples[7]);
+    fn observe_levels(&mut self, advice_list: &[Advice]) {
+        let max_level = advice_list
+            .iter()
+            .map(|advice| &advice.advice_level)
+            .chain(self.max_level.as_ref())
+            .max()
+            .cloned();
+        self.max_level = max_level;
+        self.violation_count += advice_list
+            .iter()
+            .filter(|advice| advice.advice_level == AdviceLevel::Violation)
+            .count();
+    }
 
-        // Make a sort of the advice
-        all_advice.sort_by(|a, b| a.advice_type.cmp(&b.advice_type));
-        assert_eq!(all_advice.len(), 3);
+    /// Reuse the advice computed for identical samples instead of running the
+    /// advisors again, keeping the advice of up to `capacity` distinct samples.
+    ///
+    /// Samples are identified by their serialized fields. Only the advice of the
+    /// [cacheable](Advisor::is_cacheable) advisors is reused, the other advisors, such
+    /// as batch advisors, run on every sample. The least recently used sample is
+    /// evicted once full, a `capacity` of 0 disables the cache.
+    pub fn set_advice_cache(&mut self, capacity: usize) {
+        self.advice_cache = NonZeroUsize::new(capacity).map(LruCache::new);
+    }
 
-        assert_eq!(all_advice[0].advice_type, "extends_namespace");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test", "namespace": "test"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute name 'test.string.not.allowed' collides with existing namespace 'test'"
-        );
-        assert_eq!(all_advice[1].advice_type, "illegal_namespace");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "test.string.not.allowed", "namespace": "test.string"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Namespace 'test.string' collides with existing attribute 'test.string.not.allowed'"
-        );
-        assert_eq!(all_advice[2].advice_type, "missing_attribute");
-        assert_eq!(
-            all_advice[2].advice_context,
-            json!({
-                "attribute_name": "test.string.not.allowed"
+    /// Record that a registry attribute was seen in a sample, with a type mismatch
+    /// or not, for the [`LiveChecker::group_coverage`]
+    pub fn observe_attribute(&mut self, attribute_name: &str, type_mismatched: bool) {
+        if !self.seen_attributes.contains(attribute_name) {
+            let _ = self.seen_attributes.insert(attribute_name.to_owned());
+        }
+        if type_mismatched && !self.type_mismatched_attributes.contains(attribute_name) {
+            let _ = self
+                .type_mismatched_attributes
+                .insert(attribute_name.to_owned());
+        }
+    }
+
+    /// Returns the coverage of the attributes of each registry group by group id,
+    /// from the samples checked so far
+    #[must_use]
+    pub fn group_coverage(&self) -> BTreeMap<String, GroupCoverage> {
+        self.registry
+            .groups
+            .iter()
+            .map(|group| {
+                let mut coverage = GroupCoverage::default();
+                for attribute in &group.attributes {
+                    if self.seen_attributes.contains(&attribute.name) {
+                        coverage.seen += 1;
+                    } else {
+                        coverage.missing += 1;
+                    }
+                    if self.type_mismatched_attributes.contains(&attribute.name) {
+                        coverage.type_mismatched += 1;
+                    }
+                }
+                (group.id.clone(), coverage)
             })
-        );
-        assert_eq!(
-            all_advice[2].message,
-            "Attribute 'test.string.not.allowed' does not exist in the registry."
-        );
+            .collect()
+    }
 
-        let all_advice = get_all_advice(&mut samples[8]);
-        assert_eq!(all_advice.len(), 2);
-        assert_eq!(all_advice[0].advice_type, "missing_attribute");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.extends"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'test.extends' does not exist in the registry."
-        );
-        assert_eq!(all_advice[1].advice_type, "extends_namespace");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "test", "namespace": "test"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Attribute name 'test.extends' collides with existing namespace 'test'"
-        );
+    /// Invoke `callback` every `every` top-level samples checked with
+    /// [`LiveChecker::check_sample`], with the number of samples checked and the
+    /// number of violations given so far. An `every` of 0 is taken as 1.
+    pub fn set_progress(&mut self, every: usize, callback: ProgressCallback) {
+        self.progress = Some(Progress::new(every, callback));
+    }
 
-        // test.template
-        let all_advice = get_all_advice(&mut samples[9]);
-        assert_eq!(all_advice.len(), 2);
-        assert_eq!(all_advice[0].advice_type, "template_attribute");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.template.my.key", "template_name": "test.template"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'test.template.my.key' is a template"
-        );
-        assert_eq!(all_advice[1].advice_type, "type_mismatch");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "test.template.my.key", "attribute_type": "int", "expected": "string"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Attribute 'test.template.my.key' has type 'int'. Type should be 'string'."
-        );
+    /// Record the cumulative wall time spent in each advisor, off by default.
+    /// Turning it on resets the timings.
+    pub fn set_advisor_timing(&mut self, advisor_timing: bool) {
+        self.advisor_timings = advisor_timing.then(AdvisorTimings::default);
+    }
 
-        // test.deprecated.allowed
-        // Should not get illegal_namespace for extending a deprecated attribute
-        let all_advice = get_all_advice(&mut samples[10]);
-        assert_eq!(all_advice.len(), 2);
-        assert_eq!(all_advice[0].advice_type, "missing_attribute");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.deprecated.allowed"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'test.deprecated.allowed' does not exist in the registry."
-        );
-        assert_eq!(all_advice[1].advice_type, "extends_namespace");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "test", "namespace": "test"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Attribute name 'test.deprecated.allowed' collides with existing namespace 'test'"
-        );
+    /// Returns the time spent in each advisor so far, when timing is on
+    #[must_use]
+    pub fn advisor_timings(&self) -> Option<&AdvisorTimings> {
+        self.advisor_timings.as_ref()
+    }
 
-        // Check statistics
-        assert_eq!(stats.total_entities, 11);
-        assert_eq!(stats.total_advisories, 18);
-        assert_eq!(stats.advice_level_counts.len(), 3);
-        assert_eq!(stats.advice_level_counts[&AdviceLevel::Violation], 11);
-        assert_eq!(stats.advice_level_counts[&AdviceLevel::Information], 5);
-        assert_eq!(stats.advice_level_counts[&AdviceLevel::Improvement], 2);
-        assert_eq!(stats.highest_advice_level_counts.len(), 2);
-        assert_eq!(
-            stats.highest_advice_level_counts[&AdviceLevel::Violation],
-            8
-        );
-        assert_eq!(
-            stats.highest_advice_level_counts[&AdviceLevel::Information],
-            1
-        );
-        assert_eq!(stats.no_advice_count, 2);
-        assert_eq!(stats.seen_registry_attributes.len(), 3);
-        assert_eq!(stats.seen_registry_attributes["test.enum"], 3);
-        assert_eq!(stats.seen_non_registry_attributes.len(), 6);
-        assert_eq!(stats.registry_coverage, 1.0);
+    /// Run the live check on a nested OTLP document, sample by sample in document
+    /// order with [`LiveChecker::check_sample`], the samples being indexed from 0.
+    /// The context is reset once done.
+    ///
+    /// Returns true when the sample loop stopped early, see [`LiveChecker::check_sample`].
+    pub fn check_otlp_document(
+        &mut self,
+        document: &mut OtlpDocument,
+        stats: &mut LiveCheckStatistics,
+    ) -> Result<bool, Error> {
+        let mut sample_index = 0;
+        let mut check = |live_checker: &mut Self, sample: &mut Sample| {
+            let stop = live_checker.check_sample(sample_index, sample, stats);
+            sample_index += 1;
+            stop
+        };
+        let mut stop = false;
+        'resources: for otlp_resource in &mut document.resources {
+            let mut sample = Sample::Resource(otlp_resource.resource.clone());
+            stop = check(self, &mut sample)?;
+            if let Sample::Resource(resource) = sample {
+                otlp_resource.resource = resource;
+            }
+            if stop {
+                break;
+            }
+            for otlp_scope in &mut otlp_resource.scopes {
+                let mut sample = Sample::Scope(otlp_scope.scope.clone());
+                stop = check(self, &mut sample)?;
+                if let Sample::Scope(scope) = sample {
+                    otlp_scope.scope = scope;
+                }
+                if stop {
+                    break 'resources;
+                }
+                for signal in &mut otlp_scope.signals {
+                    stop = check(self, signal)?;
+                    if stop {
+                        break 'resources;
+                    }
+                }
+            }
+        }
+        self.enter_context(OtlpContext::default());
+        Ok(stop)
     }
 
-    fn make_registry() -> ResolvedRegistry {
-        ResolvedRegistry {
-            registry_url: "TEST".to_owned(),
-            groups: vec![ResolvedGroup {
-                id: "test.comprehensive.internal".to_owned(),
-                r#type: GroupType::Span,
-                brief: "".to_owned(),
-                note: "".to_owned(),
-                prefix: "".to_owned(),
-                entity_associations: vec![],
-                extends: None,
-                stability: Some(Stability::Stable),
-                deprecated: None,
-                attributes: vec![
-                    Attribute {
-                        name: "test.string".to_owned(),
-                        r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                        examples: Some(Examples::Strings(vec![
-                            "value1".to_owned(),
-                            "value2".to_owned(),
-                        ])),
-                        brief: "".to_owned(),
-                        tag: None,
-                        requirement_level: RequirementLevel::Recommended {
-                            text: "".to_owned(),
-                        },
-                        sampling_relevant: None,
-                        note: "".to_owned(),
-                        stability: Some(Stability::Stable),
-                        deprecated: None,
-                        prefix: false,
-                        tags: None,
-                        value: None,
-                        annotations: None,
-                        role: Default::default(),
-                    },
-                    Attribute {
-                        name: "test.enum".to_owned(),
-                        r#type: AttributeType::Enum {
-                            members: vec![
-                                EnumEntriesSpec {
-                                    id: "test_enum_member".to_owned(),
-                                    value: ValueSpec::String("example_variant1".to_owned()),
-                                    brief: None,
-                                    note: None,
-                                    stability: Some(Stability::Stable),
-                                    deprecated: None,
-                                    annotations: None,
-                                },
-                                EnumEntriesSpec {
-                                    id: "test_enum_member2".to_owned(),
-                                    value: ValueSpec::String("example_variant2".to_owned()),
-                                    brief: None,
-                                    note: None,
-                                    stability: Some(Stability::Stable),
-                                    deprecated: None,
-                                    annotations: None,
-                                },
-                            ],
-                        },
-                        examples: None,
-                        brief: "".to_owned(),
-                        tag: None,
-                        requirement_level: RequirementLevel::Recommended {
-                            text: "".to_owned(),
-                        },
-                        sampling_relevant: None,
-                        note: "".to_owned(),
-                        stability: Some(Stability::Stable),
-                        deprecated: None,
-                        prefix: false,
-                        tags: None,
-                        value: None,
-                        annotations: None,
-                        role: Default::default(),
-                    },
-                    Attribute {
-                        name: "test.deprecated".to_owned(),
-                        r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                        examples: Some(Examples::Strings(vec![
-                            "value1".to_owned(),
-                            "value2".to_owned(),
-                        ])),
-                        brief: "".to_owned(),
-                        tag: None,
-                        requirement_level: RequirementLevel::Recommended {
-                            text: "".to_owned(),
-                        },
-                        sampling_relevant: None,
-                        note: "".to_owned(),
-                        stability: Some(Stability::Development),
-                        deprecated: Some(weaver_semconv::deprecated::Deprecated::Uncategorized {
-                            note: "note".to_owned(),
-                        }),
-                        prefix: false,
-                        tags: None,
-                        value: None,
-                        annotations: None,
-                        role: Default::default(),
-                    },
-                    Attribute {
-                        name: "test.template".to_owned(),
-                        r#type: AttributeType::Template(TemplateTypeSpec::String),
-                        examples: Some(Examples::Strings(vec![
-                            "value1".to_owned(),
-                            "value2".to_owned(),
-                        ])),
-                        brief: "".to_owned(),
-                        tag: None,
-                        requirement_level: RequirementLevel::Recommended {
-                            text: "".to_owned(),
-                        },
-                        sampling_relevant: None,
-                        note: "".to_owned(),
-                        stability: Some(Stability::Stable),
-                        deprecated: None,
-                        prefix: false,
-                        tags: None,
-                        value: None,
-                        annotations: None,
-                        role: Default::default(),
-                    },
-                ],
-                span_kind: Some(SpanKindSpec::Internal),
-                events: vec![],
-                metric_name: None,
-                instrument: None,
-                unit: None,
-                name: None,
-                lineage: None,
-                display_name: None,
-                body: None,
-                annotations: None,
-            }],
+    /// Returns the resource and instrumentation scope of the samples being checked,
+    /// see [`LiveChecker::check_sample`]
+    #[must_use]
+    pub fn otlp_context(&self) -> &OtlpContext {
+        &self.otlp_context
+    }
+
+    fn enter_context(&mut self, context: OtlpContext) {
+        self.otlp_context = context;
+        for advisor in self.advisors.iter_mut() {
+            advisor.enter_context(&self.otlp_context);
         }
     }
 
-    fn make_metrics_registry() -> ResolvedRegistry {
-        ResolvedRegistry {
-            registry_url: "TEST_METRICS".to_owned(),
-            groups: vec![
-                // Attribute group for system memory
-                ResolvedGroup {
-                    id: "registry.system.memory".to_owned(),
-                    r#type: GroupType::AttributeGroup,
-                    brief: "Describes System Memory attributes".to_owned(),
-                    note: "".to_owned(),
-                    prefix: "".to_owned(),
-                    entity_associations: vec![],
-                    extends: None,
-                    stability: None,
-                    deprecated: None,
-                    attributes: vec![Attribute {
-                        name: "system.memory.state".to_owned(),
-                        r#type: AttributeType::Enum {
-                            members: vec![
-                                EnumEntriesSpec {
-                                    id: "used".to_owned(),
-                                    value: ValueSpec::String("used".to_owned()),
-                                    brief: None,
-                                    note: None,
-                                    stability: Some(Stability::Development),
-                                    deprecated: None,
-                                    annotations: None,
-                                },
-                                EnumEntriesSpec {
-                                    id: "free".to_owned(),
-                                    value: ValueSpec::String("free".to_owned()),
-                                    brief: None,
-                                    note: None,
-                                    stability: Some(Stability::Development),
-                                    deprecated: None,
-                                    annotations: None,
-                                },
-                            ],
-                        },
-                        examples: Some(Examples::Strings(vec![
-                            "free".to_owned(),
-                            "cached".to_owned(),
-                        ])),
-                        brief: "The memory state".to_owned(),
-                        tag: None,
-                        requirement_level: RequirementLevel::Recommended {
-                            text: "".to_owned(),
-                        },
-                        sampling_relevant: None,
-                        note: "".to_owned(),
-                        stability: Some(Stability::Development),
-                        deprecated: None,
-                        prefix: false,
-                        tags: None,
-                        value: None,
-                        annotations: None,
-                        role: Default::default(),
-                    }],
-                    span_kind: None,
-                    events: vec![],
-                    metric_name: None,
-                    instrument: None,
-                    unit: None,
-                    name: None,
-                    lineage: None,
-                    display_name: Some("System Memory Attributes".to_owned()),
-                    body: None,
-                    annotations: None,
-                },
-                // System uptime metric
-                ResolvedGroup {
-                    id: "metric.system.uptime".to_owned(),
-                    r#type: GroupType::Metric,
-                    brief: "The time the system has been running".to_owned(),
-                    note: "".to_owned(),
-                    prefix: "".to_owned(),
-                    entity_associations: vec![],
-                    extends: None,
-                    stability: Some(Stability::Development),
-                    deprecated: None,
-                    attributes: vec![],
-                    span_kind: None,
-                    events: vec![],
-                    metric_name: Some("system.uptime".to_owned()),
-                    instrument: Some(InstrumentSpec::Gauge),
-                    unit: Some("s".to_owned()),
-                    name: None,
-                    lineage: None,
-                    display_name: None,
-                    body: None,
-                    annotations: None,
-                },
-                // System memory usage metric
-                ResolvedGroup {
-                    id: "metric.system.memory.usage".to_owned(),
-                    r#type: GroupType::Metric,
-                    brief: "Reports memory in use by state.".to_owned(),
-                    note: "".to_owned(),
-                    prefix: "".to_owned(),
-                    entity_associations: vec![],
-                    extends: None,
-                    stability: Some(Stability::Development),
-                    deprecated: None,
-                    attributes: vec![Attribute {
-                        name: "system.memory.state".to_owned(),
-                        r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                        examples: None,
-                        brief: "The memory state".to_owned(),
-                        tag: None,
-                        requirement_level: RequirementLevel::Recommended {
-                            text: "".to_owned(),
-                        },
-                        sampling_relevant: None,
-                        note: "".to_owned(),
-                        stability: Some(Stability::Development),
-                        deprecated: None,
-                        prefix: false,
-                        tags: None,
-                        value: None,
-                        annotations: None,
-                        role: Default::default(),
-                    }],
-                    span_kind: None,
-                    events: vec![],
-                    metric_name: Some("system.memory.usage".to_owned()),
-                    instrument: Some(InstrumentSpec::UpDownCounter),
-                    unit: Some("By".to_owned()),
-                    name: None,
-                    lineage: None,
-                    display_name: None,
-                    body: None,
-                    annotations: None,
-                },
-            ],
+    /// Stop the sample loop at the first sample given a violation
+    pub fn set_fail_fast(&mut self, fail_fast: bool) {
+        self.fail_fast = fail_fast;
+    }
+
+    /// Gate the run on a budget of advice by level
+    pub fn set_budget(&mut self, budget: SeverityBudget) {
+        self.budget = Some(budget);
+    }
+
+    /// Check the advice counts of the finalized statistics against the budget, and
+    /// record the result in the statistics. `None` when no budget is set.
+    pub fn check_budget(&self, stats: &mut LiveCheckStatistics) -> Option<BudgetResult> {
+        let result = self.budget.as_ref()?.check(stats);
+        stats.budget = Some(result.clone());
+        Some(result)
+    }
+
+    /// Run the live check on a top-level sample, `sample_index` being its position
+    /// in the input.
+    ///
+    /// Resource and instrumentation scope samples set the context of the samples
+    /// following them, as in OTLP where a resource and its scopes come before their
+    /// signals. The advisors are given the context with [`Advisor::enter_context`].
+    ///
+    /// Returns true when the sample loop should stop: in fail fast mode, once a
+    /// violation has been given. The sample that triggered the stop and its first
+    /// violation are recorded in the `stopped_early` statistics.
+    pub fn check_sample(
+        &mut self,
+        sample_index: usize,
+        sample: &mut Sample,
+        stats: &mut LiveCheckStatistics,
+    ) -> Result<bool, Error> {
+        match sample {
+            Sample::Resource(resource) => self.enter_context(OtlpContext {
+                resource: Some(resource.clone()),
+                scope: None,
+            }),
+            Sample::Scope(scope) => self.enter_context(OtlpContext {
+                resource: self.otlp_context.resource.clone(),
+                scope: Some(scope.clone()),
+            }),
+            _ => {}
         }
+        sample.run_live_check(self, stats, None, &sample.clone())?;
+        let violations = self.violation_count;
+        if let Some(progress) = &mut self.progress {
+            progress.sample_checked(violations);
+        }
+        if !self.fail_fast {
+            return Ok(false);
+        }
+        let Some(violation) = stats.first_violation() else {
+            return Ok(false);
+        };
+        stats.stopped_early = Some(FailFastStop {
+            sample_index,
+            violation: violation.clone(),
+        });
+        Ok(true)
+    }
+
+    /// Only surface the advice not in the baseline of accepted advice. The baseline
+    /// advice no longer given are reported by [`LiveChecker::finalize`] with a
+    /// `baseline_advice_resolved` advice.
+    pub fn set_advice_baseline(&mut self, advice_baseline: AdviceBaseline) {
+        self.advice_baseline = Some(advice_baseline);
+        let _ = self.observed_advice.get_or_insert_with(AdviceBaseline::new);
+    }
+
+    /// Record the advice given, including the advice of the baseline, to save them
+    /// as a new baseline, see [`LiveChecker::observed_advice`]. Disabled by default.
+    pub fn set_record_advice_baseline(&mut self, record_advice_baseline: bool) {
+        if record_advice_baseline {
+            let _ = self.observed_advice.get_or_insert_with(AdviceBaseline::new);
+        } else if self.advice_baseline.is_none() {
+            self.observed_advice = None;
+        }
+    }
+
+    /// The advice given so far as a baseline, when recorded
+    #[must_use]
+    pub fn observed_advice(&self) -> Option<&AdviceBaseline> {
+        self.observed_advice.as_ref()
     }
 
-    #[test]
-    fn test_custom_rego() {
-        let registry = ResolvedRegistry {
-            registry_url: "TEST".to_owned(),
-            groups: vec![ResolvedGroup {
-                id: "custom.comprehensive.internal".to_owned(),
-                r#type: GroupType::Span,
-                brief: "".to_owned(),
-                note: "".to_owned(),
-                prefix: "".to_owned(),
-                entity_associations: vec![],
-                extends: None,
-                stability: Some(Stability::Stable),
-                deprecated: None,
-                attributes: vec![Attribute {
-                    name: "custom.string".to_owned(),
-                    r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                    examples: Some(Examples::Strings(vec![
-                        "value1".to_owned(),
-                        "value2".to_owned(),
-                    ])),
-                    brief: "".to_owned(),
-                    tag: None,
-                    requirement_level: RequirementLevel::Recommended {
-                        text: "".to_owned(),
-                    },
-                    sampling_relevant: None,
-                    note: "".to_owned(),
-                    stability: Some(Stability::Stable),
-                    deprecated: None,
-                    prefix: false,
-                    tags: None,
-                    value: None,
-                    annotations: None,
-                    role: Default::default(),
-                }],
-                span_kind: Some(SpanKindSpec::Internal),
-                events: vec![],
-                metric_name: None,
-                instrument: None,
-                unit: None,
-                name: None,
-                lineage: None,
-                display_name: None,
-                body: None,
-                annotations: None,
-            }],
+    /// Records the advice and returns true if it is in the advice baseline
+    fn is_in_advice_baseline(&mut self, advice: &Advice) -> bool {
+        let Some(observed_advice) = &mut self.observed_advice else {
+            return false;
         };
+        observed_advice.add(advice);
+        self.advice_baseline
+            .as_ref()
+            .is_some_and(|advice_baseline| advice_baseline.contains(advice))
+    }
 
-        let mut samples = vec![
-            Sample::Attribute(SampleAttribute::try_from("custom.string=hello").unwrap()),
-            Sample::Attribute(SampleAttribute::try_from("test.string").unwrap()),
-        ];
+    /// Returns a `baseline_advice_resolved` advice for each advice of the baseline
+    /// not given
+    fn resolved_baseline_advice(&self) -> Vec<Advice> {
+        let (Some(advice_baseline), Some(observed_advice)) =
+            (&self.advice_baseline, &self.observed_advice)
+        else {
+            return Vec::new();
+        };
+        advice_baseline
+            .advice
+            .iter()
+            .filter(|(fingerprint, _)| !observed_advice.contains_fingerprint(fingerprint))
+            .map(|(fingerprint, entry)| {
+                builtin_advice(
+                    BuiltinAdviceType::BaselineAdviceResolved,
+                    json!({
+                        FINGERPRINT_ADVICE_CONTEXT_KEY: fingerprint,
+                        ADVICE_TYPE_ADVICE_CONTEXT_KEY: entry.advice_type,
+                        MESSAGE_ADVICE_CONTEXT_KEY: entry.message,
+                    }),
+                    AdviceLevel::Information,
+                )
+            })
+            .collect()
+    }
 
-        let advisors: Vec<Box<dyn Advisor>> = vec![];
+    /// Compare the attribute stability with a baseline registry
+    pub fn set_baseline_registry(&mut self, baseline_registry: &ResolvedRegistry) {
+        self.baseline_stability = baseline_stability(baseline_registry);
+    }
 
-        let mut live_checker = LiveChecker::new(registry, advisors);
-        let rego_advisor = RegoAdvisor::new(
-            &live_checker,
-            &Some("data/policies/live_check_advice/".into()),
-            &Some("data/jq/test.jq".into()),
-        )
-        .expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
-        }
-        stats.finalize();
+    /// Set the filter selecting the attributes and metrics to check
+    pub fn set_filter(&mut self, filter: NameFilter) {
+        self.filter = filter;
+    }
 
-        let all_advice = get_all_advice(&mut samples[0]);
-        assert!(all_advice.is_empty());
+    /// Returns true if the attribute or metric is selected by the filter
+    #[must_use]
+    pub fn is_selected(&self, name: &str) -> bool {
+        self.filter.is_selected(name)
+    }
 
-        let all_advice = get_all_advice(&mut samples[1]);
-        assert_eq!(all_advice.len(), 2);
+    /// Add a hook run on the final report, hooks are run in the order they are added
+    pub fn add_post_process(&mut self, hook: PostProcessHook) {
+        self.post_process.push(hook);
+    }
 
-        assert_eq!(all_advice[0].advice_type, "missing_attribute");
-        assert_eq!(
-            all_advice[0].advice_context,
-            json!({"attribute_name": "test.string"})
-        );
-        assert_eq!(
-            all_advice[0].message,
-            "Attribute 'test.string' does not exist in the registry."
-        );
-        assert_eq!(all_advice[1].advice_type, "contains_test");
-        assert_eq!(
-            all_advice[1].advice_context,
-            json!({"attribute_name": "test.string"})
-        );
-        assert_eq!(
-            all_advice[1].message,
-            "Attribute name must not contain 'test', but was 'test.string'"
-        );
+    /// Run the post-process hooks, in order, on the final report
+    pub fn post_process(&self, report: &mut LiveCheckReport) {
+        for hook in &self.post_process {
+            hook(report);
+        }
+    }
 
-        // Check statistics
-        assert_eq!(stats.total_entities, 2);
-        assert_eq!(stats.total_advisories, 2);
-        assert_eq!(stats.advice_level_counts.len(), 1);
-        assert_eq!(stats.advice_level_counts[&AdviceLevel::Violation], 2);
-        assert_eq!(stats.highest_advice_level_counts.len(), 1);
-        assert_eq!(
-            stats.highest_advice_level_counts[&AdviceLevel::Violation],
-            1
-        );
-        assert_eq!(stats.no_advice_count, 1);
-    }
-
-    #[test]
-    fn test_json_input_output() {
-        let registry = make_registry();
-
-        // Load samples from JSON file
-        let path = "data/span.json";
-        let mut samples: Vec<Sample> =
-            serde_json::from_reader(File::open(path).expect("Unable to open file"))
-                .expect("Unable to parse JSON");
-
-        let advisors: Vec<Box<dyn Advisor>> = vec![
-            Box::new(DeprecatedAdvisor),
-            Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
-            Box::new(EnumAdvisor),
-        ];
-
-        let mut live_checker = LiveChecker::new(registry, advisors);
-        let rego_advisor =
-            RegoAdvisor::new(&live_checker, &None, &None).expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
+    /// Run the post-process hooks on a report grouped by service.
+    ///
+    /// The hooks run on the report of each service, holding its samples and
+    /// statistics, then on a report holding the batch advice and the overall
+    /// statistics without samples. Batch advice added for a service is appended
+    /// to the batch advice of the report.
+    pub fn post_process_service_report(&self, report: &mut LiveCheckServiceReport) {
+        if self.post_process.is_empty() {
+            return;
         }
-        stats.finalize();
-
-        // Check the statistics
-        assert_eq!(stats.total_entities, 14);
-        assert_eq!(stats.total_entities_by_type.get("attribute"), Some(&10));
-        assert_eq!(stats.total_entities_by_type.get("span"), Some(&1));
-        assert_eq!(stats.total_entities_by_type.get("span_event"), Some(&1));
-        assert_eq!(stats.total_entities_by_type.get("span_link"), Some(&1));
-        assert_eq!(stats.total_entities_by_type.get("resource"), Some(&1));
-        assert_eq!(stats.total_advisories, 14);
-    }
-
-    #[test]
-    fn test_json_span_rego() {
-        let registry = make_registry();
-
-        // Load samples from JSON file
-        let path = "data/span.json";
-        let mut samples: Vec<Sample> =
-            serde_json::from_reader(File::open(path).expect("Unable to open file"))
-                .expect("Unable to parse JSON");
-
-        let mut live_checker = LiveChecker::new(registry, vec![]);
-        let rego_advisor = RegoAdvisor::new(
-            &live_checker,
-            &Some("data/policies/live_check_advice/".into()),
-            &Some("data/jq/test.jq".into()),
-        )
-        .expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
+        for service in &mut report.services {
+            let mut service_report = LiveCheckReport {
+                samples: std::mem::take(&mut service.samples),
+                batch_advice: Vec::new(),
+                statistics: std::mem::replace(
+                    &mut service.statistics,
+                    LiveCheckStatistics::new(&self.registry),
+                ),
+            };
+            self.post_process(&mut service_report);
+            service.samples = service_report.samples;
+            service.statistics = service_report.statistics;
+            report.batch_advice.append(&mut service_report.batch_advice);
         }
-        stats.finalize();
+        let mut batch_report = LiveCheckReport {
+            samples: Vec::new(),
+            batch_advice: std::mem::take(&mut report.batch_advice),
+            statistics: std::mem::replace(
+                &mut report.statistics,
+                LiveCheckStatistics::new(&self.registry),
+            ),
+        };
+        self.post_process(&mut batch_report);
+        report.batch_advice = batch_report.batch_advice;
+        report.statistics = batch_report.statistics;
+    }
 
-        // Check the statistics
-        assert_eq!(
-            stats.advice_type_counts.get("contains_test_in_status"),
-            Some(&1)
-        );
+    /// Set the experimental attributes opted in to. Once set, any other experimental
+    /// attribute gets an `experimental_attribute_not_opted_in` advice.
+    pub fn set_experimental_opt_in(&mut self, attribute_names: HashSet<String>) {
+        self.experimental_opt_in = Some(attribute_names);
     }
 
-    #[test]
-    fn test_json_metric() {
-        let registry = make_metrics_registry();
-
-        // Load samples from JSON file
-        let path = "data/metrics.json";
-        let mut samples: Vec<Sample> =
-            serde_json::from_reader(File::open(path).expect("Unable to open file"))
-                .expect("Unable to parse JSON");
-
-        let advisors: Vec<Box<dyn Advisor>> = vec![
-            Box::new(DeprecatedAdvisor),
-            Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
-            Box::new(EnumAdvisor),
-        ];
-
-        let mut live_checker = LiveChecker::new(registry, advisors);
-        let rego_advisor =
-            RegoAdvisor::new(&live_checker, &None, &None).expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
-        }
-        stats.finalize();
-
-        // Check the statistics
-        assert_eq!(stats.total_entities_by_type.get("data_point"), Some(&6));
-        assert_eq!(stats.total_entities_by_type.get("metric"), Some(&4));
-        assert_eq!(stats.total_entities_by_type.get("attribute"), Some(&3));
-        assert_eq!(stats.no_advice_count, 4);
-        assert_eq!(
-            stats
-                .advice_type_counts
-                .get("recommended_attribute_not_present"),
-            Some(&2)
-        );
-        assert_eq!(stats.advice_type_counts.get("missing_attribute"), Some(&2));
-        assert_eq!(stats.advice_type_counts.get("not_stable"), Some(&2));
-        assert_eq!(stats.advice_type_counts.get("missing_metric"), Some(&3));
-        assert_eq!(stats.advice_type_counts.get("missing_namespace"), Some(&2));
-        assert_eq!(
-            stats.seen_registry_metrics.get("system.memory.usage"),
-            Some(&1)
+    /// Returns true if the experimental attribute is used without being opted in to.
+    /// Always false when no opt-in set is configured.
+    #[must_use]
+    pub fn is_experimental_not_opted_in(&self, sample_name: &str, attribute: &Attribute) -> bool {
+        let Some(opt_in) = &self.experimental_opt_in else {
+            return false;
+        };
+        let is_experimental = matches!(
+            attribute.stability,
+            Some(
+                Stability::Development
+                    | Stability::Alpha
+                    | Stability::Beta
+                    | Stability::ReleaseCandidate
+            )
         );
-        assert_eq!(stats.seen_non_registry_metrics.len(), 3);
+        is_experimental && !opt_in.contains(sample_name) && !opt_in.contains(&attribute.name)
     }
 
-    #[test]
-    fn test_json_metric_custom_rego() {
-        let registry = make_metrics_registry();
-
-        // Load samples from JSON file
-        let path = "data/metrics.json";
-        let mut samples: Vec<Sample> =
-            serde_json::from_reader(File::open(path).expect("Unable to open file"))
-                .expect("Unable to parse JSON");
+    /// Returns the current and baseline registry stability of the attribute when its
+    /// stability was lowered.
+    /// Always `None` when no baseline registry is configured.
+    #[must_use]
+    pub fn stability_regression<'a>(
+        &'a self,
+        attribute: &'a Attribute,
+    ) -> Option<(&'a Stability, &'a Stability)> {
+        let current = attribute.stability.as_ref()?;
+        let baseline = self.baseline_stability.get(&attribute.name)?;
+        (stability_rank(current) < stability_rank(baseline)).then_some((current, baseline))
+    }
 
-        let mut live_checker = LiveChecker::new(registry, vec![]);
-        let rego_advisor = RegoAdvisor::new(
-            &live_checker,
-            &Some("data/policies/live_check_advice/".into()),
-            &Some("data/jq/test.jq".into()),
-        )
-        .expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
+    /// Final pass over the advice given on a sample: the suppressed advice is removed,
+    /// in strict mode `improvement` advice is raised to `violation`, then the advice
+    /// level overrides and the message templates are applied. The advice is given the
+    /// `source` the signal of the sample was read from.
+    #[must_use]
+    pub fn process_advice(
+        &mut self,
+        sample: SampleRef<'_>,
+        source: Option<&SampleSource>,
+        advice_list: Vec<Advice>,
+    ) -> Vec<Advice> {
+        let mut advice_list: Vec<Advice> = advice_list
+            .into_iter()
+            .filter(|advice| !self.is_suppressed(Some(&sample), advice))
+            .map(|advice| self.apply_levels(advice))
+            .map(|advice| self.apply_message(advice))
+            .map(with_signal_attribute)
+            .map(|mut advice| {
+                advice.source = source.cloned();
+                advice
+            })
+            .collect();
+        advice_list.retain(|advice| !self.is_in_advice_baseline(advice));
+        self.observe_levels(&advice_list);
+        advice_list
+    }
 
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
+    fn apply_levels(&self, mut advice: Advice) -> Advice {
+        if self.strict && advice.advice_level == AdviceLevel::Improvement {
+            advice.advice_level = AdviceLevel::Violation;
+        }
+        if let Some(level) = self
+            .advice_level_override
+            .level(advice.advice_type.as_str())
+        {
+            advice.advice_level = level.clone();
+        }
+        advice
+    }
 
-            assert!(result.is_ok());
+    fn apply_message(&self, mut advice: Advice) -> Advice {
+        if let Some(message) = self.message_catalog.format(&advice) {
+            advice.message = message;
         }
-        stats.finalize();
-        assert_eq!(
-            stats.advice_type_counts.get("invalid_data_point_value"),
-            Some(&1)
-        );
+        advice
     }
 
-    #[test]
-    fn test_bad_custom_rego() {
-        let registry = ResolvedRegistry {
-            registry_url: "TEST".to_owned(),
-            groups: vec![ResolvedGroup {
-                id: "custom.comprehensive.internal".to_owned(),
-                r#type: GroupType::Span,
-                brief: "".to_owned(),
-                note: "".to_owned(),
-                prefix: "".to_owned(),
-                entity_associations: vec![],
-                extends: None,
-                stability: Some(Stability::Stable),
-                deprecated: None,
-                attributes: vec![Attribute {
-                    name: "custom.string".to_owned(),
-                    r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                    examples: Some(Examples::Strings(vec![
-                        "value1".to_owned(),
-                        "value2".to_owned(),
-                    ])),
-                    brief: "".to_owned(),
-                    tag: None,
-                    requirement_level: RequirementLevel::Recommended {
-                        text: "".to_owned(),
-                    },
-                    sampling_relevant: None,
-                    note: "".to_owned(),
-                    stability: Some(Stability::Stable),
-                    deprecated: None,
-                    prefix: false,
-                    tags: None,
-                    value: None,
-                    annotations: None,
-                    role: Default::default(),
-                }],
-                span_kind: Some(SpanKindSpec::Internal),
-                events: vec![],
-                metric_name: None,
-                instrument: None,
-                unit: None,
-                name: None,
-                lineage: None,
-                display_name: None,
-                body: None,
-                annotations: None,
-            }],
+    fn is_suppressed(&self, sample: Option<&SampleRef<'_>>, advice: &Advice) -> bool {
+        let Some(name) = advice_subject_name(sample, advice) else {
+            return false;
         };
+        self.suppressions
+            .iter()
+            .any(|suppression| suppression.matches(name, advice))
+    }
 
-        let mut samples = vec![Sample::Attribute(
-            SampleAttribute::try_from("custom.string=hello").unwrap(),
-        )];
-
-        let advisors: Vec<Box<dyn Advisor>> = vec![];
+    /// Run all the advisors on a sample. Each advice is tagged with the id of
+    /// the advisor that emitted it, deduplicated when enabled, then processed by
+    /// [`LiveChecker::process_advice`].
+    ///
+    /// With the advice cache enabled, the advice of the cacheable advisors on an
+    /// identical sample is reused.
+    pub fn run_advisors(
+        &mut self,
+        sample: SampleRef<'_>,
+        signal: &Sample,
+        registry_attribute: Option<Rc<Attribute>>,
+        registry_group: Option<Rc<ResolvedGroup>>,
+    ) -> Result<Vec<Advice>, Error> {
+        let cache_key = self.advice_cache.as_ref().and_then(|_| {
+            advice_cache_key(
+                &sample,
+                signal,
+                registry_attribute.as_deref(),
+                registry_group.as_deref(),
+            )
+        });
+        let cached = match (self.advice_cache.as_mut(), &cache_key) {
+            (Some(cache), Some(key)) => cache.get(key).cloned(),
+            _ => None,
+        };
 
-        let mut live_checker = LiveChecker::new(registry, advisors);
-        let rego_advisor = RegoAdvisor::new(
-            &live_checker,
-            &Some("data/policies/bad_advice/".into()),
-            &Some("data/jq/test.jq".into()),
-        )
-        .expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            // This should fail with: "error: use of undefined variable `attribu1te_name` is unsafe"
-
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_err());
-            assert!(result
-                .unwrap_err()
-                .to_string()
-                .contains("use of undefined variable"));
+        // The registry defining the matched attribute, else the matched group
+        let registry = self.registry_origins.as_ref().and_then(|origins| {
+            registry_attribute
+                .as_ref()
+                .and_then(|attribute| origins.attribute(&attribute.name))
+                .or_else(|| {
+                    registry_group
+                        .as_ref()
+                        .and_then(|group| origins.group(&group.id))
+                })
+                .map(str::to_owned)
+        });
+        let matched_ref = registry_attribute
+            .as_ref()
+            .and_then(|attribute| self.matched_ref(attribute, registry_group.as_deref()));
+        let signal_type = SignalType::of(signal);
+        let mut advice_list = Vec::new();
+        let mut to_cache: CachedAdvice = vec![None; self.advisors.len()];
+        for (index, advisor) in self.advisors.iter_mut().enumerate() {
+            let signal_types = advisor.signal_types();
+            if !signal_types.is_empty()
+                && !signal_type.is_some_and(|signal_type| signal_types.contains(&signal_type))
+            {
+                continue;
+            }
+            let skip_after = advisor.skip_after_violations();
+            if !skip_after.is_empty()
+                && advice_list.iter().any(|advice: &Advice| {
+                    advice.advice_level == AdviceLevel::Violation
+                        && skip_after.contains(&advice.advice_type.as_str())
+                })
+            {
+                continue;
+            }
+            if let Some(Some(advisor_advice)) = cached.as_ref().map(|cached| &cached[index]) {
+                advice_list.extend(advisor_advice.iter().cloned());
+                continue;
+            }
+            let advisor_id = advisor.id().to_owned();
+            let start = self.advisor_timings.is_some().then(Instant::now);
+            let first = advice_list.len();
+            advisor.advise_into(
+                sample.clone(),
+                signal,
+                registry_attribute.clone(),
+                registry_group.clone(),
+                &mut |mut advice| {
+                    advice.advisor_id = Some(advisor_id.clone());
+                    if advice.registry.is_none() {
+                        advice.registry.clone_from(&registry);
+                    }
+                    if advice.matched_ref.is_none() {
+                        advice.matched_ref.clone_from(&matched_ref);
+                    }
+                    advice_list.push(advice);
+                },
+            )?;
+            if let (Some(timings), Some(start)) = (self.advisor_timings.as_mut(), start) {
+                timings.add(&advisor_id, start.elapsed());
+            }
+            if cache_key.is_some() && advisor.is_cacheable() {
+                to_cache[index] = Some(advice_list[first..].to_vec());
+            }
         }
+        if let (Some(cache), Some(key), None) = (self.advice_cache.as_mut(), cache_key, &cached) {
+            let _ = cache.put(key, to_cache);
+        }
+        // Deduplicated before processing so the violations are counted once
+        let advice_list = if self.dedup {
+            dedup_advice(advice_list)
+        } else {
+            advice_list
+        };
+        Ok(self.process_advice(sample, signal.source(), advice_list))
     }
 
-    #[test]
-    fn test_exponential_histogram() {
-        let registry = make_metrics_registry();
-
-        // A sample with exponential histogram data points
-        let sample = Sample::Metric(SampleMetric {
-            name: "system.memory.usage".to_owned(),
-            instrument: SampleInstrument::Supported(InstrumentSpec::Histogram),
-            unit: "By".to_owned(),
-            data_points: Some(DataPoints::ExponentialHistogram(vec![
-                SampleExponentialHistogramDataPoint {
-                    attributes: vec![],
-                    count: 0,
-                    sum: None,
-                    min: None,
-                    max: None,
-                    live_check_result: None,
-                    scale: 1,
-                    zero_count: 0,
-                    positive: None,
-                    negative: None,
-                    flags: 0,
-                    zero_threshold: 0.0,
-                    exemplars: vec![],
-                },
-            ])),
-            live_check_result: None,
-        });
-        let mut samples = vec![sample];
-        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
-        let mut live_checker = LiveChecker::new(registry, advisors);
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
+    /// The reference to a matched registry attribute, in the matched group when it
+    /// lists the attribute, else in the first group of the registry listing it
+    fn matched_ref(
+        &self,
+        attribute: &Attribute,
+        registry_group: Option<&ResolvedGroup>,
+    ) -> Option<MatchedRef> {
+        let group_id = registry_group
+            .filter(|group| group.attributes.iter().any(|a| a.name == attribute.name))
+            .map(|group| group.id.clone())
+            .or_else(|| self.attribute_groups.get(&attribute.name).cloned())?;
+        Some(MatchedRef {
+            attribute: attribute.name.clone(),
+            group_id,
+        })
+    }
+
+    /// Collect the advice from batch advisors once all the samples have been checked.
+    ///
+    /// A registry without any group, usually a misconfiguration, gets a
+    /// `no_registry_loaded` advice.
+    pub fn finalize(&mut self) -> Result<Vec<Advice>, Error> {
+        let mut advice_list = Vec::new();
+        if self.registry.groups.is_empty() {
+            // Samples cannot match anything, most advisors stay silent
+            advice_list.push(builtin_advice(
+                BuiltinAdviceType::NoRegistryLoaded,
+                json!({}),
+                AdviceLevel::Improvement,
+            ));
         }
-        stats.finalize();
-        assert_eq!(
-            stats.advice_type_counts.get("unexpected_instrument"),
-            Some(&1)
+        advice_list.extend(annotation_advice(&self.registry));
+        for advisor in self.advisors.iter_mut() {
+            let start = self.advisor_timings.is_some().then(Instant::now);
+            let advice = advisor.finalize()?;
+            if let (Some(timings), Some(start)) = (self.advisor_timings.as_mut(), start) {
+                timings.add(advisor.id(), start.elapsed());
+            }
+            advice_list.extend(with_advisor_id(advisor.id(), advice));
+        }
+        let mut advice_list: Vec<Advice> = advice_list
+            .into_iter()
+            .filter(|advice| !self.is_suppressed(None, advice))
+            .map(|advice| self.apply_levels(advice))
+            .map(|advice| self.apply_message(advice))
+            .map(with_signal_attribute)
+            .collect();
+        advice_list.retain(|advice| !self.is_in_advice_baseline(advice));
+        advice_list.extend(
+            self.resolved_baseline_advice()
+                .into_iter()
+                .map(|advice| self.apply_levels(advice))
+                .map(|advice| self.apply_message(advice))
+                .map(with_signal_attribute),
         );
-        // Check the live check result for the sample has the correct instrument mismatch message
-        let sample = match &samples[0] {
-            Sample::Metric(m) => m,
-            _ => panic!("Expected a Metric sample"),
+        let mut advice_list = if self.dedup {
+            dedup_advice(advice_list)
+        } else {
+            advice_list
         };
-        let live_check_result = sample.live_check_result.as_ref().unwrap();
-        // Get the instrument_mismatch from all_advice
-        let advice = live_check_result
-            .all_advice
-            .iter()
-            .find(|a| a.advice_type == "unexpected_instrument")
-            .expect("Expected unexpected_instrument advice");
-        assert_eq!(
-            advice.message,
-            "Instrument should be 'updowncounter', but found 'histogram'."
-        );
-        assert_eq!(advice.signal_name, Some("system.memory.usage".to_owned()));
-        assert_eq!(advice.signal_type, Some("metric".to_owned()));
-    }
-
-    #[test]
-    fn test_gauge_exemplar_rego() {
-        let registry = make_metrics_registry();
-
-        // A gauge sample with an exemplar
-        let mut sample = Sample::Metric(SampleMetric {
-            name: "system.uptime".to_owned(),
-            instrument: SampleInstrument::Supported(InstrumentSpec::Gauge),
-            unit: "s".to_owned(),
-            data_points: Some(DataPoints::Number(vec![SampleNumberDataPoint {
-                attributes: vec![],
-                value: json!(0.0),
-                flags: 0,
-                live_check_result: None,
-                exemplars: vec![SampleExemplar {
-                    timestamp: "".to_owned(),
-                    value: json!(0.0),
-                    filtered_attributes: vec![],
-                    span_id: "".to_owned(),
-                    trace_id: "".to_owned(),
-                    live_check_result: None,
-                }],
-            }])),
-            live_check_result: None,
-        });
-        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
-        let mut live_checker = LiveChecker::new(registry, advisors);
+        self.observe_levels(&advice_list);
+        self.order_advice(&mut advice_list);
+        Ok(advice_list)
+    }
 
-        let rego_advisor = RegoAdvisor::new(
-            &live_checker,
-            &Some("data/policies/live_check_advice/".into()),
-            &Some("data/jq/test.jq".into()),
-        )
-        .expect("Failed to create Rego advisor");
-        live_checker.add_advisor(Box::new(rego_advisor));
+    /// Find an attribute in the registry
+    #[must_use]
+    pub fn find_attribute(&self, name: &str) -> Option<Rc<Attribute>> {
+        self.semconv_attributes.get(name).map(Rc::clone)
+    }
 
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        let result = sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
+    /// Match a sample attribute name to a registry attribute following the
+    /// [`MatchStrategy`] of the live checker
+    #[must_use]
+    pub fn match_attribute(&self, name: &str) -> Option<Rc<Attribute>> {
+        match &self.match_strategy {
+            MatchStrategy::Exact => self
+                .find_attribute(name)
+                .or_else(|| self.find_template(name)),
+            MatchStrategy::Prefix => self
+                .find_attribute(name)
+                .or_else(|| self.find_template(name))
+                .or_else(|| {
+                    name.rmatch_indices('.')
+                        .find_map(|(index, _)| self.find_attribute(&name[..index]))
+                }),
+            MatchStrategy::Custom(match_name) => {
+                let name = match_name(name)?;
+                self.find_attribute(&name)
+                    .or_else(|| self.find_template(&name))
+            }
+        }
+    }
 
-        assert!(result.is_ok());
-        stats.finalize();
-        assert_eq!(stats.advice_type_counts.get("low_value"), Some(&1));
+    /// Match a sample attribute name, not matching any registry attribute, once
+    /// normalized by the [`NameNormalizer`] of the live checker. Returns the
+    /// normalized name and the registry attribute it matches.
+    #[must_use]
+    pub fn match_normalized_attribute(&self, name: &str) -> Option<(String, Rc<Attribute>)> {
+        let normalized_name = self.name_normalizer.normalize(name)?;
+        let attribute = self.match_attribute(&normalized_name)?;
+        Some((normalized_name, attribute))
     }
 
-    #[test]
-    fn test_summary_unspecified() {
-        let registry = make_metrics_registry();
+    /// Find a metric in the registry
+    #[must_use]
+    pub fn find_metric(&self, name: &str) -> Option<Rc<ResolvedGroup>> {
+        self.semconv_metrics.get(name).map(Rc::clone)
+    }
 
-        let mut samples = vec![
-            Sample::Metric(SampleMetric {
-                name: "system.memory.usage".to_owned(),
-                instrument: SampleInstrument::Unsupported("Summary".to_owned()),
-                unit: "By".to_owned(),
-                data_points: None,
-                live_check_result: None,
-            }),
-            Sample::Metric(SampleMetric {
-                name: "system.memory.usage".to_owned(),
-                instrument: SampleInstrument::Unsupported("Unspecified".to_owned()),
-                unit: "By".to_owned(),
-                data_points: None,
-                live_check_result: None,
-            }),
-        ];
-        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
-        let mut live_checker = LiveChecker::new(registry, advisors);
-
-        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-        for sample in &mut samples {
-            let result =
-                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
-            assert!(result.is_ok());
+    /// Find an event in the registry by its event name
+    #[must_use]
+    pub fn find_event(&self, name: &str) -> Option<Rc<ResolvedGroup>> {
+        self.semconv_events.get(name).map(Rc::clone)
+    }
+
+    /// Find the attributes of the resource groups a resource sample is checked against,
+    /// each attribute once.
+    ///
+    /// A resource group applies when one of its attributes is in the sample. The groups
+    /// defining `service.name` always apply, as every resource must carry it.
+    #[must_use]
+    pub fn find_resource_attributes(
+        &self,
+        sample_attributes: &[SampleAttribute],
+    ) -> Vec<Attribute> {
+        let mut attributes: Vec<Attribute> = Vec::new();
+        for group in &self.semconv_resources {
+            let applies = group.attributes.iter().any(|attribute| {
+                attribute.name == "service.name"
+                    || sample_attributes
+                        .iter()
+                        .any(|sample_attribute| sample_attribute.name == attribute.name)
+            });
+            if !applies {
+                continue;
+            }
+            for attribute in &group.attributes {
+                if !attributes.iter().any(|a| a.name == attribute.name) {
+                    attributes.push(attribute.clone());
+                }
+            }
         }
-        stats.finalize();
-        assert_eq!(
-            stats.advice_type_counts.get("unexpected_instrument"),
-            Some(&2)
-        );
+        attributes
+    }
+
+    /// Find a template in the registry
+    #[must_use]
+    pub fn find_template(&self, attribute_name: &str) -> Option<Rc<Attribute>> {
+        // Use the pre-sorted list to find the first (longest) matching template
+        for (template_name, attribute) in &self.templates_by_length {
+            if attribute_name.starts_with(template_name) {
+                return Some(Rc::clone(attribute));
+            }
+        }
+        None
+    }
+
+    /// Advise on array template attributes sent as repeated scalar instances
+    /// on one sample instead of a single array value.
+    #[must_use]
+    pub fn template_aggregation_advice(
+        &self,
+        sample: SampleRef<'_>,
+        signal: &Sample,
+    ) -> Vec<Advice> {
+        let Some(attributes) = sample.attributes() else {
+            return Vec::new();
+        };
+        // Scalar instances per attribute name, in order of first appearance
+        let mut scalars: Vec<(&str, usize, Rc<Attribute>)> = Vec::new();
+        for attribute in attributes {
+            if let Some(entry) = scalars
+                .iter_mut()
+                .find(|(name, _, _)| *name == attribute.name)
+            {
+                entry.1 += 1;
+                continue;
+            }
+            if self.find_attribute(&attribute.name).is_some() {
+                continue;
+            }
+            let Some(template) = self.find_template(&attribute.name) else {
+                continue;
+            };
+            let AttributeType::Template(template_type) = &template.r#type else {
+                continue;
+            };
+            if attribute.r#type.is_some()
+                && attribute.r#type == template_element_type(template_type)
+            {
+                scalars.push((&attribute.name, 1, template));
+            }
+        }
+        scalars
+            .into_iter()
+            .filter(|(_, count, _)| *count > 1)
+            .map(|(name, count, template)| Advice {
+                signal_type: signal.signal_type(),
+                signal_name: signal.signal_name(),
+                ..builtin_advice(
+                    BuiltinAdviceType::TemplateShouldAggregate,
+                    json!({
+                        ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: name,
+                        TEMPLATE_NAME_ADVICE_CONTEXT_KEY: template.name.clone(),
+                        COUNT_ADVICE_CONTEXT_KEY: count,
+                    }),
+                    AdviceLevel::Information,
+                )
+            })
+            .collect()
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests;
@@ -0,0 +1,1495 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests of the live checker, with the helpers running it on samples.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+    fs::File,
+};
+
+use crate::{
+    advice::{
+        ConstantAttributeAdvisor, DeprecatedAdvisor, EnumAdvisor, MetricNameAdvisor,
+        NameLengthAdvisor, PolicySource, RegoAdvisor, StabilityAdvisor, TypeAdvisor,
+    },
+    get_advice_json_schema,
+    otlp_document::{OtlpResource, OtlpScope},
+    sample_attribute::SampleAttribute,
+    sample_metric::{
+        DataPoints, SampleExemplar, SampleExponentialHistogramDataPoint, SampleInstrument,
+        SampleMetric, SampleNumberDataPoint,
+    },
+    sample_resource::SampleResource,
+    sample_scope::SampleScope,
+    sample_span::SampleSpan,
+    LiveCheckRunner, LiveCheckStatistics, Sample,
+};
+
+use super::*;
+use regex::Regex;
+use serde_json::json;
+use weaver_checker::violation::{Advice, AdviceFix, AdviceLevel};
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_semconv::{
+    attribute::{
+        AttributeType, EnumEntriesSpec, Examples, PrimitiveOrArrayTypeSpec, RequirementLevel,
+        TemplateTypeSpec, ValueSpec,
+    },
+    group::{GroupType, InstrumentSpec, SpanKindSpec},
+    stability::Stability,
+};
+
+mod options;
+mod rego;
+
+pub(crate) fn get_all_advice(sample: &mut Sample) -> &mut [Advice] {
+    match sample {
+        Sample::Attribute(sample_attribute) => sample_attribute
+            .live_check_result
+            .as_mut() // Change to as_mut() to get a mutable reference
+            .map(|result| &mut result.all_advice)
+            .map_or(&mut [], |v| v),
+        _ => &mut [],
+    }
+}
+
+/// Runs the live check on the samples, their advice being in their live check
+/// results, and returns the statistics
+pub(crate) fn check_samples_with_stats(
+    live_checker: &mut LiveChecker,
+    samples: &mut [Sample],
+) -> LiveCheckStatistics {
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    for sample in samples.iter_mut() {
+        let result = sample.run_live_check(live_checker, &mut stats, None, &sample.clone());
+        assert!(result.is_ok());
+    }
+    stats.finalize();
+    stats
+}
+
+/// Runs the live check on the samples, their advice being in their live check results
+pub(crate) fn check_samples(live_checker: &mut LiveChecker, samples: &mut [Sample]) {
+    let _ = check_samples_with_stats(live_checker, samples);
+}
+
+/// Runs the live check on a sample, its advice being in its live check result
+pub(crate) fn check_sample(live_checker: &mut LiveChecker, sample: &mut Sample) {
+    check_samples(live_checker, std::slice::from_mut(sample));
+}
+
+/// Runs the live check on an attribute sample given as `name=value`, returns its advice
+pub(crate) fn check_attribute(live_checker: &mut LiveChecker, attribute: &str) -> Vec<Advice> {
+    let mut sample = Sample::Attribute(SampleAttribute::try_from(attribute).unwrap());
+    check_sample(live_checker, &mut sample);
+    get_all_advice(&mut sample).to_vec()
+}
+
+/// Runs the live check on the samples, returns the advice of the batch advisors
+pub(crate) fn check_batch(live_checker: &mut LiveChecker, samples: &mut [Sample]) -> Vec<Advice> {
+    check_samples(live_checker, samples);
+    live_checker
+        .finalize()
+        .expect("Failed to finalize advisors")
+}
+
+#[test]
+fn test_attribute_live_checker() {
+    let registry = make_registry();
+
+    let mut samples = vec![
+        Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("testString2").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.deprecated=42").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("aws.s3.bucket.name").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.enum=foo").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.enum=example_variant1").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.enum=42.42").unwrap()),
+        Sample::Attribute(
+            SampleAttribute::try_from("test.string.not.allowed=example_value").unwrap(),
+        ),
+        Sample::Attribute(SampleAttribute::try_from("test.extends=new_value").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.template.my.key=42").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.deprecated.allowed=42").unwrap()),
+    ];
+
+    let advisors: Vec<Box<dyn Advisor>> = vec![
+        Box::new(DeprecatedAdvisor::default()),
+        Box::new(StabilityAdvisor),
+        Box::new(TypeAdvisor),
+        Box::new(EnumAdvisor::default()),
+    ];
+
+    let mut live_checker = LiveChecker::new(registry, advisors);
+    let rego_advisor =
+        RegoAdvisor::new(&live_checker, &[], &None).expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+
+    let all_advice = get_all_advice(&mut samples[0]);
+    assert!(all_advice.is_empty());
+
+    let all_advice = get_all_advice(&mut samples[1]);
+    assert_eq!(all_advice.len(), 3);
+    // make a sort of the advice
+    all_advice.sort_by(|a, b| a.advice_type.cmp(&b.advice_type));
+    assert_eq!(all_advice[0].advice_type, "invalid_format");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "testString2" })
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'testString2' does not match name formatting rules."
+    );
+    assert_eq!(all_advice[1].advice_type, "missing_attribute");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "testString2"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Attribute 'testString2' does not exist in the registry."
+    );
+    assert_eq!(all_advice[2].advice_type, "missing_namespace");
+    assert_eq!(
+        all_advice[2].advice_context,
+        json!({"attribute_name": "testString2"})
+    );
+    assert_eq!(all_advice[2].message, "Attribute name 'testString2' must include a namespace (e.g. '{namespace}.{attribute_key}')");
+
+    let all_advice = get_all_advice(&mut samples[2]);
+    assert_eq!(all_advice.len(), 3);
+    assert_eq!(all_advice[0].advice_type, "deprecated");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.deprecated", "deprecation_reason": "uncategorized", "deprecation_note": "note"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'test.deprecated' is deprecated; reason = 'uncategorized', note = 'note'."
+    );
+
+    assert_eq!(all_advice[1].advice_type, "not_stable");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "test.deprecated", "stability": "development"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Attribute 'test.deprecated' is not stable; stability = development."
+    );
+
+    assert_eq!(all_advice[2].advice_type, "type_mismatch");
+    assert_eq!(
+        all_advice[2].advice_context,
+        json!({"attribute_name": "test.deprecated", "attribute_type": "int", "expected": "string"})
+    );
+    assert_eq!(
+        all_advice[2].message,
+        "Attribute 'test.deprecated' has type 'int'. Type should be 'string'."
+    );
+
+    let all_advice = get_all_advice(&mut samples[3]);
+    assert_eq!(all_advice.len(), 1);
+    assert_eq!(all_advice[0].advice_type, "missing_attribute");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "aws.s3.bucket.name"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'aws.s3.bucket.name' does not exist in the registry."
+    );
+
+    let all_advice = get_all_advice(&mut samples[4]);
+    assert_eq!(all_advice.len(), 1);
+    assert_eq!(all_advice[0].advice_type, "undefined_enum_variant");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.enum", "attribute_value": "foo"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Enum attribute 'test.enum' has value 'foo' which is not documented."
+    );
+
+    let all_advice = get_all_advice(&mut samples[6]);
+    assert_eq!(all_advice.len(), 1);
+    assert_eq!(all_advice[0].advice_type, "type_mismatch");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.enum", "attribute_type": "double"})
+    );
+    assert_eq!(all_advice[0].message, "Enum attribute 'test.enum' has type 'double'. Enum value type should be 'string' or 'int'.");
+
+    let all_advice = get_all_advice(&mut samples[7]);
+
+    // Make a sort of the advice
+    all_advice.sort_by(|a, b| a.advice_type.cmp(&b.advice_type));
+    assert_eq!(all_advice.len(), 3);
+
+    assert_eq!(all_advice[0].advice_type, "extends_namespace");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test", "namespace": "test"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute name 'test.string.not.allowed' collides with existing namespace 'test'"
+    );
+    assert_eq!(all_advice[1].advice_type, "illegal_namespace");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "test.string.not.allowed", "namespace": "test.string"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Namespace 'test.string' collides with existing attribute 'test.string.not.allowed'"
+    );
+    assert_eq!(all_advice[2].advice_type, "missing_attribute");
+    assert_eq!(
+        all_advice[2].advice_context,
+        json!({
+            "attribute_name": "test.string.not.allowed"
+        })
+    );
+    assert_eq!(
+        all_advice[2].message,
+        "Attribute 'test.string.not.allowed' does not exist in the registry."
+    );
+
+    let all_advice = get_all_advice(&mut samples[8]);
+    assert_eq!(all_advice.len(), 2);
+    assert_eq!(all_advice[0].advice_type, "missing_attribute");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.extends"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'test.extends' does not exist in the registry."
+    );
+    assert_eq!(all_advice[1].advice_type, "extends_namespace");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "test", "namespace": "test"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Attribute name 'test.extends' collides with existing namespace 'test'"
+    );
+
+    // test.template
+    let all_advice = get_all_advice(&mut samples[9]);
+    assert_eq!(all_advice.len(), 2);
+    assert_eq!(all_advice[0].advice_type, "template_attribute");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.template.my.key", "template_name": "test.template"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'test.template.my.key' is a template"
+    );
+    assert_eq!(all_advice[1].advice_type, "type_mismatch");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "test.template.my.key", "attribute_type": "int", "expected": "string"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Attribute 'test.template.my.key' has type 'int'. Type should be 'string'."
+    );
+
+    // test.deprecated.allowed
+    // Should not get illegal_namespace for extending a deprecated attribute
+    let all_advice = get_all_advice(&mut samples[10]);
+    assert_eq!(all_advice.len(), 2);
+    assert_eq!(all_advice[0].advice_type, "missing_attribute");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.deprecated.allowed"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'test.deprecated.allowed' does not exist in the registry."
+    );
+    assert_eq!(all_advice[1].advice_type, "extends_namespace");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "test", "namespace": "test"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Attribute name 'test.deprecated.allowed' collides with existing namespace 'test'"
+    );
+
+    // Check statistics
+    assert_eq!(stats.total_entities, 11);
+    assert_eq!(stats.total_advisories, 18);
+    assert_eq!(stats.advice_level_counts.len(), 3);
+    assert_eq!(stats.advice_level_counts[&AdviceLevel::Violation], 11);
+    assert_eq!(stats.advice_level_counts[&AdviceLevel::Information], 5);
+    assert_eq!(stats.advice_level_counts[&AdviceLevel::Improvement], 2);
+    assert_eq!(stats.highest_advice_level_counts.len(), 2);
+    assert_eq!(
+        stats.highest_advice_level_counts[&AdviceLevel::Violation],
+        8
+    );
+    assert_eq!(
+        stats.highest_advice_level_counts[&AdviceLevel::Information],
+        1
+    );
+    assert_eq!(stats.no_advice_count, 2);
+    assert_eq!(stats.seen_registry_attributes.len(), 3);
+    assert_eq!(stats.seen_registry_attributes["test.enum"], 3);
+    assert_eq!(stats.seen_non_registry_attributes.len(), 6);
+    assert_eq!(stats.registry_coverage, 1.0);
+}
+
+pub(crate) fn make_registry() -> ResolvedRegistry {
+    ResolvedRegistry {
+        registry_url: "TEST".to_owned(),
+        groups: vec![ResolvedGroup {
+            id: "test.comprehensive.internal".to_owned(),
+            r#type: GroupType::Span,
+            brief: "".to_owned(),
+            note: "".to_owned(),
+            prefix: "".to_owned(),
+            entity_associations: vec![],
+            extends: None,
+            stability: Some(Stability::Stable),
+            deprecated: None,
+            attributes: vec![
+                Attribute {
+                    name: "test.string".to_owned(),
+                    r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+                    examples: Some(Examples::Strings(vec![
+                        "value1".to_owned(),
+                        "value2".to_owned(),
+                    ])),
+                    brief: "".to_owned(),
+                    tag: None,
+                    requirement_level: RequirementLevel::Recommended {
+                        text: "".to_owned(),
+                    },
+                    sampling_relevant: None,
+                    note: "".to_owned(),
+                    stability: Some(Stability::Stable),
+                    deprecated: None,
+                    prefix: false,
+                    tags: None,
+                    value: None,
+                    annotations: None,
+                    role: Default::default(),
+                },
+                Attribute {
+                    name: "test.enum".to_owned(),
+                    r#type: AttributeType::Enum {
+                        members: vec![
+                            EnumEntriesSpec {
+                                id: "test_enum_member".to_owned(),
+                                value: ValueSpec::String("example_variant1".to_owned()),
+                                brief: None,
+                                note: None,
+                                stability: Some(Stability::Stable),
+                                deprecated: None,
+                                annotations: None,
+                            },
+                            EnumEntriesSpec {
+                                id: "test_enum_member2".to_owned(),
+                                value: ValueSpec::String("example_variant2".to_owned()),
+                                brief: None,
+                                note: None,
+                                stability: Some(Stability::Stable),
+                                deprecated: None,
+                                annotations: None,
+                            },
+                        ],
+                    },
+                    examples: None,
+                    brief: "".to_owned(),
+                    tag: None,
+                    requirement_level: RequirementLevel::Recommended {
+                        text: "".to_owned(),
+                    },
+                    sampling_relevant: None,
+                    note: "".to_owned(),
+                    stability: Some(Stability::Stable),
+                    deprecated: None,
+                    prefix: false,
+                    tags: None,
+                    value: None,
+                    annotations: None,
+                    role: Default::default(),
+                },
+                Attribute {
+                    name: "test.deprecated".to_owned(),
+                    r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+                    examples: Some(Examples::Strings(vec![
+                        "value1".to_owned(),
+                        "value2".to_owned(),
+                    ])),
+                    brief: "".to_owned(),
+                    tag: None,
+                    requirement_level: RequirementLevel::Recommended {
+                        text: "".to_owned(),
+                    },
+                    sampling_relevant: None,
+                    note: "".to_owned(),
+                    stability: Some(Stability::Development),
+                    deprecated: Some(weaver_semconv::deprecated::Deprecated::Uncategorized {
+                        note: "note".to_owned(),
+                    }),
+                    prefix: false,
+                    tags: None,
+                    value: None,
+                    annotations: None,
+                    role: Default::default(),
+                },
+                Attribute {
+                    name: "test.template".to_owned(),
+                    r#type: AttributeType::Template(TemplateTypeSpec::String),
+                    examples: Some(Examples::Strings(vec![
+                        "value1".to_owned(),
+                        "value2".to_owned(),
+                    ])),
+                    brief: "".to_owned(),
+                    tag: None,
+                    requirement_level: RequirementLevel::Recommended {
+                        text: "".to_owned(),
+                    },
+                    sampling_relevant: None,
+                    note: "".to_owned(),
+                    stability: Some(Stability::Stable),
+                    deprecated: None,
+                    prefix: false,
+                    tags: None,
+                    value: None,
+                    annotations: None,
+                    role: Default::default(),
+                },
+            ],
+            span_kind: Some(SpanKindSpec::Internal),
+            events: vec![],
+            metric_name: None,
+            instrument: None,
+            unit: None,
+            name: None,
+            lineage: None,
+            display_name: None,
+            body: None,
+            annotations: None,
+        }],
+    }
+}
+
+pub(crate) fn make_metrics_registry() -> ResolvedRegistry {
+    ResolvedRegistry {
+        registry_url: "TEST_METRICS".to_owned(),
+        groups: vec![
+            // Attribute group for system memory
+            ResolvedGroup {
+                id: "registry.system.memory".to_owned(),
+                r#type: GroupType::AttributeGroup,
+                brief: "Describes System Memory attributes".to_owned(),
+                note: "".to_owned(),
+                prefix: "".to_owned(),
+                entity_associations: vec![],
+                extends: None,
+                stability: None,
+                deprecated: None,
+                attributes: vec![Attribute {
+                    name: "system.memory.state".to_owned(),
+                    r#type: AttributeType::Enum {
+                        members: vec![
+                            EnumEntriesSpec {
+                                id: "used".to_owned(),
+                                value: ValueSpec::String("used".to_owned()),
+                                brief: None,
+                                note: None,
+                                stability: Some(Stability::Development),
+                                deprecated: None,
+                                annotations: None,
+                            },
+                            EnumEntriesSpec {
+                                id: "free".to_owned(),
+                                value: ValueSpec::String("free".to_owned()),
+                                brief: None,
+                                note: None,
+                                stability: Some(Stability::Development),
+                                deprecated: None,
+                                annotations: None,
+                            },
+                        ],
+                    },
+                    examples: Some(Examples::Strings(vec![
+                        "free".to_owned(),
+                        "cached".to_owned(),
+                    ])),
+                    brief: "The memory state".to_owned(),
+                    tag: None,
+                    requirement_level: RequirementLevel::Recommended {
+                        text: "".to_owned(),
+                    },
+                    sampling_relevant: None,
+                    note: "".to_owned(),
+                    stability: Some(Stability::Development),
+                    deprecated: None,
+                    prefix: false,
+                    tags: None,
+                    value: None,
+                    annotations: None,
+                    role: Default::default(),
+                }],
+                span_kind: None,
+                events: vec![],
+                metric_name: None,
+                instrument: None,
+                unit: None,
+                name: None,
+                lineage: None,
+                display_name: Some("System Memory Attributes".to_owned()),
+                body: None,
+                annotations: None,
+            },
+            // System uptime metric
+            ResolvedGroup {
+                id: "metric.system.uptime".to_owned(),
+                r#type: GroupType::Metric,
+                brief: "The time the system has been running".to_owned(),
+                note: "".to_owned(),
+                prefix: "".to_owned(),
+                entity_associations: vec![],
+                extends: None,
+                stability: Some(Stability::Development),
+                deprecated: None,
+                attributes: vec![],
+                span_kind: None,
+                events: vec![],
+                metric_name: Some("system.uptime".to_owned()),
+                instrument: Some(InstrumentSpec::Gauge),
+                unit: Some("s".to_owned()),
+                name: None,
+                lineage: None,
+                display_name: None,
+                body: None,
+                annotations: None,
+            },
+            // System memory usage metric
+            ResolvedGroup {
+                id: "metric.system.memory.usage".to_owned(),
+                r#type: GroupType::Metric,
+                brief: "Reports memory in use by state.".to_owned(),
+                note: "".to_owned(),
+                prefix: "".to_owned(),
+                entity_associations: vec![],
+                extends: None,
+                stability: Some(Stability::Development),
+                deprecated: None,
+                attributes: vec![Attribute {
+                    name: "system.memory.state".to_owned(),
+                    r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+                    examples: None,
+                    brief: "The memory state".to_owned(),
+                    tag: None,
+                    requirement_level: RequirementLevel::Recommended {
+                        text: "".to_owned(),
+                    },
+                    sampling_relevant: None,
+                    note: "".to_owned(),
+                    stability: Some(Stability::Development),
+                    deprecated: None,
+                    prefix: false,
+                    tags: None,
+                    value: None,
+                    annotations: None,
+                    role: Default::default(),
+                }],
+                span_kind: None,
+                events: vec![],
+                metric_name: Some("system.memory.usage".to_owned()),
+                instrument: Some(InstrumentSpec::UpDownCounter),
+                unit: Some("By".to_owned()),
+                name: None,
+                lineage: None,
+                display_name: None,
+                body: None,
+                annotations: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_json_input_output() {
+    let registry = make_registry();
+
+    // Load samples from JSON file
+    let path = "data/span.json";
+    let mut samples: Vec<Sample> =
+        serde_json::from_reader(File::open(path).expect("Unable to open file"))
+            .expect("Unable to parse JSON");
+
+    let advisors: Vec<Box<dyn Advisor>> = vec![
+        Box::new(DeprecatedAdvisor::default()),
+        Box::new(StabilityAdvisor),
+        Box::new(TypeAdvisor),
+        Box::new(EnumAdvisor::default()),
+    ];
+
+    let mut live_checker = LiveChecker::new(registry, advisors);
+    let rego_advisor =
+        RegoAdvisor::new(&live_checker, &[], &None).expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+
+    // Check the statistics
+    assert_eq!(stats.total_entities, 14);
+    assert_eq!(stats.total_entities_by_type.get("attribute"), Some(&10));
+    assert_eq!(stats.total_entities_by_type.get("span"), Some(&1));
+    assert_eq!(stats.total_entities_by_type.get("span_event"), Some(&1));
+    assert_eq!(stats.total_entities_by_type.get("span_link"), Some(&1));
+    assert_eq!(stats.total_entities_by_type.get("resource"), Some(&1));
+    assert_eq!(stats.total_advisories, 14);
+}
+
+#[test]
+fn test_json_metric() {
+    let registry = make_metrics_registry();
+
+    // Load samples from JSON file
+    let path = "data/metrics.json";
+    let mut samples: Vec<Sample> =
+        serde_json::from_reader(File::open(path).expect("Unable to open file"))
+            .expect("Unable to parse JSON");
+
+    let advisors: Vec<Box<dyn Advisor>> = vec![
+        Box::new(DeprecatedAdvisor::default()),
+        Box::new(StabilityAdvisor),
+        Box::new(TypeAdvisor),
+        Box::new(EnumAdvisor::default()),
+    ];
+
+    let mut live_checker = LiveChecker::new(registry, advisors);
+    let rego_advisor =
+        RegoAdvisor::new(&live_checker, &[], &None).expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+
+    // Check the statistics
+    assert_eq!(stats.total_entities_by_type.get("data_point"), Some(&6));
+    assert_eq!(stats.total_entities_by_type.get("metric"), Some(&4));
+    assert_eq!(stats.total_entities_by_type.get("attribute"), Some(&3));
+    assert_eq!(stats.no_advice_count, 4);
+    assert_eq!(
+        stats
+            .advice_type_counts
+            .get("recommended_attribute_not_present"),
+        Some(&2)
+    );
+    assert_eq!(stats.advice_type_counts.get("missing_attribute"), Some(&2));
+    assert_eq!(stats.advice_type_counts.get("not_stable"), Some(&2));
+    assert_eq!(stats.advice_type_counts.get("missing_metric"), Some(&3));
+    assert_eq!(stats.advice_type_counts.get("missing_namespace"), Some(&2));
+    assert_eq!(
+        stats.seen_registry_metrics.get("system.memory.usage"),
+        Some(&1)
+    );
+    assert_eq!(stats.seen_non_registry_metrics.len(), 3);
+}
+
+#[test]
+fn test_exponential_histogram() {
+    let registry = make_metrics_registry();
+
+    // A sample with exponential histogram data points
+    let sample = Sample::Metric(SampleMetric {
+        name: "system.memory.usage".to_owned(),
+        instrument: SampleInstrument::Supported(InstrumentSpec::Histogram),
+        unit: "By".to_owned(),
+        temporality: None,
+        data_points: Some(DataPoints::ExponentialHistogram(vec![
+            SampleExponentialHistogramDataPoint {
+                attributes: vec![],
+                count: 0,
+                sum: None,
+                min: None,
+                max: None,
+                live_check_result: None,
+                scale: 1,
+                zero_count: 0,
+                positive: None,
+                negative: None,
+                flags: 0,
+                zero_threshold: 0.0,
+                exemplars: vec![],
+            },
+        ])),
+        live_check_result: None,
+        source: None,
+    });
+    let mut samples = vec![sample];
+    let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
+    let mut live_checker = LiveChecker::new(registry, advisors);
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+    assert_eq!(
+        stats.advice_type_counts.get("unexpected_instrument"),
+        Some(&1)
+    );
+    // Check the live check result for the sample has the correct instrument mismatch message
+    let sample = match &samples[0] {
+        Sample::Metric(m) => m,
+        _ => panic!("Expected a Metric sample"),
+    };
+    let live_check_result = sample.live_check_result.as_ref().unwrap();
+    // Get the instrument_mismatch from all_advice
+    let advice = live_check_result
+        .all_advice
+        .iter()
+        .find(|a| a.advice_type == "unexpected_instrument")
+        .expect("Expected unexpected_instrument advice");
+    assert_eq!(
+        advice.message,
+        "Instrument should be 'updowncounter', but found 'histogram'."
+    );
+    assert_eq!(advice.signal_name, Some("system.memory.usage".to_owned()));
+    assert_eq!(advice.signal_type, Some("metric".to_owned()));
+}
+
+#[test]
+fn test_from_registry_dir() {
+    let mut live_checker = LiveChecker::from_registry_dir(Path::new("data/model")).unwrap();
+    assert!(live_checker.find_metric("system.memory.usage").is_some());
+    assert!(live_checker.find_attribute("system.memory.state").is_some());
+
+    let make_metric = |name: &str, attribute: &str| {
+        Sample::Metric(SampleMetric {
+            name: name.to_owned(),
+            instrument: SampleInstrument::Supported(InstrumentSpec::UpDownCounter),
+            unit: "By".to_owned(),
+            temporality: None,
+            data_points: Some(DataPoints::Number(vec![SampleNumberDataPoint {
+                attributes: vec![SampleAttribute::try_from(attribute).unwrap()],
+                value: json!(1),
+                flags: 0,
+                live_check_result: None,
+                exemplars: vec![],
+            }])),
+            live_check_result: None,
+            source: None,
+        })
+    };
+    let mut samples = [
+        make_metric("system.memory.usage", "system.memory.state=used"),
+        make_metric("system.memory.total", "system.memory.state=used"),
+    ];
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+    assert_eq!(stats.advice_type_counts.get("missing_metric"), Some(&1));
+    assert_eq!(stats.advice_type_counts.get("missing_attribute"), None);
+    assert_eq!(stats.advice_type_counts.get("undefined_enum_variant"), None);
+
+    let Err(Error::RegistryResolutionError { path, .. }) =
+        LiveChecker::from_registry_dir(Path::new("data/no_such_model"))
+    else {
+        panic!("Expected a resolution error");
+    };
+    assert_eq!(path, "data/no_such_model");
+}
+
+#[test]
+fn test_merged_registries() {
+    let public = make_registry();
+    let mut private_group = public.groups[0].clone();
+    private_group.id = "private.test".to_owned();
+    let mut private_string = private_group.attributes[0].clone();
+    private_string.r#type = AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Int);
+    private_group.attributes = vec![private_string];
+    let private = ResolvedRegistry {
+        registry_url: "private".to_owned(),
+        groups: vec![private_group],
+    };
+
+    let (registry, origins) = merge_registries(vec![
+        ("private".to_owned(), private),
+        ("public".to_owned(), public),
+    ]);
+    assert_eq!(registry.groups.len(), 2);
+    assert_eq!(origins.attribute("test.string"), Some("private"));
+    assert_eq!(origins.attribute("test.enum"), Some("public"));
+    assert_eq!(origins.group("test.comprehensive.internal"), Some("public"));
+
+    let mut live_checker = LiveChecker::builder(registry)
+        .without_default_advisors()
+        .with_advisor(Box::new(TypeAdvisor))
+        .with_advisor(Box::new(EnumAdvisor::default()))
+        .registry_origins(origins)
+        .build();
+    let mut check = |sample: &str| check_attribute(&mut live_checker, sample);
+
+    // `test.string` resolves to the private int definition
+    assert!(check("test.string=42").is_empty());
+    let advice = check("test.string=value");
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_type, "type_mismatch");
+    assert_eq!(advice[0].advice_context["expected"], json!("int"));
+    assert_eq!(advice[0].registry, Some("private".to_owned()));
+
+    // `test.enum` is only defined in the public registry
+    let advice = check("test.enum=unknown_variant");
+    assert!(!advice.is_empty());
+    assert!(advice
+        .iter()
+        .all(|advice| advice.registry == Some("public".to_owned())));
+}
+
+#[test]
+fn test_group_coverage() {
+    let mut registry = make_registry();
+    let group = registry.groups[0].clone();
+    registry.groups.push(ResolvedGroup {
+        id: "test.covered".to_owned(),
+        attributes: group.attributes[..2].to_vec(),
+        ..group
+    });
+    let mut live_checker = LiveChecker::builder(registry).build();
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    for (index, sample) in [
+        "test.string=value",
+        "test.string=42",
+        "test.enum=example_variant1",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let mut sample = Sample::Attribute(SampleAttribute::try_from(sample).unwrap());
+        let _ = live_checker
+            .check_sample(index, &mut sample, &mut stats)
+            .unwrap();
+    }
+
+    let coverage = live_checker.group_coverage();
+    assert_eq!(
+        coverage["test.covered"],
+        GroupCoverage {
+            seen: 2,
+            missing: 0,
+            type_mismatched: 1,
+        }
+    );
+    assert_eq!(
+        coverage["test.comprehensive.internal"],
+        GroupCoverage {
+            seen: 2,
+            missing: 2,
+            type_mismatched: 1,
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(&coverage["test.covered"]).unwrap(),
+        json!({ "seen": 2, "missing": 0, "type_mismatched": 1 })
+    );
+}
+
+#[test]
+fn test_advise_into() {
+    let live_checker = LiveChecker::new(make_metrics_registry(), vec![]);
+    let group = live_checker.find_metric("system.memory.usage");
+    let metric = SampleMetric {
+        name: "system.memory.usage_bytes".to_owned(),
+        instrument: SampleInstrument::Supported(InstrumentSpec::Gauge),
+        unit: "KiBy".to_owned(),
+        temporality: None,
+        data_points: None,
+        live_check_result: None,
+        source: None,
+    };
+    let data_point = SampleNumberDataPoint {
+        attributes: vec![],
+        value: json!(0),
+        flags: 0,
+        exemplars: vec![],
+        live_check_result: None,
+    };
+    let signal = Sample::Metric(metric.clone());
+    let samples = [
+        SampleRef::Metric(&metric),
+        SampleRef::NumberDataPoint(&data_point),
+    ];
+
+    // `TypeAdvisor` implements `advise_into`, `MetricNameAdvisor` implements `advise`
+    let mut advisors: Vec<Box<dyn Advisor>> =
+        vec![Box::new(TypeAdvisor), Box::new(MetricNameAdvisor)];
+    for advisor in &mut advisors {
+        for sample in &samples {
+            let advice = advisor
+                .advise(sample.clone(), &signal, None, group.clone())
+                .unwrap();
+            let mut advice_into = Vec::new();
+            advisor
+                .advise_into(
+                    sample.clone(),
+                    &signal,
+                    None,
+                    group.clone(),
+                    &mut |advice| {
+                        advice_into.push(advice);
+                    },
+                )
+                .unwrap();
+            assert_eq!(advice, advice_into, "{}", advisor.id());
+        }
+    }
+
+    // Both paths give advice on the metric and its data point
+    let advice = TypeAdvisor
+        .advise(SampleRef::Metric(&metric), &signal, None, group.clone())
+        .unwrap();
+    assert_eq!(advice.len(), 2);
+    let advice = TypeAdvisor
+        .advise(
+            SampleRef::NumberDataPoint(&data_point),
+            &signal,
+            None,
+            group,
+        )
+        .unwrap();
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_type, "recommended_attribute_not_present");
+}
+
+#[test]
+fn test_advisor_id() {
+    let registry = make_registry();
+    let mut samples = vec![
+        Sample::Attribute(SampleAttribute::try_from("test.string=42").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("unknown.attribute=42").unwrap()),
+    ];
+
+    let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
+    let mut live_checker = LiveChecker::new(registry, advisors);
+    let rego_advisor =
+        RegoAdvisor::new(&live_checker, &[], &None).expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    check_samples(&mut live_checker, &mut samples);
+
+    let all_advice = get_all_advice(&mut samples[0]);
+    assert_eq!(all_advice.len(), 1);
+    assert_eq!(all_advice[0].advice_type, "type_mismatch");
+    assert_eq!(all_advice[0].advisor_id, Some("type".to_owned()));
+
+    // Advice emitted by the live checker itself has no advisor
+    let all_advice = get_all_advice(&mut samples[1]);
+    assert!(all_advice
+        .iter()
+        .any(|advice| advice.advice_type == "missing_attribute" && advice.advisor_id.is_none()));
+    assert!(all_advice
+        .iter()
+        .filter(|advice| advice.advice_type != "missing_attribute")
+        .all(|advice| advice.advisor_id == Some("rego".to_owned())));
+}
+
+#[test]
+fn test_signal_advice_attribute() {
+    let mut sample = Sample::Span(SampleSpan {
+        name: "GET /users".to_owned(),
+        kind: SpanKindSpec::Server,
+        status: None,
+        attributes: vec![SampleAttribute::try_from("unknown.attribute=value").unwrap()],
+        span_events: vec![],
+        span_links: vec![],
+        live_check_result: None,
+        source: None,
+        trace_id: None,
+        span_id: None,
+    });
+    let mut live_checker = LiveChecker::new(make_registry(), default_advisors());
+    check_sample(&mut live_checker, &mut sample);
+
+    let Sample::Span(span) = &sample else {
+        panic!("Expected a span sample");
+    };
+    let advice = &span.attributes[0]
+        .live_check_result
+        .as_ref()
+        .unwrap()
+        .all_advice;
+    let missing = advice
+        .iter()
+        .find(|advice| advice.advice_type == "missing_attribute")
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&missing.attributes).unwrap(),
+        json!({"signal": "span"})
+    );
+
+    // A signal attribute set by the advisor is kept
+    let mut advice = missing.clone();
+    let _ = advice
+        .attributes
+        .insert("signal".to_owned(), json!("custom"));
+    let advice = with_signal_attribute(advice);
+    assert_eq!(advice.attributes["signal"], json!("custom"));
+
+    // Empty attributes are not serialized
+    let mut advice = advice;
+    advice.attributes.clear();
+    assert!(serde_json::to_value(&advice)
+        .unwrap()
+        .get("attributes")
+        .is_none());
+}
+
+#[test]
+fn test_no_registry_loaded() {
+    let registry = ResolvedRegistry {
+        registry_url: "TEST".to_owned(),
+        groups: vec![],
+    };
+    let mut live_checker = LiveChecker::new(registry, default_advisors());
+    let mut sample = Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap());
+    check_sample(&mut live_checker, &mut sample);
+    let batch_advice = live_checker
+        .finalize()
+        .expect("Failed to finalize advisors");
+    assert_eq!(batch_advice.len(), 1);
+    assert_eq!(batch_advice[0].advice_type, "no_registry_loaded");
+    assert_eq!(batch_advice[0].advice_level, AdviceLevel::Improvement);
+
+    // Not given with a registry
+    let mut live_checker = LiveChecker::new(make_registry(), default_advisors());
+    let batch_advice = live_checker
+        .finalize()
+        .expect("Failed to finalize advisors");
+    assert!(batch_advice.is_empty());
+}
+
+#[test]
+fn test_invalid_live_check_annotation() {
+    let annotation = |value: serde_json::Value| {
+        Some(BTreeMap::from([(
+            "live_check".to_owned(),
+            serde_json::from_value(value).unwrap(),
+        )]))
+    };
+    let mut registry = make_registry();
+    // A misspelled key, a group key on an attribute and a valid annotation
+    registry.groups[0].attributes[0].annotations = annotation(json!({"open_enums": true}));
+    registry.groups[0].attributes[1].annotations = annotation(json!({"any_of": [["a"]]}));
+    registry.groups[0].attributes[2].annotations = annotation(json!({"open_enum": true}));
+    registry.groups[0].annotations = annotation(json!({"temporality": "sometimes"}));
+
+    let mut live_checker = LiveChecker::new(registry, vec![]);
+    let batch_advice = live_checker
+        .finalize()
+        .expect("Failed to finalize advisors");
+    let reported: Vec<_> = batch_advice
+        .iter()
+        .map(|advice| {
+            assert_eq!(advice.advice_type, "invalid_live_check_annotation");
+            assert_eq!(advice.advice_level, AdviceLevel::Improvement);
+            (
+                advice.advice_context["group_id"].as_str().unwrap(),
+                advice.advice_context.get("attribute_name").cloned(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        reported,
+        vec![
+            ("test.comprehensive.internal", None),
+            ("test.comprehensive.internal", Some(json!("test.string"))),
+            ("test.comprehensive.internal", Some(json!("test.enum"))),
+        ]
+    );
+    assert!(batch_advice[1]
+        .message
+        .contains("unknown field `open_enums`"));
+}
+
+#[test]
+fn test_statistics_prometheus_text() {
+    let advice = |advice_type: &str, advice_level: AdviceLevel| {
+        Advice::new(
+            advice_type.into(),
+            json!({}),
+            "Advice".to_owned(),
+            advice_level,
+        )
+    };
+    let mut stats = LiveCheckStatistics::new(&make_registry());
+    stats.inc_entity_count("attribute");
+    stats.inc_entity_count("attribute");
+    stats.inc_entity_count("metric");
+    stats.add_batch_advice(&[
+        advice("type_mismatch", AdviceLevel::Violation),
+        advice("type_mismatch", AdviceLevel::Violation),
+        advice("type_mismatch", AdviceLevel::Improvement),
+        advice("deprecated", AdviceLevel::Violation),
+        advice("quote\"d", AdviceLevel::Information),
+    ]);
+    stats.finalize();
+
+    assert_eq!(
+        stats.to_prometheus_text(),
+        r#"# HELP weaver_live_check_advice_total The number of advice given by advice type and level.
+# TYPE weaver_live_check_advice_total counter
+weaver_live_check_advice_total{type="deprecated",level="violation"} 1
+weaver_live_check_advice_total{type="quote\"d",level="information"} 1
+weaver_live_check_advice_total{type="type_mismatch",level="improvement"} 1
+weaver_live_check_advice_total{type="type_mismatch",level="violation"} 2
+# HELP weaver_live_check_entities_total The number of sample entities checked by entity type.
+# TYPE weaver_live_check_entities_total counter
+weaver_live_check_entities_total{type="attribute"} 2
+weaver_live_check_entities_total{type="metric"} 1
+# HELP weaver_live_check_registry_coverage The fraction of the registry covered by the samples.
+# TYPE weaver_live_check_registry_coverage gauge
+weaver_live_check_registry_coverage 0
+"#
+    );
+
+    // Every series line is a metric name, labels and a value
+    let series =
+        Regex::new(r#"^weaver_live_check_[a-z_]+(\{([a-z]+="([^"\\]|\\.)*",?)+\})? [0-9.]+$"#)
+            .unwrap();
+    for line in stats.to_prometheus_text().lines() {
+        assert!(line.starts_with('#') || series.is_match(line), "{line}");
+    }
+}
+
+#[test]
+fn test_template_should_aggregate() {
+    let mut registry = make_registry();
+    let mut header_template = registry.groups[0]
+        .attributes
+        .iter()
+        .find(|attribute| attribute.name == "test.template")
+        .unwrap()
+        .clone();
+    header_template.name = "http.request.header".to_owned();
+    header_template.r#type = AttributeType::Template(TemplateTypeSpec::Strings);
+    registry.groups[0].attributes.push(header_template);
+
+    let mut sample = Sample::Span(SampleSpan {
+        name: "GET /users".to_owned(),
+        kind: SpanKindSpec::Server,
+        status: None,
+        attributes: vec![
+            SampleAttribute::try_from("http.request.header.set-cookie=a=1").unwrap(),
+            SampleAttribute::try_from("http.request.header.accept=text/html").unwrap(),
+            SampleAttribute::try_from("http.request.header.set-cookie=b=2").unwrap(),
+            SampleAttribute::try_from("http.request.header.set-cookie=c=3").unwrap(),
+            // Scalar templates are not expected to aggregate
+            SampleAttribute::try_from("test.template.my.key=x").unwrap(),
+            SampleAttribute::try_from("test.template.my.key=y").unwrap(),
+        ],
+        span_events: vec![],
+        span_links: vec![],
+        live_check_result: None,
+        source: None,
+        trace_id: None,
+        span_id: None,
+    });
+
+    let mut live_checker = LiveChecker::new(registry, vec![]);
+    check_sample(&mut live_checker, &mut sample);
+
+    let Sample::Span(span) = &sample else {
+        panic!("Expected a span sample");
+    };
+    let advice = &span.live_check_result.as_ref().unwrap().all_advice;
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_type, "template_should_aggregate");
+    assert_eq!(advice[0].advice_level, AdviceLevel::Information);
+    assert_eq!(
+        advice[0].advice_context,
+        json!({
+            "attribute_name": "http.request.header.set-cookie",
+            "template_name": "http.request.header",
+            "count": 3
+        })
+    );
+    assert_eq!(
+        advice[0].message,
+        "Attribute 'http.request.header.set-cookie' is present 3 times as a scalar value. Template 'http.request.header' expects a single array value."
+    );
+}
+
+/// An advisor counting the samples it advised on, with a type mismatch advice
+/// Reports the scope and service of each metric, from the OTLP context
+#[derive(Default)]
+struct ContextAdvisor {
+    context: OtlpContext,
+}
+
+impl Advisor for ContextAdvisor {
+    fn id(&self) -> &str {
+        "context"
+    }
+
+    fn advise(
+        &mut self,
+        sample: SampleRef<'_>,
+        signal: &Sample,
+        _registry_attribute: Option<Rc<Attribute>>,
+        _registry_group: Option<Rc<ResolvedGroup>>,
+    ) -> Result<Vec<Advice>, Error> {
+        let SampleRef::Metric(_) = sample else {
+            return Ok(Vec::new());
+        };
+        let scope = self.context.scope.as_ref();
+        Ok(vec![Advice {
+            signal_type: signal.signal_type(),
+            signal_name: signal.signal_name(),
+            ..Advice::new(
+                "metric_context".into(),
+                json!({
+                    "scope_name": scope.map(|scope| scope.name.clone()),
+                    "scope_version": scope.and_then(|scope| scope.version.clone()),
+                    "service_name": self.context.resource.as_ref().and_then(SampleResource::service_name),
+                }),
+                "Metric context".to_owned(),
+                AdviceLevel::Information,
+            )
+        }])
+    }
+
+    fn enter_context(&mut self, context: &OtlpContext) {
+        self.context = context.clone();
+    }
+}
+
+#[test]
+fn test_check_otlp_document() {
+    let make_metric = |name: &str| {
+        Sample::Metric(SampleMetric {
+            name: name.to_owned(),
+            instrument: SampleInstrument::Supported(InstrumentSpec::UpDownCounter),
+            unit: "By".to_owned(),
+            temporality: None,
+            data_points: None,
+            live_check_result: None,
+            source: None,
+        })
+    };
+    let mut document = OtlpDocument {
+        resources: vec![OtlpResource {
+            resource: SampleResource {
+                attributes: vec![SampleAttribute::try_from("service.name=checkout").unwrap()],
+                live_check_result: None,
+                source: None,
+            },
+            scopes: vec![OtlpScope {
+                scope: SampleScope {
+                    name: "io.opentelemetry.runtime".to_owned(),
+                    version: Some("1.2.0".to_owned()),
+                    attributes: vec![],
+                    live_check_result: None,
+                    source: None,
+                },
+                signals: vec![
+                    make_metric("system.memory.usage"),
+                    make_metric("system.memory.limit"),
+                ],
+            }],
+        }],
+    };
+
+    let mut live_checker = LiveChecker::builder(make_metrics_registry())
+        .without_default_advisors()
+        .with_advisor(Box::new(ContextAdvisor::default()))
+        .build();
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    let stopped = live_checker
+        .check_otlp_document(&mut document, &mut stats)
+        .expect("Failed to check the document");
+    assert!(!stopped);
+
+    // Both metrics were advised knowing their resource and scope
+    let scope = &document.resources[0].scopes[0];
+    for signal in &scope.signals {
+        let Sample::Metric(metric) = signal else {
+            panic!("Expected a metric sample");
+        };
+        let advice = &metric.live_check_result.as_ref().unwrap().all_advice;
+        let context = advice
+            .iter()
+            .find(|advice| advice.advice_type == "metric_context")
+            .unwrap();
+        assert_eq!(
+            context.advice_context,
+            json!({
+                "scope_name": "io.opentelemetry.runtime",
+                "scope_version": "1.2.0",
+                "service_name": "checkout"
+            })
+        );
+    }
+    // The resource and scope were checked too
+    assert!(document.resources[0].resource.live_check_result.is_some());
+    assert!(scope.scope.live_check_result.is_some());
+    // The context is reset once done
+    assert_eq!(live_checker.otlp_context(), &OtlpContext::default());
+
+    // The flat samples of the OTLP receiver set the context the same way
+    let otlp_resource = &document.resources[0];
+    let mut samples = [
+        Sample::Resource(otlp_resource.resource.clone()),
+        Sample::Scope(otlp_resource.scopes[0].scope.clone()),
+        make_metric("system.memory.usage"),
+    ];
+    for (index, sample) in samples.iter_mut().enumerate() {
+        assert!(!live_checker
+            .check_sample(index, sample, &mut stats)
+            .expect("Failed to check the sample"));
+    }
+    let Sample::Metric(metric) = &samples[2] else {
+        panic!("Expected a metric sample");
+    };
+    let advice = &metric.live_check_result.as_ref().unwrap().all_advice;
+    assert!(advice
+        .iter()
+        .any(|advice| advice.advice_type == "metric_context"
+            && advice.advice_context["service_name"] == "checkout"));
+}
+
+#[test]
+fn test_max_level() {
+    // Advice levels are ordered by severity
+    assert!(AdviceLevel::Information < AdviceLevel::Improvement);
+    assert!(AdviceLevel::Improvement < AdviceLevel::Violation);
+    assert!(AdviceLevel::Information < AdviceLevel::Violation);
+    assert_eq!(
+        [
+            AdviceLevel::Violation,
+            AdviceLevel::Information,
+            AdviceLevel::Improvement,
+        ]
+        .into_iter()
+        .max(),
+        Some(AdviceLevel::Violation)
+    );
+
+    let mut live_checker = LiveChecker::new(make_registry(), vec![]);
+    assert_eq!(live_checker.max_level(), None);
+
+    let check = |live_checker: &mut LiveChecker, attribute: &str| {
+        let _ = check_attribute(live_checker, attribute);
+    };
+    // A known attribute gets no advice
+    check(&mut live_checker, "test.string=value");
+    assert_eq!(live_checker.max_level(), None);
+    // A template attribute gets an information advice
+    check(&mut live_checker, "test.template.my.key=value");
+    assert_eq!(live_checker.max_level(), Some(&AdviceLevel::Information));
+    // A missing attribute gets a violation
+    check(&mut live_checker, "unknown.attribute=value");
+    assert_eq!(live_checker.max_level(), Some(&AdviceLevel::Violation));
+    // Less severe advice does not lower the maximum
+    check(&mut live_checker, "test.template.my.key=value");
+    assert_eq!(live_checker.max_level(), Some(&AdviceLevel::Violation));
+}
+
+#[test]
+fn test_advice_json_schema() {
+    let schema: serde_json::Value =
+        serde_json::from_str(&get_advice_json_schema().unwrap()).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    // Advice with the optional fields validates
+    let advice = Advice {
+        signal_type: Some("span".to_owned()),
+        signal_name: Some("query".to_owned()),
+        advisor_id: Some("deprecated".to_owned()),
+        count: Some(2),
+        confidence: Some(0.9),
+        fix: Some(AdviceFix::Rename {
+            from: "db.system".to_owned(),
+            to: "db.system.name".to_owned(),
+        }),
+        registry: Some("main".to_owned()),
+        ..Advice::new(
+            BuiltinAdviceType::Deprecated,
+            json!({"attribute_name": "db.system"}),
+            "Attribute 'db.system' is deprecated.".to_owned(),
+            AdviceLevel::Violation,
+        )
+    };
+    let instance = serde_json::to_value(&advice).unwrap();
+    assert!(validator.is_valid(&instance), "{instance}");
+
+    // So does advice without them
+    let instance = json!({
+        "advice_type": "missing_attribute",
+        "advice_context": {"attribute_name": "foo"},
+        "message": "Attribute 'foo' does not exist in the registry.",
+        "advice_level": "violation",
+        "signal_type": null,
+        "signal_name": null
+    });
+    assert!(validator.is_valid(&instance));
+
+    // An unknown advice level does not
+    let mut instance = instance;
+    instance["advice_level"] = json!("fatal");
+    assert!(!validator.is_valid(&instance));
+}
+
+#[test]
+fn test_matched_ref() {
+    let registry = make_registry();
+    let mut samples = vec![
+        Sample::Attribute(SampleAttribute::try_from("test.string=42").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("unknown.attribute=42").unwrap()),
+    ];
+    let mut live_checker = LiveChecker::new(registry, vec![Box::new(TypeAdvisor)]);
+    check_samples(&mut live_checker, &mut samples);
+
+    let all_advice = get_all_advice(&mut samples[0]);
+    assert_eq!(all_advice[0].advice_type, "type_mismatch");
+    assert_eq!(
+        all_advice[0].matched_ref,
+        Some(MatchedRef {
+            attribute: "test.string".to_owned(),
+            group_id: "test.comprehensive.internal".to_owned(),
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(&all_advice[0]).unwrap()["matched_ref"],
+        json!({"attribute": "test.string", "group_id": "test.comprehensive.internal"})
+    );
+
+    // Without a match, nothing is serialized
+    let all_advice = get_all_advice(&mut samples[1]);
+    assert_eq!(all_advice[0].advice_type, "missing_attribute");
+    assert!(all_advice[0].matched_ref.is_none());
+    assert!(serde_json::to_value(&all_advice[0])
+        .unwrap()
+        .get("matched_ref")
+        .is_none());
+}
+
+#[test]
+fn test_statistics_yaml() {
+    let mut samples = vec![Sample::Attribute(
+        SampleAttribute::try_from("test.string=42").unwrap(),
+    )];
+    let mut live_checker = LiveChecker::new(make_registry(), vec![Box::new(TypeAdvisor)]);
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+
+    // Same content as the JSON
+    let yaml = stats.to_yaml().unwrap();
+    assert!(yaml.contains("type_mismatch: 1"));
+    assert_eq!(
+        serde_yaml::from_str::<serde_json::Value>(&yaml).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&stats).unwrap()).unwrap()
+    );
+}
@@ -0,0 +1,1139 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests of the options of the live checker and its builder.
+
+use super::*;
+
+#[test]
+fn test_experimental_opt_in() {
+    let mut registry = make_registry();
+    registry.groups[0].attributes[1].stability = Some(Stability::Development);
+
+    let run = |opt_in: Option<HashSet<String>>| {
+        let mut samples = vec![
+            Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.enum=example_variant1").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.deprecated=value").unwrap()),
+        ];
+        let mut live_checker = LiveChecker::new(registry.clone(), vec![]);
+        if let Some(opt_in) = opt_in {
+            live_checker.set_experimental_opt_in(opt_in);
+        }
+        check_samples(&mut live_checker, &mut samples);
+        samples
+            .iter_mut()
+            .map(|sample| get_all_advice(sample).to_vec())
+            .collect::<Vec<_>>()
+    };
+
+    // Without an opt-in set experimental attributes are not reported
+    assert!(run(None).iter().all(Vec::is_empty));
+
+    let advice = run(Some(HashSet::from(["test.enum".to_owned()])));
+    assert!(advice[0].is_empty());
+    assert!(advice[1].is_empty());
+    assert_eq!(advice[2].len(), 1);
+    assert_eq!(
+        advice[2][0].advice_type,
+        "experimental_attribute_not_opted_in"
+    );
+    assert_eq!(advice[2][0].advice_level, AdviceLevel::Improvement);
+    assert_eq!(
+        advice[2][0].advice_context,
+        json!({"attribute_name": "test.deprecated"})
+    );
+}
+
+#[test]
+fn test_stability_regression() {
+    // `test.string` is stable in the baseline registry but experimental now
+    let baseline_registry = make_registry();
+    let mut registry = make_registry();
+    registry.groups[0].attributes[0].stability = Some(Stability::Development);
+
+    let mut samples = vec![
+        Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.enum=example_variant1").unwrap()),
+    ];
+
+    let mut live_checker = LiveChecker::builder(registry)
+        .without_default_advisors()
+        .baseline_registry(&baseline_registry)
+        .build();
+    check_samples(&mut live_checker, &mut samples);
+
+    let all_advice = get_all_advice(&mut samples[0]);
+    assert_eq!(all_advice.len(), 1);
+    assert_eq!(all_advice[0].advice_type, "stability_regression");
+    assert_eq!(all_advice[0].advice_level, AdviceLevel::Violation);
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({
+            "attribute_name": "test.string",
+            "stability": "development",
+            "baseline_stability": "stable",
+        })
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'test.string' is development but was stable in the baseline registry."
+    );
+
+    // The stability is unchanged
+    assert!(get_all_advice(&mut samples[1]).is_empty());
+}
+
+#[test]
+fn test_fail_fast() {
+    let make_samples = || {
+        vec![
+            Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.string=42").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.string=43").unwrap()),
+        ]
+    };
+    let run = |fail_fast: bool, samples: &mut Vec<Sample>| {
+        let mut live_checker = LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .with_advisor(Box::new(TypeAdvisor))
+            .fail_fast(fail_fast)
+            .build();
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        for (index, sample) in samples.iter_mut().enumerate() {
+            if live_checker
+                .check_sample(index, sample, &mut stats)
+                .unwrap()
+            {
+                break;
+            }
+        }
+        stats
+    };
+    let is_checked = |sample: &Sample| match sample {
+        Sample::Attribute(attribute) => attribute.live_check_result.is_some(),
+        _ => panic!("Expected an attribute sample"),
+    };
+
+    // Processing stops at the first violation
+    let mut samples = make_samples();
+    let stats = run(true, &mut samples);
+    assert!(is_checked(&samples[0]));
+    assert!(is_checked(&samples[1]));
+    assert!(!is_checked(&samples[2]));
+    let stopped_early = stats.stopped_early.unwrap();
+    assert_eq!(stopped_early.sample_index, 1);
+    assert_eq!(stopped_early.violation.advice_type, "type_mismatch");
+
+    // Disabled by default, all the samples are checked
+    let mut samples = make_samples();
+    let stats = run(false, &mut samples);
+    assert!(samples.iter().all(is_checked));
+    assert!(stats.stopped_early.is_none());
+}
+
+#[test]
+fn test_progress_callback() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorded = calls.clone();
+    let mut live_checker = LiveChecker::builder(make_registry())
+        .without_default_advisors()
+        .with_advisor(Box::new(TypeAdvisor))
+        .progress(
+            3,
+            Box::new(move |processed, violations| {
+                recorded.borrow_mut().push((processed, violations));
+            }),
+        )
+        .build();
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    for index in 0..10 {
+        let value = if index < 4 { "value" } else { "42" };
+        let mut sample = Sample::Attribute(
+            SampleAttribute::try_from(format!("test.string={value}").as_str()).unwrap(),
+        );
+        let _ = live_checker
+            .check_sample(index, &mut sample, &mut stats)
+            .unwrap();
+    }
+
+    // Invoked after samples 3, 6 and 9, samples 5 onwards are type mismatches
+    assert_eq!(*calls.borrow(), vec![(3, 0), (6, 2), (9, 5)]);
+
+    // No callback by default
+    let mut live_checker = LiveChecker::new(make_registry(), vec![]);
+    let mut sample = Sample::Attribute(SampleAttribute::try_from("test.string").unwrap());
+    assert!(!live_checker
+        .check_sample(0, &mut sample, &mut stats)
+        .unwrap());
+}
+
+#[test]
+fn test_advisor_timing() {
+    let mut live_checker = LiveChecker::builder(make_registry())
+        .advisor_timing(true)
+        .build();
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    let mut sample = Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap());
+    let _ = live_checker
+        .check_sample(0, &mut sample, &mut stats)
+        .unwrap();
+    let _ = live_checker.finalize().unwrap();
+
+    let timings = live_checker.advisor_timings().expect("timings");
+    let json = serde_json::to_value(timings).unwrap();
+    for advisor in &live_checker.advisors {
+        assert!(timings.get(advisor.id()).is_some(), "{}", advisor.id());
+        assert!(json[advisor.id()].is_f64());
+    }
+    assert_eq!(timings.iter().count(), live_checker.advisors.len());
+
+    // Off by default
+    let live_checker = LiveChecker::builder(make_registry()).build();
+    assert!(live_checker.advisor_timings().is_none());
+}
+
+/// An advisor giving an improvement advice on every attribute
+struct CustomAdvisor;
+
+impl Advisor for CustomAdvisor {
+    fn id(&self) -> &str {
+        "custom"
+    }
+
+    fn advise(
+        &mut self,
+        sample: SampleRef<'_>,
+        _signal: &Sample,
+        _registry_attribute: Option<Rc<Attribute>>,
+        _registry_group: Option<Rc<ResolvedGroup>>,
+    ) -> Result<Vec<Advice>, Error> {
+        let SampleRef::Attribute(attribute) = sample else {
+            return Ok(Vec::new());
+        };
+        Ok(vec![Advice::new(
+            "custom".into(),
+            json!({"attribute_name": attribute.name}),
+            "Custom advice".to_owned(),
+            AdviceLevel::Improvement,
+        )])
+    }
+}
+
+fn run_builder(builder: LiveCheckerBuilder) -> Vec<Advice> {
+    let mut live_checker = builder.build();
+    check_attribute(&mut live_checker, "test.deprecated=value")
+}
+
+#[test]
+fn test_builder() {
+    // The default advisors run
+    let advice = run_builder(LiveChecker::builder(make_registry()));
+    assert!(advice
+        .iter()
+        .any(|advice| advice.advisor_id == Some("deprecated".to_owned())));
+
+    // Only the custom advisor runs
+    let advice = run_builder(
+        LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .with_advisor(Box::new(CustomAdvisor)),
+    );
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advisor_id, Some("custom".to_owned()));
+    assert_eq!(advice[0].advice_level, AdviceLevel::Improvement);
+
+    // Strict mode raises improvements to violations
+    let advice = run_builder(
+        LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .with_advisor(Box::new(CustomAdvisor))
+            .strict(true),
+    );
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_level, AdviceLevel::Violation);
+
+    // Suppressed advice is dropped
+    let advice = run_builder(
+        LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .with_advisor(Box::new(CustomAdvisor))
+            .suppress(Suppression::new("test.*", "custom").unwrap()),
+    );
+    assert!(advice.is_empty());
+}
+
+#[test]
+fn test_name_filter() {
+    let run = |filter: NameFilter| {
+        let mut live_checker = LiveChecker::builder(make_registry()).filter(filter).build();
+        let mut samples = vec![
+            Sample::Attribute(SampleAttribute::try_from("db.system=postgresql").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("db.query.text=SELECT 1").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("http.route=/users").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
+        ];
+        let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+        let checked: Vec<String> = samples
+            .iter()
+            .filter_map(|sample| match sample {
+                Sample::Attribute(attribute) if attribute.live_check_result.is_some() => {
+                    Some(attribute.name.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        (checked, stats.total_entities)
+    };
+
+    // Only the included namespace is checked
+    let (checked, total_entities) = run(NameFilter::new().include("db.*").unwrap());
+    assert_eq!(checked, vec!["db.system", "db.query.text"]);
+    assert_eq!(total_entities, 2);
+
+    // The excluded namespace is dropped
+    let (checked, total_entities) = run(NameFilter::new().exclude("db.*").unwrap());
+    assert_eq!(checked, vec!["http.route", "test.string"]);
+    assert_eq!(total_entities, 2);
+
+    // Exclusions apply to the included names
+    let (checked, _) = run(NameFilter::new()
+        .include("db.*")
+        .unwrap()
+        .exclude("db.query.*")
+        .unwrap());
+    assert_eq!(checked, vec!["db.system"]);
+
+    assert!(matches!(
+        NameFilter::new().include("db.[*"),
+        Err(Error::InvalidFilterPattern { .. })
+    ));
+}
+
+#[test]
+fn test_post_process() {
+    let live_checker = LiveChecker::builder(make_registry())
+        .without_default_advisors()
+        // Redact the values looking like tokens
+        .post_process(Box::new(|report: &mut LiveCheckReport| {
+            for sample in &mut report.samples {
+                if let Sample::Attribute(attribute) = sample {
+                    if attribute
+                        .value
+                        .as_ref()
+                        .and_then(serde_json::Value::as_str)
+                        .is_some_and(|value| value.starts_with("ghp_"))
+                    {
+                        attribute.value = Some(json!("<redacted>"));
+                    }
+                }
+            }
+        }))
+        // Runs after the redaction
+        .post_process(Box::new(|report: &mut LiveCheckReport| {
+            let redacted = report
+                .samples
+                .iter()
+                .filter(|sample| {
+                    matches!(sample, Sample::Attribute(attribute)
+                        if attribute.value == Some(json!("<redacted>")))
+                })
+                .count();
+            report.batch_advice.push(Advice::new(
+                "redacted_values".into(),
+                json!({ "count": redacted }),
+                format!("{redacted} values were redacted."),
+                AdviceLevel::Information,
+            ));
+        }))
+        .build();
+
+    let mut report = LiveCheckReport {
+        samples: vec![
+            Sample::Attribute(SampleAttribute::try_from("test.string=ghp_secret").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.string=value").unwrap()),
+        ],
+        batch_advice: vec![],
+        statistics: LiveCheckStatistics::new(&live_checker.registry),
+    };
+    live_checker.post_process(&mut report);
+
+    let Sample::Attribute(redacted) = &report.samples[0] else {
+        panic!("Expected an attribute sample");
+    };
+    assert_eq!(redacted.value, Some(json!("<redacted>")));
+    let Sample::Attribute(untouched) = &report.samples[1] else {
+        panic!("Expected an attribute sample");
+    };
+    assert_eq!(untouched.value, Some(json!("value")));
+    assert_eq!(report.batch_advice.len(), 1);
+    assert_eq!(report.batch_advice[0].advice_context, json!({"count": 1}));
+}
+
+#[test]
+fn test_advice_level_override() {
+    let advisors: Vec<Box<dyn Advisor>> = vec![
+        Box::new(DeprecatedAdvisor::default()),
+        Box::new(StabilityAdvisor),
+    ];
+    let mut live_checker = LiveChecker::new(make_registry(), advisors);
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/live_check_advice/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+    live_checker.set_advice_level_override(
+        AdviceLevelOverride::new()
+            .with("not_stable", AdviceLevel::Violation)
+            .with("contains_test", AdviceLevel::Information),
+    );
+
+    let levels: BTreeMap<String, AdviceLevel> =
+        check_attribute(&mut live_checker, "test.deprecated=value")
+            .iter()
+            .map(|advice| (advice.advice_type.to_string(), advice.advice_level.clone()))
+            .collect();
+    assert_eq!(
+        levels,
+        BTreeMap::from([
+            // Untouched
+            ("deprecated".to_owned(), AdviceLevel::Violation),
+            // Builtin improvement raised
+            ("not_stable".to_owned(), AdviceLevel::Violation),
+            // Rego violation lowered
+            ("contains_test".to_owned(), AdviceLevel::Information),
+        ])
+    );
+}
+
+#[test]
+fn test_message_catalog() {
+    let run = |message_catalog: MessageCatalog| {
+        let mut live_checker = LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .with_advisor(Box::new(TypeAdvisor))
+            .message_catalog(message_catalog)
+            .build();
+        check_attribute(&mut live_checker, "test.string=42")
+            .iter()
+            .map(|advice| advice.message.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // The English messages by default
+    assert_eq!(
+        run(MessageCatalog::new()),
+        vec!["Attribute 'test.string' has type 'int'. Type should be 'string'."]
+    );
+
+    // Overridden, placeholders missing from the context are left untouched
+    let message_catalog = MessageCatalog::new().with(
+        "type_mismatch",
+        "Le type de '{attribute_name}' devrait être '{expected}', pas '{attribute_type}' {unknown}.",
+    );
+    assert_eq!(
+        run(message_catalog),
+        vec!["Le type de 'test.string' devrait être 'string', pas 'int' {unknown}."]
+    );
+}
+
+fn run_with_suppressions(suppressions: &[(&str, &str)], samples: &mut [Sample]) {
+    let advisors: Vec<Box<dyn Advisor>> = vec![
+        Box::new(DeprecatedAdvisor::default()),
+        Box::new(TypeAdvisor),
+    ];
+    let mut live_checker = LiveChecker::new(make_registry(), advisors);
+    for (name_pattern, advice_type) in suppressions {
+        live_checker.add_suppression(Suppression::new(name_pattern, advice_type).unwrap());
+    }
+    check_samples(&mut live_checker, samples);
+}
+
+fn advice_types(sample: &mut Sample) -> Vec<String> {
+    get_all_advice(sample)
+        .iter()
+        .map(|advice| advice.advice_type.to_string())
+        .collect()
+}
+
+#[test]
+fn test_suppressions() {
+    let make_samples = || {
+        vec![
+            Sample::Attribute(SampleAttribute::try_from("test.string=42").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("test.deprecated=value").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("legacy.code=42").unwrap()),
+        ]
+    };
+
+    let mut samples = make_samples();
+    run_with_suppressions(&[], &mut samples);
+    assert_eq!(advice_types(&mut samples[0]), vec!["type_mismatch"]);
+    assert_eq!(advice_types(&mut samples[1]), vec!["deprecated"]);
+    assert_eq!(advice_types(&mut samples[2]), vec!["missing_attribute"]);
+
+    // Suppressed advice is removed, other advice passes through
+    let mut samples = make_samples();
+    run_with_suppressions(
+        &[
+            ("test.*", "type_mismatch"),
+            ("legacy.*", "missing_attribute"),
+        ],
+        &mut samples,
+    );
+    assert!(advice_types(&mut samples[0]).is_empty());
+    assert_eq!(advice_types(&mut samples[1]), vec!["deprecated"]);
+    assert!(advice_types(&mut samples[2]).is_empty());
+
+    // A non-matching suppression has no effect
+    let mut samples = make_samples();
+    run_with_suppressions(
+        &[
+            ("legacy.*", "type_mismatch"),
+            ("test.*", "missing_attribute"),
+        ],
+        &mut samples,
+    );
+    assert_eq!(advice_types(&mut samples[0]), vec!["type_mismatch"]);
+    assert_eq!(advice_types(&mut samples[1]), vec!["deprecated"]);
+    assert_eq!(advice_types(&mut samples[2]), vec!["missing_attribute"]);
+
+    assert!(Suppression::new("legacy.[", "type_mismatch").is_err());
+}
+
+#[test]
+fn test_advice_baseline() {
+    let run = |live_checker: &mut LiveChecker, values: &[&str]| {
+        values
+            .iter()
+            .map(|value| check_attribute(live_checker, &format!("test.enum={value}")))
+            .collect::<Vec<_>>()
+    };
+
+    // Record the advice of a first run as the baseline
+    let mut live_checker = LiveCheckerBuilder::new(make_registry())
+        .without_default_advisors()
+        .with_advisor(Box::new(EnumAdvisor::default()))
+        .record_advice_baseline(true)
+        .build();
+    let _ = run(&mut live_checker, &["legacy", "removed"]);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("baseline.json");
+    live_checker.observed_advice().unwrap().save(&path).unwrap();
+    let baseline = AdviceBaseline::load(&path).unwrap();
+    assert_eq!(baseline.len(), 2);
+
+    let mut live_checker = LiveCheckerBuilder::new(make_registry())
+        .without_default_advisors()
+        .with_advisor(Box::new(EnumAdvisor::default()))
+        .advice_baseline(baseline)
+        .build();
+    let advice = run(&mut live_checker, &["legacy", "new"]);
+
+    // The accepted advice is not surfaced, the new finding is
+    assert!(advice[0].is_empty());
+    assert_eq!(advice[1].len(), 1);
+    assert_eq!(advice[1][0].advice_type, "undefined_enum_variant");
+    assert_eq!(advice[1][0].advice_context["attribute_value"], json!("new"));
+
+    // The baseline advice no longer given is reported as resolved
+    let batch_advice = live_checker.finalize().unwrap();
+    assert_eq!(batch_advice.len(), 1);
+    assert_eq!(batch_advice[0].advice_type, "baseline_advice_resolved");
+    assert_eq!(batch_advice[0].advice_level, AdviceLevel::Information);
+    assert_eq!(
+        batch_advice[0].advice_context["advice_type"],
+        json!("undefined_enum_variant")
+    );
+    assert!(batch_advice[0].advice_context["fingerprint"]
+        .as_str()
+        .unwrap()
+        .contains("removed"));
+
+    // Both the accepted and new advice are observed for the next baseline
+    assert_eq!(live_checker.observed_advice().unwrap().len(), 2);
+}
+
+#[test]
+fn test_skip_after_violations() {
+    /// Flags every attribute as a type mismatch
+    struct TypeMismatchAdvisor;
+
+    impl Advisor for TypeMismatchAdvisor {
+        fn id(&self) -> &str {
+            "type_mismatch"
+        }
+
+        fn advise(
+            &mut self,
+            sample: SampleRef<'_>,
+            _signal: &Sample,
+            _registry_attribute: Option<Rc<Attribute>>,
+            _registry_group: Option<Rc<ResolvedGroup>>,
+        ) -> Result<Vec<Advice>, Error> {
+            let SampleRef::Attribute(attribute) = sample else {
+                return Ok(Vec::new());
+            };
+            Ok(vec![Advice::new(
+                BuiltinAdviceType::TypeMismatch,
+                json!({"attribute_name": attribute.name}),
+                "Type mismatch".to_owned(),
+                AdviceLevel::Violation,
+            )])
+        }
+    }
+
+    let run = |advisors: Vec<Box<dyn Advisor>>| {
+        let mut live_checker = LiveChecker::new(make_registry(), advisors);
+        check_attribute(&mut live_checker, "test.enum=unknown")
+            .iter()
+            .map(|advice| advice.advice_type.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // On its own, the enum advisor flags the unknown value
+    assert_eq!(
+        run(vec![Box::new(EnumAdvisor::default())]),
+        vec!["undefined_enum_variant"]
+    );
+
+    // A type mismatch given earlier skips the enum advisor
+    assert_eq!(
+        run(vec![
+            Box::new(TypeMismatchAdvisor),
+            Box::new(EnumAdvisor::default())
+        ]),
+        vec!["type_mismatch"]
+    );
+
+    // Advisors run in order, a later type mismatch does not skip it
+    assert_eq!(
+        run(vec![
+            Box::new(EnumAdvisor::default()),
+            Box::new(TypeMismatchAdvisor)
+        ]),
+        vec!["undefined_enum_variant", "type_mismatch"]
+    );
+}
+
+#[test]
+fn test_dedup() {
+    let run = |dedup: bool| {
+        // Two advisors giving the same advice for the same sample
+        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor), Box::new(TypeAdvisor)];
+        let mut live_checker = LiveChecker::new(make_registry(), advisors);
+        live_checker.set_dedup(dedup);
+        let all_advice = check_attribute(&mut live_checker, "test.string=42");
+        (all_advice, live_checker.violation_count)
+    };
+
+    // Disabled by default, both advice are kept
+    let (all_advice, violation_count) = run(false);
+    assert_eq!(all_advice.len(), 2);
+    assert!(all_advice.iter().all(|advice| advice.count.is_none()));
+    assert_eq!(violation_count, 2);
+
+    // The violation count, shown by the progress, agrees with the deduplicated advice
+    let (all_advice, violation_count) = run(true);
+    assert_eq!(all_advice.len(), 1);
+    assert_eq!(all_advice[0].advice_type, "type_mismatch");
+    assert_eq!(all_advice[0].count, Some(2));
+    assert_eq!(violation_count, 1);
+}
+
+#[test]
+fn test_sort_advice() {
+    /// Gives the same advice list on every span
+    struct FixedAdvisor(Vec<Advice>);
+
+    impl Advisor for FixedAdvisor {
+        fn id(&self) -> &str {
+            "fixed"
+        }
+
+        fn advise(
+            &mut self,
+            sample: SampleRef<'_>,
+            _signal: &Sample,
+            _registry_attribute: Option<Rc<Attribute>>,
+            _registry_group: Option<Rc<ResolvedGroup>>,
+        ) -> Result<Vec<Advice>, Error> {
+            let SampleRef::Span(_) = sample else {
+                return Ok(Vec::new());
+            };
+            Ok(self.0.clone())
+        }
+    }
+
+    let make_advice = |advice_level: AdviceLevel, advice_type: &str, name: &str| Advice {
+        signal_type: Some("span".to_owned()),
+        signal_name: Some("GET /users".to_owned()),
+        ..Advice::new(
+            advice_type.into(),
+            json!({"attribute_name": name}),
+            format!("{advice_type} on {name}"),
+            advice_level,
+        )
+    };
+
+    let first = vec![
+        make_advice(AdviceLevel::Improvement, "b_advice", "http.route"),
+        make_advice(AdviceLevel::Violation, "z_advice", "http.route"),
+        // Equal to the next one on the sort keys, kept in this order
+        make_advice(AdviceLevel::Information, "a_advice", "http.route"),
+    ];
+    let second = vec![
+        make_advice(AdviceLevel::Information, "a_advice", "http.route"),
+        make_advice(AdviceLevel::Improvement, "b_advice", "db.system"),
+        make_advice(AdviceLevel::Violation, "c_advice", "db.system"),
+    ];
+    let run = |advisors: Vec<Box<dyn Advisor>>, sort_advice: bool| {
+        let mut live_checker = LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .sort_advice(sort_advice)
+            .build();
+        for advisor in advisors {
+            live_checker.add_advisor(advisor);
+        }
+        let mut sample = Sample::Span(SampleSpan {
+            name: "GET /users".to_owned(),
+            kind: SpanKindSpec::Server,
+            status: None,
+            attributes: vec![],
+            span_events: vec![],
+            span_links: vec![],
+            live_check_result: None,
+            source: None,
+            trace_id: None,
+            span_id: None,
+        });
+        check_sample(&mut live_checker, &mut sample);
+        let Sample::Span(span) = sample else {
+            panic!("Expected a span sample");
+        };
+        span.live_check_result
+            .unwrap()
+            .all_advice
+            .iter()
+            .map(|advice| {
+                (
+                    advice.advice_level.clone(),
+                    advice.advice_type.to_string(),
+                    advice.advice_context["attribute_name"].clone(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Unsorted, advice is in the order the advisors gave it
+    let unsorted = run(
+        vec![
+            Box::new(FixedAdvisor(first.clone())),
+            Box::new(FixedAdvisor(second.clone())),
+        ],
+        false,
+    );
+    assert_eq!(unsorted[0].1, "b_advice");
+
+    // Sorted by level desc, advice type and sample name, whatever the advisor order
+    let sorted = run(
+        vec![
+            Box::new(FixedAdvisor(first.clone())),
+            Box::new(FixedAdvisor(second.clone())),
+        ],
+        true,
+    );
+    assert_eq!(
+        sorted,
+        vec![
+            (
+                AdviceLevel::Violation,
+                "c_advice".to_owned(),
+                json!("db.system")
+            ),
+            (
+                AdviceLevel::Violation,
+                "z_advice".to_owned(),
+                json!("http.route")
+            ),
+            (
+                AdviceLevel::Improvement,
+                "b_advice".to_owned(),
+                json!("db.system")
+            ),
+            (
+                AdviceLevel::Improvement,
+                "b_advice".to_owned(),
+                json!("http.route")
+            ),
+            (
+                AdviceLevel::Information,
+                "a_advice".to_owned(),
+                json!("http.route")
+            ),
+            (
+                AdviceLevel::Information,
+                "a_advice".to_owned(),
+                json!("http.route")
+            ),
+        ]
+    );
+    for _ in 0..3 {
+        assert_eq!(
+            run(
+                vec![
+                    Box::new(FixedAdvisor(second.clone())),
+                    Box::new(FixedAdvisor(first.clone())),
+                ],
+                true,
+            ),
+            sorted
+        );
+    }
+
+    // The sort is stable
+    let mut advice_list = vec![
+        make_advice(AdviceLevel::Information, "a_advice", "http.route"),
+        make_advice(AdviceLevel::Information, "a_advice", "http.route"),
+    ];
+    advice_list[0].message = "first".to_owned();
+    advice_list[1].message = "second".to_owned();
+    sort_advice_list(&mut advice_list);
+    assert_eq!(advice_list[0].message, "first");
+    assert_eq!(advice_list[1].message, "second");
+}
+
+struct CountingAdvisor {
+    runs: Rc<std::cell::Cell<usize>>,
+}
+
+impl Advisor for CountingAdvisor {
+    fn id(&self) -> &str {
+        "counting"
+    }
+
+    fn is_cacheable(&self) -> bool {
+        true
+    }
+
+    fn advise(
+        &mut self,
+        sample: SampleRef<'_>,
+        signal: &Sample,
+        registry_attribute: Option<Rc<Attribute>>,
+        registry_group: Option<Rc<ResolvedGroup>>,
+    ) -> Result<Vec<Advice>, Error> {
+        self.runs.set(self.runs.get() + 1);
+        TypeAdvisor.advise(sample, signal, registry_attribute, registry_group)
+    }
+}
+
+#[test]
+fn test_scoped_advisor() {
+    let runs = Rc::new(std::cell::Cell::new(0));
+    let mut live_checker = LiveChecker::builder(make_metrics_registry())
+        .without_default_advisors()
+        .with_scoped_advisor(
+            vec![SignalType::Metric],
+            Box::new(CountingAdvisor {
+                runs: Rc::clone(&runs),
+            }),
+        )
+        .build();
+
+    // A metric-only advisor doesn't run for a span sample, nor its attributes
+    let mut span = Sample::Span(SampleSpan {
+        name: "GET /users".to_owned(),
+        kind: SpanKindSpec::Server,
+        status: None,
+        attributes: vec![SampleAttribute::try_from("test.string=value").unwrap()],
+        span_events: vec![],
+        span_links: vec![],
+        live_check_result: None,
+        source: None,
+        trace_id: None,
+        span_id: None,
+    });
+    check_sample(&mut live_checker, &mut span);
+    assert_eq!(runs.get(), 0);
+
+    // It runs for a metric sample and its data points
+    let mut metric = Sample::Metric(SampleMetric {
+        name: "system.memory.usage".to_owned(),
+        instrument: SampleInstrument::Supported(InstrumentSpec::UpDownCounter),
+        unit: "By".to_owned(),
+        temporality: None,
+        data_points: Some(DataPoints::Number(vec![SampleNumberDataPoint {
+            attributes: vec![],
+            value: json!(1),
+            flags: 0,
+            live_check_result: None,
+            exemplars: vec![],
+        }])),
+        live_check_result: None,
+        source: None,
+    });
+    check_sample(&mut live_checker, &mut metric);
+    assert_eq!(runs.get(), 2);
+
+    // Builtins are scoped by default
+    let advisors = default_advisors();
+    let signal_types = |id: &str| {
+        advisors
+            .iter()
+            .find(|advisor| advisor.id() == id)
+            .map(|advisor| advisor.signal_types().to_vec())
+            .unwrap()
+    };
+    assert_eq!(signal_types("temporality"), vec![SignalType::Metric]);
+    assert_eq!(
+        signal_types("span_name_duplication"),
+        vec![SignalType::Span]
+    );
+    assert_eq!(signal_types("service_resource"), vec![SignalType::Resource]);
+    assert!(signal_types("type").is_empty());
+}
+
+#[test]
+fn test_advice_cache() {
+    let run = |cache_capacity: Option<usize>, samples: &[&str]| {
+        let runs = Rc::new(std::cell::Cell::new(0));
+        let mut builder = LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .with_advisor(Box::new(CountingAdvisor {
+                runs: Rc::clone(&runs),
+            }))
+            .with_advisor(Box::new(
+                ConstantAttributeAdvisor::new().with_min_samples(2),
+            ));
+        if let Some(cache_capacity) = cache_capacity {
+            builder = builder.advice_cache(cache_capacity);
+        }
+        let mut live_checker = builder.build();
+        let all_advice = samples
+            .iter()
+            .map(|sample| check_attribute(&mut live_checker, sample))
+            .collect::<Vec<_>>();
+        let batch_advice = live_checker.finalize().unwrap();
+        (runs.get(), all_advice, batch_advice)
+    };
+
+    // Without the cache, the advisors run on every sample
+    let (runs, _, _) = run(None, &["test.string=42", "test.string=42"]);
+    assert_eq!(runs, 2);
+
+    // The cacheable advisors run once for identical samples, which get the same advice
+    let (runs, all_advice, batch_advice) = run(Some(10), &["test.string=42", "test.string=42"]);
+    assert_eq!(runs, 1);
+    assert_eq!(all_advice[0].len(), 1);
+    assert_eq!(all_advice[0][0].advice_type, "type_mismatch");
+    assert_eq!(all_advice[0], all_advice[1]);
+    // The stateful advisors still see every sample
+    assert_eq!(batch_advice.len(), 1);
+    assert_eq!(batch_advice[0].advice_type, "constant_attribute");
+
+    // A different value is another sample
+    let (runs, _, _) = run(Some(10), &["test.string=42", "test.string=43"]);
+    assert_eq!(runs, 2);
+
+    // The least recently used sample is evicted
+    let (runs, _, _) = run(
+        Some(1),
+        &["test.string=42", "test.string=43", "test.string=42"],
+    );
+    assert_eq!(runs, 3);
+}
+
+#[test]
+fn test_advice_cache_sample_source() {
+    let runs = Rc::new(std::cell::Cell::new(0));
+    let mut live_checker = LiveChecker::builder(make_registry())
+        .without_default_advisors()
+        .with_advisor(Box::new(CountingAdvisor {
+            runs: Rc::clone(&runs),
+        }))
+        .advice_cache(10)
+        .build();
+    let source = |line| SampleSource {
+        file: Some("samples.jsonl".to_owned()),
+        line: Some(line),
+        record: line - 1,
+    };
+    let mut sources = Vec::new();
+    for line in [Some(1), Some(2), None] {
+        let mut sample = Sample::Attribute(SampleAttribute::try_from("test.string=42").unwrap());
+        if let Some(line) = line {
+            sample.set_source(source(line));
+        }
+        check_sample(&mut live_checker, &mut sample);
+        sources.push(get_all_advice(&mut sample)[0].source.clone());
+    }
+
+    // Samples read from different lines are identical for the cache, and each
+    // advice points to its own line. A sample without a source gets none.
+    assert_eq!(runs.get(), 1);
+    assert_eq!(sources, vec![Some(source(1)), Some(source(2)), None]);
+}
+
+#[test]
+fn test_match_strategy() {
+    let advice_types = |match_strategy: MatchStrategy, attribute: &str| {
+        let mut live_checker = LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .match_strategy(match_strategy)
+            .build();
+        check_attribute(&mut live_checker, attribute)
+            .iter()
+            .map(|advice| advice.advice_type.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Templated attributes match with both strategies
+    assert_eq!(
+        advice_types(MatchStrategy::Exact, "test.template.my.key=value"),
+        vec!["template_attribute"]
+    );
+    assert_eq!(
+        advice_types(MatchStrategy::Prefix, "test.template.my.key=value"),
+        vec!["template_attribute"]
+    );
+
+    // Only the prefix strategy matches a plain attribute as a template
+    assert_eq!(
+        advice_types(MatchStrategy::Exact, "test.string.my.key=value"),
+        vec!["missing_attribute"]
+    );
+    assert!(advice_types(MatchStrategy::Prefix, "test.string.my.key=value").is_empty());
+    // Prefixes are only taken at a `.` boundary
+    assert_eq!(
+        advice_types(MatchStrategy::Prefix, "test.stringify=value"),
+        vec!["missing_attribute"]
+    );
+
+    // A custom strategy maps the names of a custom naming scheme
+    let custom = || {
+        MatchStrategy::Custom(Box::new(|name: &str| {
+            name.strip_prefix("legacy_")
+                .map(|name| name.replace('_', "."))
+        }))
+    };
+    assert!(advice_types(custom(), "legacy_test_string=value").is_empty());
+    assert_eq!(
+        advice_types(custom(), "test.string=value"),
+        vec!["missing_attribute"]
+    );
+}
+
+#[test]
+fn test_name_normalizer() {
+    assert_eq!(NameNormalizer::None.normalize("http-request-method"), None);
+    assert_eq!(
+        NameNormalizer::Semconv.normalize("http-request-method"),
+        Some("http.request.method".to_owned())
+    );
+    assert_eq!(
+        NameNormalizer::Semconv.normalize("http.requestMethod"),
+        Some("http.request_method".to_owned())
+    );
+    // Only ASCII letters are changed
+    assert_eq!(
+        NameNormalizer::Semconv.normalize("http.request.méthod"),
+        None
+    );
+    assert_eq!(
+        NameNormalizer::Semconv.normalize("http.request.method"),
+        None
+    );
+
+    let advice = |name_normalizer: NameNormalizer, attribute: &str| {
+        let mut live_checker = LiveChecker::builder(make_registry())
+            .without_default_advisors()
+            .name_normalizer(name_normalizer)
+            .build();
+        check_attribute(&mut live_checker, attribute)
+    };
+
+    // A hyphenated name normalizes to a registry match
+    let advice_list = advice(NameNormalizer::Semconv, "test-string=value");
+    assert_eq!(advice_list.len(), 1);
+    assert_eq!(advice_list[0].advice_type, "name_normalized_to_match");
+    assert_eq!(advice_list[0].advice_level, AdviceLevel::Improvement);
+    assert_eq!(
+        advice_list[0].advice_context,
+        json!({"attribute_name": "test-string", "normalized_name": "test.string"})
+    );
+    assert_eq!(
+        advice_list[0].message,
+        "Attribute 'test-string' only matches the registry once normalized to 'test.string'."
+    );
+
+    // Registry names are matched as is
+    assert!(advice(NameNormalizer::Semconv, "test.string=value").is_empty());
+    // Without normalization, or when the normalized name isn't in the registry either
+    for (name_normalizer, attribute) in [
+        (NameNormalizer::None, "test-string=value"),
+        (NameNormalizer::Semconv, "test-unknown=value"),
+    ] {
+        let advice_list = advice(name_normalizer, attribute);
+        assert_eq!(advice_list.len(), 1);
+        assert_eq!(advice_list[0].advice_type, "missing_attribute");
+    }
+}
+
+#[test]
+fn test_severity_budget() {
+    // Two improvements, the registry attribute names being longer than 5
+    let check = |budget: SeverityBudget| {
+        let mut live_checker = LiveCheckerBuilder::new(make_registry())
+            .with_advisor(Box::new(NameLengthAdvisor::new().with_max_length(5)))
+            .budget(budget)
+            .build();
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        for (index, attribute) in ["test.string=a", "test.string=b"].iter().enumerate() {
+            let mut sample = Sample::Attribute(SampleAttribute::try_from(*attribute).unwrap());
+            let _ = live_checker
+                .check_sample(index, &mut sample, &mut stats)
+                .unwrap();
+        }
+        stats.finalize();
+        let result = live_checker.check_budget(&mut stats).unwrap();
+        assert_eq!(stats.budget.as_ref(), Some(&result));
+        result
+    };
+
+    // Exactly met
+    let result = check(SeverityBudget::new().with(AdviceLevel::Improvement, 2));
+    assert!(!result.is_exceeded());
+    assert_eq!(
+        result.levels,
+        vec![
+            BudgetUsage {
+                level: AdviceLevel::Violation,
+                count: 0,
+                max: 0,
+                excess: 0,
+            },
+            BudgetUsage {
+                level: AdviceLevel::Improvement,
+                count: 2,
+                max: 2,
+                excess: 0,
+            },
+        ]
+    );
+
+    // Exceeded
+    let result = check(SeverityBudget::new().with(AdviceLevel::Improvement, 1));
+    assert!(result.is_exceeded());
+    let exceeded: Vec<_> = result.exceeded().collect();
+    assert_eq!(exceeded.len(), 1);
+    assert_eq!(exceeded[0].level, AdviceLevel::Improvement);
+    assert_eq!(exceeded[0].excess, 1);
+
+    // Without a budget
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    assert!(live_checker.check_budget(&mut stats).is_none());
+    assert!(stats.budget.is_none());
+}
@@ -0,0 +1,578 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests of the Rego advice policies run by the live checker.
+
+use super::*;
+
+#[test]
+fn test_custom_rego() {
+    let registry = ResolvedRegistry {
+        registry_url: "TEST".to_owned(),
+        groups: vec![ResolvedGroup {
+            id: "custom.comprehensive.internal".to_owned(),
+            r#type: GroupType::Span,
+            brief: "".to_owned(),
+            note: "".to_owned(),
+            prefix: "".to_owned(),
+            entity_associations: vec![],
+            extends: None,
+            stability: Some(Stability::Stable),
+            deprecated: None,
+            attributes: vec![Attribute {
+                name: "custom.string".to_owned(),
+                r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+                examples: Some(Examples::Strings(vec![
+                    "value1".to_owned(),
+                    "value2".to_owned(),
+                ])),
+                brief: "".to_owned(),
+                tag: None,
+                requirement_level: RequirementLevel::Recommended {
+                    text: "".to_owned(),
+                },
+                sampling_relevant: None,
+                note: "".to_owned(),
+                stability: Some(Stability::Stable),
+                deprecated: None,
+                prefix: false,
+                tags: None,
+                value: None,
+                annotations: None,
+                role: Default::default(),
+            }],
+            span_kind: Some(SpanKindSpec::Internal),
+            events: vec![],
+            metric_name: None,
+            instrument: None,
+            unit: None,
+            name: None,
+            lineage: None,
+            display_name: None,
+            body: None,
+            annotations: None,
+        }],
+    };
+
+    let mut samples = vec![
+        Sample::Attribute(SampleAttribute::try_from("custom.string=hello").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("test.string").unwrap()),
+    ];
+
+    let advisors: Vec<Box<dyn Advisor>> = vec![];
+
+    let mut live_checker = LiveChecker::new(registry, advisors);
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/live_check_advice/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+
+    let all_advice = get_all_advice(&mut samples[0]);
+    assert!(all_advice.is_empty());
+
+    let all_advice = get_all_advice(&mut samples[1]);
+    assert_eq!(all_advice.len(), 2);
+
+    assert_eq!(all_advice[0].advice_type, "missing_attribute");
+    assert_eq!(
+        all_advice[0].advice_context,
+        json!({"attribute_name": "test.string"})
+    );
+    assert_eq!(
+        all_advice[0].message,
+        "Attribute 'test.string' does not exist in the registry."
+    );
+    assert_eq!(all_advice[1].advice_type, "contains_test");
+    assert_eq!(
+        all_advice[1].advice_context,
+        json!({"attribute_name": "test.string"})
+    );
+    assert_eq!(
+        all_advice[1].message,
+        "Attribute name must not contain 'test', but was 'test.string'"
+    );
+
+    // Check statistics
+    assert_eq!(stats.total_entities, 2);
+    assert_eq!(stats.total_advisories, 2);
+    assert_eq!(stats.advice_level_counts.len(), 1);
+    assert_eq!(stats.advice_level_counts[&AdviceLevel::Violation], 2);
+    assert_eq!(stats.highest_advice_level_counts.len(), 1);
+    assert_eq!(
+        stats.highest_advice_level_counts[&AdviceLevel::Violation],
+        1
+    );
+    assert_eq!(stats.no_advice_count, 1);
+}
+
+#[test]
+fn test_json_span_rego() {
+    let registry = make_registry();
+
+    // Load samples from JSON file
+    let path = "data/span.json";
+    let mut samples: Vec<Sample> =
+        serde_json::from_reader(File::open(path).expect("Unable to open file"))
+            .expect("Unable to parse JSON");
+
+    let mut live_checker = LiveChecker::new(registry, vec![]);
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/live_check_advice/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+
+    // Check the statistics
+    assert_eq!(
+        stats.advice_type_counts.get("contains_test_in_status"),
+        Some(&1)
+    );
+}
+
+#[test]
+fn test_json_metric_custom_rego() {
+    let registry = make_metrics_registry();
+
+    // Load samples from JSON file
+    let path = "data/metrics.json";
+    let mut samples: Vec<Sample> =
+        serde_json::from_reader(File::open(path).expect("Unable to open file"))
+            .expect("Unable to parse JSON");
+
+    let mut live_checker = LiveChecker::new(registry, vec![]);
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/live_check_advice/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, &mut samples);
+    assert_eq!(
+        stats.advice_type_counts.get("invalid_data_point_value"),
+        Some(&1)
+    );
+}
+
+#[test]
+fn test_bad_custom_rego() {
+    let registry = ResolvedRegistry {
+        registry_url: "TEST".to_owned(),
+        groups: vec![ResolvedGroup {
+            id: "custom.comprehensive.internal".to_owned(),
+            r#type: GroupType::Span,
+            brief: "".to_owned(),
+            note: "".to_owned(),
+            prefix: "".to_owned(),
+            entity_associations: vec![],
+            extends: None,
+            stability: Some(Stability::Stable),
+            deprecated: None,
+            attributes: vec![Attribute {
+                name: "custom.string".to_owned(),
+                r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+                examples: Some(Examples::Strings(vec![
+                    "value1".to_owned(),
+                    "value2".to_owned(),
+                ])),
+                brief: "".to_owned(),
+                tag: None,
+                requirement_level: RequirementLevel::Recommended {
+                    text: "".to_owned(),
+                },
+                sampling_relevant: None,
+                note: "".to_owned(),
+                stability: Some(Stability::Stable),
+                deprecated: None,
+                prefix: false,
+                tags: None,
+                value: None,
+                annotations: None,
+                role: Default::default(),
+            }],
+            span_kind: Some(SpanKindSpec::Internal),
+            events: vec![],
+            metric_name: None,
+            instrument: None,
+            unit: None,
+            name: None,
+            lineage: None,
+            display_name: None,
+            body: None,
+            annotations: None,
+        }],
+    };
+
+    let mut samples = vec![Sample::Attribute(
+        SampleAttribute::try_from("custom.string=hello").unwrap(),
+    )];
+
+    let advisors: Vec<Box<dyn Advisor>> = vec![];
+
+    let mut live_checker = LiveChecker::new(registry, advisors);
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/bad_advice/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+    for sample in &mut samples {
+        // This should fail with: "error: use of undefined variable `attribu1te_name` is unsafe"
+
+        let result = sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("use of undefined variable"));
+    }
+}
+
+#[test]
+fn test_rego_compile_error() {
+    let policy_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        policy_dir.path().join("valid.rego"),
+        "package live_check_advice\n\ndeny contains 1 if { false }\n",
+    )
+    .unwrap();
+    let broken_policy = policy_dir.path().join("broken.rego");
+    std::fs::write(
+        &broken_policy,
+        "package live_check_advice\n\ndeny contains x if {\n",
+    )
+    .unwrap();
+
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    let result = RegoAdvisor::new(&live_checker, &[policy_dir.path().to_path_buf()], &None);
+    let Err(Error::RegoCompileError {
+        policy_path,
+        message,
+    }) = result
+    else {
+        panic!("Expected a Rego compile error");
+    };
+    assert_eq!(policy_path, broken_policy.to_string_lossy());
+    assert!(!message.is_empty());
+}
+
+#[test]
+fn test_rego_batch_advice() {
+    // Fires once across the samples, referencing them by index
+    let rego = r#"package live_check_batch_advice
+
+import rego.v1
+
+deny contains {
+	"type": "advice",
+	"advice_type": "user_id_in_many_samples",
+	"advice_level": "improvement",
+	"advice_context": {"attribute_name": "user.id", "samples": indices},
+	"message": "Attribute 'user.id' is in many samples",
+} if {
+	indices := [i | some i, entry in input.samples; entry.sample.attribute.name == "user.id"]
+	count(indices) > 2
+}
+"#;
+    let check = |batch: bool| {
+        let mut live_checker = LiveChecker::new(make_registry(), vec![]);
+        let rego_advisor = RegoAdvisor::from_sources(
+            &live_checker,
+            &[PolicySource::Inline(rego.to_owned())],
+            None,
+        )
+        .expect("Failed to create Rego advisor")
+        .with_batch(batch);
+        live_checker.add_advisor(Box::new(rego_advisor));
+        let mut samples = ["user.id=1", "user.id=2", "test.string=x", "user.id=3"]
+            .map(|attribute| Sample::Attribute(SampleAttribute::try_from(attribute).unwrap()));
+        check_batch(&mut live_checker, &mut samples)
+    };
+
+    let advice = check(true);
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_type, "user_id_in_many_samples");
+    assert_eq!(advice[0].advisor_id.as_deref(), Some("rego"));
+    assert_eq!(
+        advice[0].advice_context,
+        json!({"attribute_name": "user.id", "samples": [0, 1, 3]})
+    );
+
+    // Not evaluated without the batch mode
+    assert!(check(false).is_empty());
+}
+
+#[test]
+fn test_rego_inline_sources() {
+    // The policy reads the forbidden word from the data produced by the jq filter
+    let rego = r#"package live_check_advice
+
+import rego.v1
+
+deny contains {
+	"type": "advice",
+	"advice_type": "forbidden_word",
+	"advice_level": "violation",
+	"advice_context": {"attribute_name": input.sample.attribute.name},
+	"message": "Forbidden word",
+} if {
+	contains(input.sample.attribute.name, data.forbidden)
+}
+"#;
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    let mut rego_advisor = RegoAdvisor::from_sources(
+        &live_checker,
+        &[PolicySource::Inline(rego.to_owned())],
+        Some(&PolicySource::Inline(r#"{"forbidden": "team"}"#.to_owned())),
+    )
+    .expect("Failed to create Rego advisor");
+    let mut advise = |sample: &str| {
+        let sample_attribute = SampleAttribute::try_from(sample).unwrap();
+        let sample = Sample::Attribute(sample_attribute.clone());
+        rego_advisor
+            .advise(SampleRef::Attribute(&sample_attribute), &sample, None, None)
+            .unwrap()
+    };
+    let advice = advise("org.team.name=hello");
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_type, "forbidden_word");
+    assert!(advise("org.name=hello").is_empty());
+
+    // Inline policies are named by index in the errors
+    let result = RegoAdvisor::from_sources(
+        &live_checker,
+        &[
+            PolicySource::Inline(rego.to_owned()),
+            PolicySource::Inline("package live_check_advice\n\ndeny contains x if {\n".to_owned()),
+        ],
+        None,
+    );
+    let Err(Error::RegoCompileError { policy_path, .. }) = result else {
+        panic!("Expected a Rego compile error");
+    };
+    assert_eq!(policy_path, "inline/1.rego");
+}
+
+#[test]
+fn test_multiple_rego_policy_dirs() {
+    let registry = make_registry();
+    let mut samples = vec![
+        Sample::Attribute(SampleAttribute::try_from("org.team.name=hello").unwrap()),
+        Sample::Attribute(SampleAttribute::try_from("org.name=hello").unwrap()),
+    ];
+
+    let mut live_checker = LiveChecker::new(registry, vec![]);
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &[
+            "data/policies/multi/org/".into(),
+            "data/policies/multi/team/".into(),
+        ],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    check_samples(&mut live_checker, &mut samples);
+
+    let mut advice_types: Vec<String> = get_all_advice(&mut samples[0])
+        .iter()
+        .map(|advice| advice.advice_type.to_string())
+        .collect();
+    advice_types.sort();
+    assert_eq!(
+        advice_types,
+        vec!["contains_org", "contains_team", "missing_attribute"]
+    );
+
+    let advice_types: Vec<String> = get_all_advice(&mut samples[1])
+        .iter()
+        .map(|advice| advice.advice_type.to_string())
+        .collect();
+    assert!(advice_types.contains(&"contains_org".to_owned()));
+    assert!(!advice_types.contains(&"contains_team".to_owned()));
+}
+
+#[test]
+fn test_unused_rego_policies() {
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    let mut rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &[
+            "data/policies/multi/org/".into(),
+            "data/policies/multi/team/".into(),
+        ],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor")
+    .with_policy_usage(true);
+
+    // Nothing was evaluated yet
+    assert_eq!(rego_advisor.unused_policies().unwrap().len(), 2);
+
+    // Only the org policy fires
+    let sample_attribute = SampleAttribute::try_from("org.name=hello").unwrap();
+    let sample = Sample::Attribute(sample_attribute.clone());
+    let advice = rego_advisor
+        .advise(SampleRef::Attribute(&sample_attribute), &sample, None, None)
+        .unwrap();
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].advice_type, "contains_org");
+
+    let unused = rego_advisor.unused_policies().unwrap();
+    assert_eq!(unused.len(), 1);
+    assert!(unused.iter().all(|path| path.ends_with("team.rego")));
+
+    // Only when requested
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/multi/org/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    assert!(rego_advisor.unused_policies().is_err());
+}
+
+#[test]
+fn test_rego_explain() {
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    let policy_dirs = [
+        "data/policies/multi/org/".into(),
+        "data/policies/multi/team/".into(),
+    ];
+    let jq_preprocessor = Some("data/jq/test.jq".into());
+    let sample_attribute = SampleAttribute::try_from("org.name=hello").unwrap();
+    let sample = Sample::Attribute(sample_attribute.clone());
+
+    // Off by default
+    let mut rego_advisor = RegoAdvisor::new(&live_checker, &policy_dirs, &jq_preprocessor)
+        .expect("Failed to create Rego advisor");
+    let advice = rego_advisor
+        .advise(SampleRef::Attribute(&sample_attribute), &sample, None, None)
+        .unwrap();
+    assert_eq!(advice.len(), 1);
+    assert!(advice[0].explanation.is_none());
+
+    // The trace of the firing rule is attached to the advice
+    let mut rego_advisor = RegoAdvisor::new(&live_checker, &policy_dirs, &jq_preprocessor)
+        .expect("Failed to create Rego advisor")
+        .with_explain(true)
+        .with_policy_usage(true);
+    let advice = rego_advisor
+        .advise(SampleRef::Attribute(&sample_attribute), &sample, None, None)
+        .unwrap();
+    assert_eq!(advice.len(), 1);
+    let trace = advice[0].explanation.as_ref().expect("explanation");
+    assert!(!trace.is_empty());
+    assert!(trace.iter().any(|line| line.contains("org.rego")
+        && line.contains("contains(input.sample.attribute.name, \"org\")")));
+
+    // The usage tracking is left untouched by the explanation
+    let unused = rego_advisor.unused_policies().unwrap();
+    assert_eq!(unused.len(), 1);
+    assert!(unused.iter().all(|path| path.ends_with("team.rego")));
+}
+
+#[test]
+fn test_rego_allowed_advice_types() {
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    let policy_dirs = ["data/policies/multi/org/".into()];
+    let jq_preprocessor = Some("data/jq/test.jq".into());
+    let sample_attribute = SampleAttribute::try_from("org.name=hello").unwrap();
+    let sample = Sample::Attribute(sample_attribute.clone());
+
+    // A permitted advice type
+    let mut rego_advisor = RegoAdvisor::new(&live_checker, &policy_dirs, &jq_preprocessor)
+        .expect("Failed to create Rego advisor")
+        .with_allowed_advice_types(["contains_org".to_owned()]);
+    let advice = rego_advisor
+        .advise(SampleRef::Attribute(&sample_attribute), &sample, None, None)
+        .unwrap();
+    assert_eq!(advice.len(), 1);
+
+    // A forbidden advice type
+    let mut rego_advisor = RegoAdvisor::new(&live_checker, &policy_dirs, &jq_preprocessor)
+        .expect("Failed to create Rego advisor")
+        .with_allowed_advice_types(["missing_attribute".to_owned()]);
+    let result = rego_advisor.advise(SampleRef::Attribute(&sample_attribute), &sample, None, None);
+    assert_eq!(
+        result,
+        Err(Error::UnknownAdviceType {
+            advice_type: "contains_org".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn test_conflicting_rego_policy_dirs() {
+    let live_checker = LiveChecker::new(make_registry(), vec![]);
+    // Both directories declare `make_advice` in the `live_check_advice` package
+    let result = RegoAdvisor::new(
+        &live_checker,
+        &[
+            "data/policies/live_check_advice/".into(),
+            "data/policies/multi/org/".into(),
+        ],
+        &Some("data/jq/test.jq".into()),
+    );
+    let Err(err) = result else {
+        panic!("Expected a conflict error");
+    };
+    let message = err.to_string();
+    assert!(message.contains("Conflicting Rego policies"));
+    assert!(message.contains("make_advice"));
+}
+
+#[test]
+fn test_gauge_exemplar_rego() {
+    let registry = make_metrics_registry();
+
+    // A gauge sample with an exemplar
+    let mut sample = Sample::Metric(SampleMetric {
+        name: "system.uptime".to_owned(),
+        instrument: SampleInstrument::Supported(InstrumentSpec::Gauge),
+        unit: "s".to_owned(),
+        temporality: None,
+        data_points: Some(DataPoints::Number(vec![SampleNumberDataPoint {
+            attributes: vec![],
+            value: json!(0.0),
+            flags: 0,
+            live_check_result: None,
+            exemplars: vec![SampleExemplar {
+                timestamp: "".to_owned(),
+                value: json!(0.0),
+                filtered_attributes: vec![],
+                span_id: "".to_owned(),
+                trace_id: "".to_owned(),
+                live_check_result: None,
+            }],
+        }])),
+        live_check_result: None,
+        source: None,
+    });
+    let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
+    let mut live_checker = LiveChecker::new(registry, advisors);
+
+    let rego_advisor = RegoAdvisor::new(
+        &live_checker,
+        &["data/policies/live_check_advice/".into()],
+        &Some("data/jq/test.jq".into()),
+    )
+    .expect("Failed to create Rego advisor");
+    live_checker.add_advisor(Box::new(rego_advisor));
+
+    let stats = check_samples_with_stats(&mut live_checker, std::slice::from_mut(&mut sample));
+    assert_eq!(stats.advice_type_counts.get("low_value"), Some(&1));
+}
@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The message templates of the advice. The English templates of the builtin advice
+//! are the defaults, the messages of the builtin advisors are rendered from them.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use weaver_checker::violation::{Advice, AdviceLevel};
+
+use crate::advice_type::BuiltinAdviceType;
+
+/// Message templates by advice type, replacing the English messages of the advice,
+/// whichever advisor gave it. Advice types without a template keep their message.
+///
+/// A template refers to the advice context fields by name in braces. For example:
+///
+/// ```yaml
+/// type_mismatch: "Le type de '{attribute_name}' devrait être '{expected}'."
+/// ```
+///
+/// See [`render`] for how the fields are inserted. The English template of a builtin
+/// advice type is returned by [`default_template`]. Some advice types are given in
+/// several forms, e.g. on attributes and on metrics, each with its own English
+/// template keyed `<advice type>.<form>`; a template set for the advice type replaces
+/// all of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MessageCatalog {
+    templates: HashMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// Create a new MessageCatalog with the English templates
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the message template of an advice type
+    #[must_use]
+    pub fn with(mut self, advice_type: &str, template: &str) -> Self {
+        let _ = self
+            .templates
+            .insert(advice_type.to_owned(), template.to_owned());
+        self
+    }
+
+    /// Returns the template of an advice type: the template set for it, else its
+    /// English template
+    #[must_use]
+    pub fn template(&self, advice_type: &str) -> Option<&str> {
+        self.templates
+            .get(advice_type)
+            .map(String::as_str)
+            .or_else(|| default_template(advice_type))
+    }
+
+    /// Returns the message of the advice formatted with the template set for its
+    /// advice type, if any
+    #[must_use]
+    pub fn format(&self, advice: &Advice) -> Option<String> {
+        let template = self.templates.get(advice.advice_type.as_str())?;
+        Some(render(template, &advice.advice_context))
+    }
+}
+
+/// Render a template with the fields of an advice context, in a single pass: the
+/// inserted values are not searched for placeholders.
+///
+/// String fields are inserted as is, array fields as their items separated by `, `,
+/// other fields as JSON. Placeholders not in the context are left untouched.
+#[must_use]
+pub fn render(template: &str, advice_context: &Value) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let field = after
+            .find('}')
+            .map(|end| &after[..end])
+            .and_then(|key| advice_context.get(key).map(|value| (key, value)));
+        if let Some((key, value)) = field {
+            message.push_str(&value_text(value));
+            rest = &after[key.len() + 1..];
+        } else {
+            message.push('{');
+            rest = after;
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(items) => items.iter().map(value_text).collect::<Vec<_>>().join(", "),
+        value => value.to_string(),
+    }
+}
+
+/// Create a builtin advice, its message rendered from the English template of its
+/// advice type
+#[must_use]
+pub fn builtin_advice(
+    advice_type: BuiltinAdviceType,
+    advice_context: Value,
+    advice_level: AdviceLevel,
+) -> Advice {
+    let template = default_template(advice_type.as_str()).unwrap_or_default();
+    let message = render(template, &advice_context);
+    Advice::new(advice_type, advice_context, message, advice_level)
+}
+
+/// Create a builtin advice, its message rendered from the English template of a form
+/// of its advice type, e.g. the `metric` form of `deprecated`
+#[must_use]
+pub fn builtin_advice_form(
+    advice_type: BuiltinAdviceType,
+    form: &str,
+    advice_context: Value,
+    advice_level: AdviceLevel,
+) -> Advice {
+    let template =
+        default_template(&format!("{}.{form}", advice_type.as_str())).unwrap_or_default();
+    let message = render(template, &advice_context);
+    Advice::new(advice_type, advice_context, message, advice_level)
+}
+
+/// Returns the English template of a builtin advice type, or of one of its forms
+/// keyed `<advice type>.<form>`
+#[must_use]
+pub fn default_template(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "missing_attribute" => "Attribute '{attribute_name}' does not exist in the registry.",
+        "template_attribute" => "Attribute '{attribute_name}' is a template",
+        "template_should_aggregate" => "Attribute '{attribute_name}' is present {count} times as a scalar value. Template '{template_name}' expects a single array value.",
+        "missing_metric" => "Metric does not exist in the registry.",
+        "missing_identifying_dimension" => "Identifying attribute '{attribute_name}' is not present.",
+        "deprecated" => "Attribute '{attribute_name}' is deprecated; reason = '{deprecation_reason}', note = '{deprecation_note}'.",
+        "deprecated.metric" => "Metric is deprecated; reason = {deprecation_reason}, note = {deprecation_note}",
+        "type_mismatch" => "Attribute '{attribute_name}' has type '{attribute_type}'. Type should be '{expected}'.",
+        "type_mismatch.enum" => "Enum attribute '{attribute_name}' has type '{attribute_type}'. Enum value type should be 'string' or 'int'.",
+        "not_stable" => "Attribute '{attribute_name}' is not stable; stability = {stability}.",
+        "not_stable.metric" => "Metric is not stable; stability = {stability}.",
+        "unit_mismatch" => "Unit should be '{expected}', but found '{unit}'.",
+        "unexpected_instrument" => "Instrument should be '{expected}', but found '{instrument}'.",
+        "unexpected_instrument.unsupported" => "Instrument '{instrument}' is not supported",
+        "counter_negative_value" => "Counter '{metric_name}' has a negative value {value}. Counters must be monotonic.",
+        "temporality_mismatch" => "Metric '{metric_name}' has temporality '{temporality}'. Temporality should be '{expected}'.",
+        "undefined_enum_variant" => "Enum attribute '{attribute_name}' has value '{attribute_value}' which is not documented.",
+        "custom_enum_value" => "Open enum attribute '{attribute_name}' has custom value '{attribute_value}'.",
+        "enum_value_case_mismatch" => "Enum attribute '{attribute_name}' has value '{attribute_value}' which only matches '{expected}' when ignoring case.",
+        "attribute_type_changed" => "Attribute '{attribute_name}' has type '{attribute_type}' which was valid in the baseline registry. Type has changed from '{baseline_type}' to '{expected}'.",
+        "valid_in_newer_registry" => "Attribute '{attribute_name}' would be valid in the newer registry.",
+        "valid_in_newer_registry.metric" => "Metric would be valid in the newer registry.",
+        "scope_attribute_should_be_resource" => "Scope attribute '{attribute_name}' has the same value in all {count} instrumentation scopes, it may belong on the resource.",
+        "cross_field_constraint_violation" => "Attribute '{attribute_name}' ({attribute_value}) and attribute '{other_attribute_name}' ({other_attribute_value}) violate the constraint '{constraint}'.",
+        "constant_attribute" => "Attribute '{attribute_name}' has the same value '{attribute_value}' in all {count} samples, it may be hardcoded.",
+        "inconsistent_service_resource" => "Resource attribute '{attribute_name}' has the distinct values {values} for service '{service_name}', the resources may be mislabeled.",
+        "value_out_of_range" => "Attribute '{attribute_name}' has value {attribute_value} which is out of the expected range; min = {min}, max = {max}.",
+        "value_format_mismatch" => "Attribute '{attribute_name}' has value '{attribute_value}' which does not match the expected pattern '{pattern}'.",
+        "attribute_duplicates_span_name" => "Attribute '{attribute_name}' duplicates the span name '{attribute_value}'.",
+        "duplicate_attribute" => "Attribute '{attribute_name}' is present {count} times.",
+        "attribute_name_too_long" => "Attribute name '{attribute_name}' is {length} characters long, more than the maximum of {max}.",
+        "possible_secret_value" => "Attribute '{attribute_name}' has a value that looks like a secret ({reason}).",
+        "experimental_attribute_not_opted_in" => "Experimental attribute '{attribute_name}' is used without being opted in to.",
+        "conditionally_required_condition_met" => "Conditionally required attribute '{attribute_name}' is not present while its condition `{condition}` is met.",
+        "attribute_not_in_registry" => "Attribute '{attribute_name}' is not defined in any registry group.",
+        "stability_regression" => "Attribute '{attribute_name}' is {stability} but was {baseline_stability} in the baseline registry.",
+        "invalid_metric_name" => "Metric name '{metric_name}' does not follow the naming rules; reason = {reason}.",
+        "value_unlike_examples" => "Attribute '{attribute_name}' has a {shape} value unlike its examples ({example_shapes}).",
+        "possible_misspelling" => "Attribute '{attribute_name}' is not defined in the registry, did you mean '{suggestion}'?",
+        "attribute_should_be_resource" => "Attribute '{attribute_name}' is declared on the entity '{entity}', it should be set on the resource.",
+        "unit_instrument_inconsistent" => "Metric '{metric_name}' with unit '{unit}' is expected to use the unit '{expected}'.",
+        "sample_type_value_mismatch" => "Attribute '{attribute_name}' is declared as '{attribute_type}' but has the value {attribute_value}.",
+        "required_attribute_group_missing" => "None of the attributes {attribute_names} is present, at least one is required.",
+        "value_not_in_allowed_set" => "Attribute '{attribute_name}' has value '{attribute_value}' which is not one of the allowed values ({values}).",
+        "redundant_resource_attribute" => "Attribute '{attribute_name}' duplicates the resource attribute value in all {count} signals, it is redundant with the resource.",
+        "unsupported_value_kind" => "Attribute '{attribute_name}' has a {attribute_type} value, which semantic conventions don't model.",
+        "enum_value_type_mismatch" => "Enum attribute '{attribute_name}' has type '{attribute_type}' but its members are of type '{expected}'.",
+        "duplicate_time_series" => "Metric '{metric_name}' has {count} data points with the identical attribute set {attributes}, they are duplicate time series.",
+        "no_registry_loaded" => "The registry has no groups, the samples were not checked against any semantic convention.",
+        "missing_scope_name" => "Instrumentation scope has no name.",
+        "invalid_scope_version" => "Instrumentation scope '{scope_name}' has version '{scope_version}' which is not a semantic version.",
+        "histogram_min_max_inverted" => "Histogram data point has min {min} greater than max {max}.",
+        "histogram_sum_out_of_bounds" => "Histogram data point has sum {sum} out of the bounds of {count} values between {min} and {max}.",
+        "attribute_type_any" => "Attribute '{attribute_name}' is typed 'any' in the registry, consider a concrete type.",
+        "invalid_trace_id" => "Trace id '{trace_id}' is invalid, it must be 32 hex characters and not all zeros.",
+        "invalid_span_id" => "Span id '{span_id}' is invalid, it must be 16 hex characters and not all zeros.",
+        "requirement_level_mismatch_suspected" => "Attribute '{attribute_name}' is {requirement_level} in group '{group_id}' but has a presence of {presence} over {count} samples, it may be effectively always on.",
+        "baseline_advice_resolved" => "Baseline advice '{advice_type}' is no longer given: {message}",
+        "datapoint_value_type_mismatch" => "Data point value {value} is of type '{attribute_type}'. Type should be '{expected}'.",
+        "name_normalized_to_match" => "Attribute '{attribute_name}' only matches the registry once normalized to '{normalized_name}'.",
+        "too_many_attributes" => "Data point has {count} attributes, more than the maximum of {max}.",
+        "inconsistent_attribute_type" => "Attribute '{attribute_name}' is seen with the types {types} across the samples.",
+        "placeholder_value" => "Attribute '{attribute_name}' has the placeholder value '{attribute_value}'.",
+        "numeric_coercion_applied" => "Attribute '{attribute_name}' has type '{attribute_type}' with no fractional part, accepted as '{expected}'.",
+        "rate_unit_on_cumulative_instrument" => "Metric '{metric_name}' is a {instrument} with the per-second rate unit '{unit}'. Record raw counts with a counter, or the rate with a gauge.",
+        "delta_cumulative_drift" => "Metric '{metric_name}' deltas sum to {sum} but its cumulative value is {value} for the attribute set {attributes}, a drift above the tolerance.",
+        "invalid_live_check_annotation" => "The live_check annotation of group '{group_id}' is malformed and ignored: {reason}.",
+        "invalid_live_check_annotation.attribute" => "The live_check annotation of attribute '{attribute_name}' is malformed and ignored: {reason}.",
+        "required_attribute_not_present" => "Required attribute '{attribute_name}' is not present.",
+        "recommended_attribute_not_present" => "Recommended attribute '{attribute_name}' is not present.",
+        "opt_in_attribute_not_present" => "Opt-in attribute '{attribute_name}' is not present.",
+        "conditionally_required_attribute_not_present" => "Conditionally required attribute '{attribute_name}' is not present.",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let context = json!({
+            "attribute_name": "{expected}",
+            "expected": "string",
+            "values": ["a", 1, ["b"]],
+            "count": 2,
+        });
+        // Inserted values are not expanded again, unknown placeholders are kept
+        assert_eq!(
+            render(
+                "'{attribute_name}' should be '{expected}' {unknown} {",
+                &context
+            ),
+            "'{expected}' should be 'string' {unknown} {"
+        );
+        assert_eq!(render("{values} ({count})", &context), "a, 1, b (2)");
+        assert_eq!(render("{{count}}", &context), "{2}");
+        assert_eq!(render("{count}", &Value::Null), "{count}");
+    }
+
+    #[test]
+    fn test_template() {
+        let catalog = MessageCatalog::new().with("type_mismatch", "Mauvais type.");
+        assert_eq!(catalog.template("type_mismatch"), Some("Mauvais type."));
+        assert_eq!(
+            catalog.template("missing_metric"),
+            Some("Metric does not exist in the registry.")
+        );
+        assert_eq!(catalog.template("custom_advice"), None);
+
+        let advice = builtin_advice_form(
+            BuiltinAdviceType::NotStable,
+            "metric",
+            json!({ "stability": "development" }),
+            AdviceLevel::Improvement,
+        );
+        assert_eq!(
+            advice.message,
+            "Metric is not stable; stability = development."
+        );
+        assert_eq!(catalog.format(&advice), None);
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Nested OTLP documents, where resources contain instrumentation scopes which
+//! contain the signals
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{sample_resource::SampleResource, sample_scope::SampleScope, Sample};
+
+/// Represents an OTLP document, e.g. the content of an export request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OtlpDocument {
+    /// The resources of the document
+    #[serde(default)]
+    pub resources: Vec<OtlpResource>,
+}
+
+/// Represents a resource and the instrumentation scopes of its signals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OtlpResource {
+    /// The resource
+    pub resource: SampleResource,
+    /// The instrumentation scopes of the resource
+    #[serde(default)]
+    pub scopes: Vec<OtlpScope>,
+}
+
+/// Represents an instrumentation scope and its signals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OtlpScope {
+    /// The instrumentation scope
+    pub scope: SampleScope,
+    /// The signals of the instrumentation scope, e.g. metrics or spans
+    #[serde(default)]
+    pub signals: Vec<Sample>,
+}
+
+/// The resource and instrumentation scope of the samples being checked, as given
+/// to the advisors by [`crate::advice::Advisor::enter_context`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OtlpContext {
+    /// The resource of the samples
+    pub resource: Option<SampleResource>,
+    /// The instrumentation scope of the samples
+    pub scope: Option<SampleScope>,
+}
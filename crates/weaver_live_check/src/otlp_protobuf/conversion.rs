@@ -0,0 +1,478 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion routines for OTLP to Sample
+
+use crate::{
+    sample_attribute::{SampleAttribute, UnsupportedValueKind},
+    sample_metric::{DataPoints, SampleInstrument, SampleMetric, SampleTemporality},
+    sample_resource::SampleResource,
+    sample_scope::SampleScope,
+    sample_span::{SampleSpan, SampleSpanEvent, SampleSpanLink, Status, StatusCode},
+    Sample,
+};
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
+
+use super::proto::trace::v1::status::StatusCode as OtlpStatusCode;
+use super::proto::{
+    collector::{metrics::v1::ExportMetricsServiceRequest, trace::v1::ExportTraceServiceRequest},
+    common::v1::{AnyValue, InstrumentationScope, KeyValue},
+    metrics::v1::{
+        metric::Data, AggregationTemporality, HistogramDataPoint, Metric, NumberDataPoint,
+    },
+    resource::v1::Resource,
+    trace::v1::{span::SpanKind, Span},
+};
+
+fn maybe_to_json(value: Option<AnyValue>) -> Option<Value> {
+    if let Some(value) = value {
+        if let Some(value) = value.value {
+            use super::proto::common::v1::any_value::Value as GrpcValue;
+            match value {
+                GrpcValue::StringValue(string) => Some(Value::String(string)),
+                GrpcValue::IntValue(int_value) => Some(Value::Number(int_value.into())),
+                GrpcValue::DoubleValue(double_value) => Some(json!(double_value)),
+                GrpcValue::BoolValue(bool_value) => Some(Value::Bool(bool_value)),
+                GrpcValue::ArrayValue(array_value) => {
+                    let mut vec = Vec::new();
+                    for value in array_value.values {
+                        if let Some(value) = maybe_to_json(Some(value)) {
+                            vec.push(value);
+                        }
+                    }
+                    Some(Value::Array(vec))
+                }
+                GrpcValue::KvlistValue(kvlist_value) => {
+                    let mut map = serde_json::Map::new();
+                    for key_value in kvlist_value.values {
+                        let value = maybe_to_json(key_value.value).unwrap_or(Value::Null);
+                        let _ = map.insert(key_value.key, value);
+                    }
+                    Some(Value::Object(map))
+                }
+                GrpcValue::BytesValue(bytes_value) => Some(Value::String(
+                    bytes_value
+                        .iter()
+                        .map(|byte| format!("{byte:02x}"))
+                        .collect(),
+                )),
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Converts an OTLP KeyValue to a SampleAttribute
+fn sample_attribute_from_key_value(key_value: &KeyValue) -> SampleAttribute {
+    use super::proto::common::v1::any_value::Value as GrpcValue;
+    let value = maybe_to_json(key_value.value.clone());
+    let value_kind = key_value
+        .value
+        .as_ref()
+        .and_then(|value| value.value.as_ref())
+        .and_then(|value| match value {
+            GrpcValue::KvlistValue(_) => Some(UnsupportedValueKind::Kvlist),
+            GrpcValue::BytesValue(_) => Some(UnsupportedValueKind::Bytes),
+            _ => None,
+        });
+    // Kvlist and bytes values don't map to a semantic convention type
+    let r#type = match (&value, value_kind) {
+        (Some(val), None) => SampleAttribute::infer_type(val),
+        _ => None,
+    };
+    SampleAttribute {
+        name: key_value.key.clone(),
+        value,
+        r#type,
+        value_kind,
+        live_check_result: None,
+        source: None,
+    }
+}
+
+/// Converts an OTLP InstrumentationScope to a SampleScope
+fn sample_scope_from_otlp(scope: InstrumentationScope) -> SampleScope {
+    SampleScope {
+        name: scope.name,
+        version: (!scope.version.is_empty()).then_some(scope.version),
+        attributes: scope
+            .attributes
+            .iter()
+            .map(sample_attribute_from_key_value)
+            .collect(),
+        live_check_result: None,
+        source: None,
+    }
+}
+
+/// Converts an OTLP span kind to a SpanKindSpec
+fn span_kind_from_otlp_kind(kind: SpanKind) -> SpanKindSpec {
+    match kind {
+        SpanKind::Server => SpanKindSpec::Server,
+        SpanKind::Client => SpanKindSpec::Client,
+        SpanKind::Producer => SpanKindSpec::Producer,
+        SpanKind::Consumer => SpanKindSpec::Consumer,
+        _ => SpanKindSpec::Internal,
+    }
+}
+
+/// Converts an OTLP status to a Status
+fn status_from_otlp_status(status: Option<super::proto::trace::v1::Status>) -> Option<Status> {
+    if let Some(status) = status {
+        let code = match status.code() {
+            OtlpStatusCode::Unset => StatusCode::Unset,
+            OtlpStatusCode::Ok => StatusCode::Ok,
+            OtlpStatusCode::Error => StatusCode::Error,
+        };
+        return Some(Status {
+            code,
+            message: status.message,
+        });
+    }
+    None
+}
+
+/// Converts an OTLP resource to a SampleResource
+fn sample_resource_from_otlp(resource: Resource) -> SampleResource {
+    SampleResource {
+        attributes: resource
+            .attributes
+            .iter()
+            .map(sample_attribute_from_key_value)
+            .collect(),
+        live_check_result: None,
+        source: None,
+    }
+}
+
+/// Converts an OTLP metrics export request to samples, each resource and scope
+/// being followed by their metrics
+#[must_use]
+pub fn otlp_metrics_request_to_samples(request: ExportMetricsServiceRequest) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    for resource_metric in request.resource_metrics {
+        if let Some(resource) = resource_metric.resource {
+            samples.push(Sample::Resource(sample_resource_from_otlp(resource)));
+        }
+
+        for scope_metric in resource_metric.scope_metrics {
+            if let Some(scope) = scope_metric.scope {
+                samples.push(Sample::Scope(sample_scope_from_otlp(scope)));
+            }
+
+            for metric in scope_metric.metrics {
+                samples.push(Sample::Metric(otlp_metric_to_sample(metric)));
+            }
+        }
+    }
+    samples
+}
+
+/// Converts an OTLP trace export request to samples, each resource and scope
+/// being followed by their spans
+#[must_use]
+pub fn otlp_trace_request_to_samples(request: ExportTraceServiceRequest) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    for resource_span in request.resource_spans {
+        if let Some(resource) = resource_span.resource {
+            samples.push(Sample::Resource(sample_resource_from_otlp(resource)));
+        }
+
+        for scope_span in resource_span.scope_spans {
+            if let Some(scope) = scope_span.scope {
+                samples.push(Sample::Scope(sample_scope_from_otlp(scope)));
+            }
+
+            for span in scope_span.spans {
+                samples.push(Sample::Span(otlp_span_to_sample(span)));
+            }
+        }
+    }
+    samples
+}
+
+/// Converts an OTLP span, with its events and links, to a SampleSpan
+fn otlp_span_to_sample(span: Span) -> SampleSpan {
+    let span_kind = span.kind();
+    SampleSpan {
+        name: span.name,
+        kind: span_kind_from_otlp_kind(span_kind),
+        status: status_from_otlp_status(span.status),
+        trace_id: Some(id_hex(&span.trace_id)),
+        span_id: Some(id_hex(&span.span_id)),
+        attributes: span
+            .attributes
+            .iter()
+            .map(sample_attribute_from_key_value)
+            .collect(),
+        span_events: span
+            .events
+            .into_iter()
+            .map(|event| SampleSpanEvent {
+                name: event.name,
+                timestamp: Some(unix_nanos_to_utc(event.time_unix_nano)),
+                attributes: event
+                    .attributes
+                    .iter()
+                    .map(sample_attribute_from_key_value)
+                    .collect(),
+                live_check_result: None,
+                source: None,
+            })
+            .collect(),
+        span_links: span
+            .links
+            .into_iter()
+            .map(|link| SampleSpanLink {
+                trace_id: Some(id_hex(&link.trace_id)),
+                span_id: Some(id_hex(&link.span_id)),
+                attributes: link
+                    .attributes
+                    .iter()
+                    .map(sample_attribute_from_key_value)
+                    .collect(),
+                live_check_result: None,
+                source: None,
+            })
+            .collect(),
+        live_check_result: None,
+        source: None,
+    }
+}
+
+/// Converts an OTLP metric to a SampleMetric
+fn otlp_metric_to_sample(otlp_metric: Metric) -> SampleMetric {
+    SampleMetric {
+        name: otlp_metric.name,
+        instrument: otlp_data_to_instrument(&otlp_metric.data),
+        unit: otlp_metric.unit,
+        temporality: otlp_data_to_temporality(&otlp_metric.data),
+        data_points: otlp_data_to_data_points(&otlp_metric.data),
+        live_check_result: None,
+        source: None,
+    }
+}
+
+/// Converts OTLP data to a SampleMetric instrument
+/// Mapping:
+/// counter → Sum with is_monotonic: true
+/// updowncounter → Sum with is_monotonic: false
+/// gauge → Gauge
+/// histogram → Histogram
+/// histogram → ExponentialHistogram
+/// summary → Summary (this will cause a legacy_instrument violation)
+fn otlp_data_to_instrument(data: &Option<Data>) -> SampleInstrument {
+    match data {
+        Some(Data::Sum(sum)) => {
+            if sum.is_monotonic {
+                SampleInstrument::Supported(InstrumentSpec::Counter)
+            } else {
+                SampleInstrument::Supported(InstrumentSpec::UpDownCounter)
+            }
+        }
+        Some(Data::Gauge(_)) => SampleInstrument::Supported(InstrumentSpec::Gauge),
+        Some(Data::Histogram(_)) => SampleInstrument::Supported(InstrumentSpec::Histogram),
+        Some(Data::ExponentialHistogram(_)) => {
+            SampleInstrument::Supported(InstrumentSpec::Histogram)
+        }
+        Some(Data::Summary(_)) => SampleInstrument::Unsupported("Summary".to_owned()),
+        None => SampleInstrument::Unsupported("Unspecified".to_owned()),
+    }
+}
+
+/// Converts the OTLP aggregation temporality of sums and histograms
+fn otlp_data_to_temporality(data: &Option<Data>) -> Option<SampleTemporality> {
+    let aggregation_temporality = match data {
+        Some(Data::Sum(sum)) => sum.aggregation_temporality,
+        Some(Data::Histogram(histogram)) => histogram.aggregation_temporality,
+        Some(Data::ExponentialHistogram(exponential_histogram)) => {
+            exponential_histogram.aggregation_temporality
+        }
+        _ => return None,
+    };
+    match AggregationTemporality::try_from(aggregation_temporality) {
+        Ok(AggregationTemporality::Delta) => Some(SampleTemporality::Delta),
+        Ok(AggregationTemporality::Cumulative) => Some(SampleTemporality::Cumulative),
+        _ => None,
+    }
+}
+
+/// Converts OTLP data to SampleMetric data points
+fn otlp_data_to_data_points(data: &Option<Data>) -> Option<DataPoints> {
+    match data {
+        Some(Data::Sum(sum)) => Some(otlp_number_data_points(&sum.data_points)),
+        Some(Data::Gauge(gauge)) => Some(otlp_number_data_points(&gauge.data_points)),
+        Some(Data::Histogram(histogram)) => {
+            Some(otlp_histogram_data_points(&histogram.data_points))
+        }
+        Some(Data::ExponentialHistogram(exponential_histogram)) => Some(
+            otlp_exponential_histogram_data_points(&exponential_histogram.data_points),
+        ),
+        _ => None,
+    }
+}
+
+/// Converts an OTLP Exemplar to a SampleExemplar
+fn otlp_exemplar_to_sample_exemplar(
+    exemplar: &super::proto::metrics::v1::Exemplar,
+) -> crate::sample_metric::SampleExemplar {
+    crate::sample_metric::SampleExemplar {
+        filtered_attributes: exemplar
+            .filtered_attributes
+            .iter()
+            .map(sample_attribute_from_key_value)
+            .collect(),
+        value: match &exemplar.value {
+            Some(value) => match value {
+                super::proto::metrics::v1::exemplar::Value::AsDouble(double) => {
+                    json!(double)
+                }
+                super::proto::metrics::v1::exemplar::Value::AsInt(int) => {
+                    Value::Number((*int).into())
+                }
+            },
+            None => Value::Null,
+        },
+        timestamp: unix_nanos_to_utc(exemplar.time_unix_nano),
+        span_id: id_hex(&exemplar.span_id),
+        trace_id: id_hex(&exemplar.trace_id),
+        live_check_result: None,
+    }
+}
+
+/// Converts a Unix timestamp in nanoseconds to a UTC string
+fn unix_nanos_to_utc(time_unix_nano: u64) -> String {
+    if let Ok(nanos) = time_unix_nano.try_into() {
+        Utc.timestamp_nanos(nanos).to_rfc3339()
+    } else {
+        "".to_owned()
+    }
+}
+
+/// Converts a trace ID (16 bytes) or a span ID (8 bytes) to a hex string.
+///
+/// IDs of the wrong length are converted as is, for the live check to report them.
+fn id_hex(id: &[u8]) -> String {
+    id.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Converts OTLP ExponentialHistogram data points to DataPoints::ExponentialHistogram
+fn otlp_exponential_histogram_data_points(
+    otlp: &Vec<super::proto::metrics::v1::ExponentialHistogramDataPoint>,
+) -> DataPoints {
+    let mut data_points = Vec::new();
+    for point in otlp {
+        let positive = point.positive.as_ref().map(|buckets| {
+            crate::sample_metric::SampleExponentialHistogramBuckets {
+                offset: buckets.offset,
+                bucket_counts: buckets.bucket_counts.clone(),
+            }
+        });
+
+        let negative = point.negative.as_ref().map(|buckets| {
+            crate::sample_metric::SampleExponentialHistogramBuckets {
+                offset: buckets.offset,
+                bucket_counts: buckets.bucket_counts.clone(),
+            }
+        });
+
+        let exemplars = point
+            .exemplars
+            .iter()
+            .map(otlp_exemplar_to_sample_exemplar)
+            .collect();
+
+        let live_check_point = crate::sample_metric::SampleExponentialHistogramDataPoint {
+            attributes: point
+                .attributes
+                .iter()
+                .map(sample_attribute_from_key_value)
+                .collect(),
+            count: point.count,
+            sum: point.sum,
+            scale: point.scale,
+            zero_count: point.zero_count,
+            positive,
+            negative,
+            flags: point.flags,
+            min: point.min,
+            max: point.max,
+            zero_threshold: point.zero_threshold,
+            exemplars,
+            live_check_result: None,
+        };
+        data_points.push(live_check_point);
+    }
+    DataPoints::ExponentialHistogram(data_points)
+}
+
+/// Converts OTLP Histogram data points to DataPoints::Histogram
+fn otlp_histogram_data_points(otlp: &Vec<HistogramDataPoint>) -> DataPoints {
+    let mut data_points = Vec::new();
+    for point in otlp {
+        let exemplars = point
+            .exemplars
+            .iter()
+            .map(otlp_exemplar_to_sample_exemplar)
+            .collect();
+
+        let live_check_point = crate::sample_metric::SampleHistogramDataPoint {
+            attributes: point
+                .attributes
+                .iter()
+                .map(sample_attribute_from_key_value)
+                .collect(),
+            count: point.count,
+            sum: point.sum,
+            bucket_counts: point.bucket_counts.clone(),
+            explicit_bounds: point.explicit_bounds.clone(),
+            min: point.min,
+            max: point.max,
+            flags: point.flags,
+            exemplars,
+            live_check_result: None,
+        };
+        data_points.push(live_check_point);
+    }
+    DataPoints::Histogram(data_points)
+}
+
+/// Converts OTLP Number data points to DataPoints::Number
+fn otlp_number_data_points(otlp: &Vec<NumberDataPoint>) -> DataPoints {
+    let mut data_points = Vec::new();
+    for point in otlp {
+        let exemplars = point
+            .exemplars
+            .iter()
+            .map(otlp_exemplar_to_sample_exemplar)
+            .collect();
+
+        let live_check_point = crate::sample_metric::SampleNumberDataPoint {
+            attributes: point
+                .attributes
+                .iter()
+                .map(sample_attribute_from_key_value)
+                .collect(),
+            value: match point.value {
+                Some(value) => match value {
+                    super::proto::metrics::v1::number_data_point::Value::AsDouble(double) => {
+                        json!(double)
+                    }
+                    super::proto::metrics::v1::number_data_point::Value::AsInt(int) => {
+                        Value::Number(int.into())
+                    }
+                },
+                None => Value::Null,
+            },
+            flags: point.flags,
+            exemplars,
+            live_check_result: None,
+        };
+        data_points.push(live_check_point);
+    }
+    DataPoints::Number(data_points)
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoding of protobuf encoded OTLP export requests, as written by collectors
+//! exporting protobuf, into samples.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use prost::Message;
+
+use crate::{Error, Ingester, Sample};
+
+use conversion::{otlp_metrics_request_to_samples, otlp_trace_request_to_samples};
+use proto::collector::{
+    metrics::v1::ExportMetricsServiceRequest, trace::v1::ExportTraceServiceRequest,
+};
+
+pub mod conversion;
+
+/// The OTLP protobuf messages.
+/// See the build.rs file of the `weaver` binary for more information.
+#[allow(missing_docs)]
+#[allow(clippy::must_use_candidate)]
+pub mod proto {
+    #[path = ""]
+    pub mod collector {
+        #[path = ""]
+        pub mod metrics {
+            #[allow(unused_qualifications)]
+            #[allow(rustdoc::invalid_html_tags)]
+            #[path = "opentelemetry.proto.collector.metrics.v1.rs"]
+            pub mod v1;
+        }
+        #[path = ""]
+        pub mod trace {
+            #[allow(unused_qualifications)]
+            #[allow(rustdoc::invalid_html_tags)]
+            #[path = "opentelemetry.proto.collector.trace.v1.rs"]
+            pub mod v1;
+        }
+    }
+
+    #[path = ""]
+    pub mod metrics {
+        #[allow(rustdoc::invalid_html_tags)]
+        #[path = "opentelemetry.proto.metrics.v1.rs"]
+        pub mod v1;
+    }
+
+    #[path = ""]
+    pub mod trace {
+        #[allow(rustdoc::invalid_html_tags)]
+        #[path = "opentelemetry.proto.trace.v1.rs"]
+        pub mod v1;
+    }
+
+    #[path = ""]
+    pub mod common {
+        #[allow(clippy::enum_variant_names)]
+        #[path = "opentelemetry.proto.common.v1.rs"]
+        pub mod v1;
+    }
+
+    #[path = ""]
+    pub mod resource {
+        #[path = "opentelemetry.proto.resource.v1.rs"]
+        pub mod v1;
+    }
+}
+
+/// The signal of a protobuf encoded OTLP export request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpSignal {
+    /// An `ExportMetricsServiceRequest`
+    Metrics,
+    /// An `ExportTraceServiceRequest`
+    Traces,
+}
+
+/// Decodes a protobuf encoded OTLP export request into samples, each resource and
+/// scope being followed by their metrics or spans.
+pub fn from_otlp_protobuf(signal: OtlpSignal, bytes: &[u8]) -> Result<Vec<Sample>, Error> {
+    let decode_error = |e: prost::DecodeError| Error::IngestError {
+        error: format!("Failed to decode the OTLP {signal:?} protobuf request: {e}"),
+    };
+    match signal {
+        OtlpSignal::Metrics => Ok(otlp_metrics_request_to_samples(
+            ExportMetricsServiceRequest::decode(bytes).map_err(decode_error)?,
+        )),
+        OtlpSignal::Traces => Ok(otlp_trace_request_to_samples(
+            ExportTraceServiceRequest::decode(bytes).map_err(decode_error)?,
+        )),
+    }
+}
+
+/// An ingester that reads a protobuf encoded OTLP export request from a file or
+/// from stdin.
+pub struct OtlpProtobufIngester {
+    /// The file to read, stdin when not set
+    path: Option<PathBuf>,
+    signal: OtlpSignal,
+}
+
+impl OtlpProtobufIngester {
+    /// Create a new OtlpProtobufIngester reading from a file
+    #[must_use]
+    pub fn from_file(path: &Path, signal: OtlpSignal) -> Self {
+        OtlpProtobufIngester {
+            path: Some(path.to_path_buf()),
+            signal,
+        }
+    }
+
+    /// Create a new OtlpProtobufIngester reading from stdin
+    #[must_use]
+    pub fn from_stdin(signal: OtlpSignal) -> Self {
+        OtlpProtobufIngester { path: None, signal }
+    }
+}
+
+impl Ingester for OtlpProtobufIngester {
+    fn ingest(&self) -> Result<Box<dyn Iterator<Item = Sample>>, Error> {
+        let bytes = if let Some(path) = &self.path {
+            std::fs::read(path).map_err(|e| Error::IngestError {
+                error: format!("Failed to read file {}: {}", path.display(), e),
+            })?
+        } else {
+            let mut bytes = Vec::new();
+            let _ = std::io::stdin()
+                .read_to_end(&mut bytes)
+                .map_err(|e| Error::IngestError {
+                    error: format!("Failed to read from stdin: {e}"),
+                })?;
+            bytes
+        };
+        let samples = from_otlp_protobuf(self.signal, &bytes)?;
+        Ok(Box::new(samples.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_metric::{DataPoints, SampleInstrument, SampleTemporality};
+    use serde_json::json;
+    use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
+
+    #[test]
+    fn test_otlp_protobuf_metrics() {
+        let samples: Vec<Sample> = OtlpProtobufIngester::from_file(
+            Path::new("data/otlp_metrics.binpb"),
+            OtlpSignal::Metrics,
+        )
+        .ingest()
+        .unwrap()
+        .collect();
+        assert_eq!(samples.len(), 4);
+
+        let Sample::Resource(resource) = &samples[0] else {
+            panic!("Expected a resource sample, got {:?}", samples[0]);
+        };
+        assert_eq!(resource.attributes[0].name, "service.name");
+        assert_eq!(resource.attributes[0].value, Some(json!("checkout")));
+        let Sample::Scope(scope) = &samples[1] else {
+            panic!("Expected a scope sample, got {:?}", samples[1]);
+        };
+        assert_eq!(
+            scope.name,
+            "go.opentelemetry.io/contrib/instrumentation/net/http/otelhttp"
+        );
+
+        let Sample::Metric(counter) = &samples[2] else {
+            panic!("Expected a metric sample, got {:?}", samples[2]);
+        };
+        assert_eq!(counter.name, "http.server.request.count");
+        assert_eq!(counter.unit, "{request}");
+        assert_eq!(
+            counter.instrument,
+            SampleInstrument::Supported(InstrumentSpec::Counter)
+        );
+        assert_eq!(counter.temporality, Some(SampleTemporality::Cumulative));
+        let Some(DataPoints::Number(data_points)) = &counter.data_points else {
+            panic!("Expected number data points, got {:?}", counter.data_points);
+        };
+        assert_eq!(data_points.len(), 1);
+        assert_eq!(data_points[0].value, 42);
+        assert_eq!(data_points[0].attributes[0].name, "http.request.method");
+        assert_eq!(data_points[0].attributes[1].value, Some(json!(200)));
+
+        let Sample::Metric(histogram) = &samples[3] else {
+            panic!("Expected a metric sample, got {:?}", samples[3]);
+        };
+        assert_eq!(histogram.name, "http.server.request.duration");
+        let Some(DataPoints::Histogram(data_points)) = &histogram.data_points else {
+            panic!(
+                "Expected histogram data points, got {:?}",
+                histogram.data_points
+            );
+        };
+        assert_eq!(data_points[0].count, 42);
+        assert_eq!(data_points[0].bucket_counts, [30, 10, 2, 0]);
+    }
+
+    #[test]
+    fn test_otlp_protobuf_traces() {
+        let samples: Vec<Sample> = OtlpProtobufIngester::from_file(
+            Path::new("data/otlp_traces.binpb"),
+            OtlpSignal::Traces,
+        )
+        .ingest()
+        .unwrap()
+        .collect();
+        assert_eq!(samples.len(), 3);
+
+        let Sample::Span(span) = &samples[2] else {
+            panic!("Expected a span sample, got {:?}", samples[2]);
+        };
+        assert_eq!(span.name, "GET /cart");
+        assert_eq!(span.kind, SpanKindSpec::Server);
+        assert_eq!(
+            span.trace_id.as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+        assert_eq!(span.span_id.as_deref(), Some("00f067aa0ba902b7"));
+        assert_eq!(span.attributes[1].name, "http.route");
+    }
+
+    #[test]
+    fn test_otlp_protobuf_malformed() {
+        // Truncated and malformed payloads are reported
+        let bytes = std::fs::read("data/otlp_metrics.binpb").unwrap();
+        assert!(from_otlp_protobuf(OtlpSignal::Metrics, &bytes[..bytes.len() - 1]).is_err());
+        assert!(from_otlp_protobuf(OtlpSignal::Metrics, &[0xff, 0xff]).is_err());
+    }
+}
@@ -2,21 +2,45 @@
 
 //! Intermediary format for telemetry sample attributes
 
-use std::rc::Rc;
+use std::{
+    fmt::{Display, Formatter},
+    rc::Rc,
+};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use weaver_checker::violation::{Advice, AdviceLevel};
+use weaver_checker::violation::{Advice, AdviceLevel, SampleSource};
 use weaver_forge::registry::ResolvedGroup;
 use weaver_semconv::attribute::{AttributeType, PrimitiveOrArrayTypeSpec};
 
 use crate::{
-    live_checker::LiveChecker, Error, LiveCheckResult, LiveCheckRunner, LiveCheckStatistics,
-    Sample, SampleRef, ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY, MISSING_ATTRIBUTE_ADVICE_TYPE,
-    TEMPLATE_ATTRIBUTE_ADVICE_TYPE,
+    advice_type::BuiltinAdviceType, live_checker::LiveChecker, message_catalog::builtin_advice,
+    Error, LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
+    ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY, BASELINE_STABILITY_ADVICE_CONTEXT_KEY,
+    NORMALIZED_NAME_ADVICE_CONTEXT_KEY, STABILITY_ADVICE_CONTEXT_KEY,
+    TEMPLATE_NAME_ADVICE_CONTEXT_KEY,
 };
 
+/// The kind of an attribute value not modeled by semantic conventions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsupportedValueKind {
+    /// A list of key/values, the OTLP `kvlist_value`, held as a JSON object
+    Kvlist,
+    /// A byte array, the OTLP `bytes_value`, held as a hex string
+    Bytes,
+}
+
+impl Display for UnsupportedValueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnsupportedValueKind::Kvlist => f.write_str("kvlist"),
+            UnsupportedValueKind::Bytes => f.write_str("bytes"),
+        }
+    }
+}
+
 /// Represents a sample telemetry attribute parsed from any source
 #[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
 pub struct SampleAttribute {
@@ -28,8 +52,15 @@ pub struct SampleAttribute {
     /// This may be available in the upstream source, an o11y vendor for example
     /// or inferred from the value
     pub r#type: Option<PrimitiveOrArrayTypeSpec>,
+    /// The kind of the value when it is not modeled by semantic conventions and
+    /// can't be told from the JSON value, e.g. bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_kind: Option<UnsupportedValueKind>,
     /// Live check result
     pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
 }
 
 impl<'de> Deserialize<'de> for SampleAttribute {
@@ -42,6 +73,10 @@ impl<'de> Deserialize<'de> for SampleAttribute {
             name: String,
             value: Option<Value>,
             r#type: Option<PrimitiveOrArrayTypeSpec>,
+            #[serde(default)]
+            value_kind: Option<UnsupportedValueKind>,
+            #[serde(default)]
+            source: Option<SampleSource>,
         }
 
         let helper = SampleAttributeHelper::deserialize(deserializer)?;
@@ -56,7 +91,9 @@ impl<'de> Deserialize<'de> for SampleAttribute {
             name: helper.name,
             value: helper.value,
             r#type: inferred_type,
+            value_kind: helper.value_kind,
             live_check_result: None,
+            source: helper.source,
         })
     }
 }
@@ -78,7 +115,9 @@ impl TryFrom<&str> for SampleAttribute {
                 name: name.trim().to_owned(),
                 value: Some(json_value),
                 r#type,
+                value_kind: None,
                 live_check_result: None,
+                source: None,
             };
             return Ok(sample_attribute);
         }
@@ -87,12 +126,26 @@ impl TryFrom<&str> for SampleAttribute {
             name: trimmed.to_owned(),
             value: None,
             r#type: None,
+            value_kind: None,
             live_check_result: None,
+            source: None,
         })
     }
 }
 
 impl SampleAttribute {
+    /// Returns the kind of the value when it is not modeled by semantic conventions:
+    /// the declared `value_kind`, or a kvlist for a JSON object value
+    #[must_use]
+    pub fn unsupported_value_kind(&self) -> Option<UnsupportedValueKind> {
+        self.value_kind.or_else(|| {
+            self.value
+                .as_ref()
+                .filter(|value| value.is_object())
+                .map(|_| UnsupportedValueKind::Kvlist)
+        })
+    }
+
     /// Infer the type of the attribute from the value
     #[must_use]
     pub fn infer_type(value: &Value) -> Option<PrimitiveOrArrayTypeSpec> {
@@ -155,7 +208,7 @@ impl SampleAttribute {
         if let Some(result) = &mut self.live_check_result {
             for advice in &mut result.all_advice {
                 // If the advice is a template, adjust the name
-                if advice.advice_type == TEMPLATE_ATTRIBUTE_ADVICE_TYPE {
+                if matches!(advice.advice_type, BuiltinAdviceType::TemplateAttribute) {
                     if let Some(template_name) = advice.advice_context["template_name"].as_str() {
                         seen_attribute_name = template_name.to_owned();
                     }
@@ -174,52 +227,114 @@ impl LiveCheckRunner for SampleAttribute {
         parent_group: Option<Rc<ResolvedGroup>>,
         parent_signal: &Sample,
     ) -> Result<(), Error> {
+        if !live_checker.is_selected(&self.name) {
+            return Ok(());
+        }
         let mut result = LiveCheckResult::new();
+        let mut advice_list = Vec::new();
         // find the attribute in the registry
-        let semconv_attribute = {
-            if let Some(attribute) = live_checker.find_attribute(&self.name) {
-                Some(attribute)
-            } else {
-                live_checker.find_template(&self.name)
-            }
-        };
+        let mut semconv_attribute = live_checker.match_attribute(&self.name);
         let signal_type: Option<String> = parent_signal.signal_type();
         let signal_name: Option<String> = parent_signal.signal_name();
         if semconv_attribute.is_none() {
-            result.add_advice(Advice {
-                advice_type: MISSING_ATTRIBUTE_ADVICE_TYPE.to_owned(),
-                advice_context: json!({ ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone() }),
-                message: format!("Attribute '{}' does not exist in the registry.", self.name),
-                advice_level: AdviceLevel::Violation,
+            if let Some((normalized_name, attribute)) =
+                live_checker.match_normalized_attribute(&self.name)
+            {
+                advice_list.push(Advice {
+                    signal_type: signal_type.clone(),
+                    signal_name: signal_name.clone(),
+                    ..builtin_advice(
+                        BuiltinAdviceType::NameNormalizedToMatch,
+                        json!({
+                            ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone(),
+                            NORMALIZED_NAME_ADVICE_CONTEXT_KEY: normalized_name.clone(),
+                        }),
+                        AdviceLevel::Improvement,
+                    )
+                });
+                semconv_attribute = Some(attribute);
+            }
+        }
+        if semconv_attribute.is_none() {
+            advice_list.push(Advice {
                 signal_type,
                 signal_name,
+                ..builtin_advice(
+                    BuiltinAdviceType::MissingAttribute,
+                    json!({ ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone() }),
+                    AdviceLevel::Violation,
+                )
             });
         } else {
             // Provide an info advice if the attribute is a template
             if let Some(attribute) = &semconv_attribute {
                 if let AttributeType::Template(_) = attribute.r#type {
-                    result.add_advice(Advice {
-                        advice_type: TEMPLATE_ATTRIBUTE_ADVICE_TYPE.to_owned(),
-                        advice_context: json!({ ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone(), "template_name": attribute.name.clone() }),
-                        message: format!("Attribute '{}' is a template", self.name),
-                        advice_level: AdviceLevel::Information,
+                    advice_list.push(Advice {
+                        signal_type: signal_type.clone(),
+                        signal_name: signal_name.clone(),
+                        ..builtin_advice(
+                            BuiltinAdviceType::TemplateAttribute,
+                            json!({
+                                ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone(),
+                                TEMPLATE_NAME_ADVICE_CONTEXT_KEY: attribute.name.clone(),
+                            }),
+                            AdviceLevel::Information,
+                        )
+                    });
+                }
+                if live_checker.is_experimental_not_opted_in(&self.name, attribute) {
+                    advice_list.push(Advice {
+                        signal_type: signal_type.clone(),
+                        signal_name: signal_name.clone(),
+                        ..builtin_advice(
+                            BuiltinAdviceType::ExperimentalAttributeNotOptedIn,
+                            json!({ ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone() }),
+                            AdviceLevel::Improvement,
+                        )
+                    });
+                }
+                if let Some((stability, baseline)) = live_checker.stability_regression(attribute) {
+                    advice_list.push(Advice {
                         signal_type,
                         signal_name,
+                        ..builtin_advice(
+                            BuiltinAdviceType::StabilityRegression,
+                            json!({
+                                ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: self.name.clone(),
+                                STABILITY_ADVICE_CONTEXT_KEY: stability,
+                                BASELINE_STABILITY_ADVICE_CONTEXT_KEY: baseline,
+                            }),
+                            AdviceLevel::Violation,
+                        )
                     });
                 }
             }
         }
 
+        let mut advice_list = live_checker.process_advice(
+            SampleRef::Attribute(self),
+            parent_signal.source(),
+            advice_list,
+        );
+        let registry_attribute_name = semconv_attribute
+            .as_ref()
+            .map(|attribute| attribute.name.clone());
+
         // run advisors on the attribute
-        for advisor in live_checker.advisors.iter_mut() {
-            let advice_list = advisor.advise(
-                SampleRef::Attribute(self),
-                parent_signal,
-                semconv_attribute.clone(),
-                parent_group.clone(),
-            )?;
-            result.add_advice_list(advice_list);
+        advice_list.extend(live_checker.run_advisors(
+            SampleRef::Attribute(self),
+            parent_signal,
+            semconv_attribute,
+            parent_group,
+        )?);
+        if let Some(attribute_name) = registry_attribute_name {
+            let type_mismatched = advice_list
+                .iter()
+                .any(|advice| matches!(advice.advice_type, BuiltinAdviceType::TypeMismatch));
+            live_checker.observe_attribute(&attribute_name, type_mismatched);
         }
+        live_checker.order_advice(&mut advice_list);
+        result.add_advice_list(advice_list);
         self.live_check_result = Some(result);
         self.update_stats(stats);
         Ok(())
@@ -7,14 +7,14 @@ use std::rc::Rc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use weaver_checker::violation::{Advice, AdviceLevel};
+use weaver_checker::violation::{Advice, AdviceLevel, SampleSource};
 use weaver_forge::registry::ResolvedGroup;
 use weaver_semconv::group::InstrumentSpec;
 
 use crate::{
-    live_checker::LiveChecker, sample_attribute::SampleAttribute, Advisable, Error,
-    LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
-    MISSING_METRIC_ADVICE_TYPE,
+    advice_type::BuiltinAdviceType, live_checker::LiveChecker, message_catalog::builtin_advice,
+    sample_attribute::SampleAttribute, Advisable, Error, LiveCheckResult, LiveCheckRunner,
+    LiveCheckStatistics, Sample, SampleRef,
 };
 
 /// Represents the instrument type of a metric
@@ -27,6 +27,25 @@ pub enum SampleInstrument {
     Unsupported(String),
 }
 
+/// The aggregation temporality of a metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleTemporality {
+    /// Values are changes since the last report
+    Delta,
+    /// Values are accumulated since a fixed start time
+    Cumulative,
+}
+
+impl std::fmt::Display for SampleTemporality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SampleTemporality::Delta => write!(f, "delta"),
+            SampleTemporality::Cumulative => write!(f, "cumulative"),
+        }
+    }
+}
+
 /// The data point types of a metric
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
@@ -39,6 +58,22 @@ pub enum DataPoints {
     ExponentialHistogram(Vec<SampleExponentialHistogramDataPoint>),
 }
 
+impl DataPoints {
+    /// Returns the attribute set of each data point
+    #[must_use]
+    pub fn attribute_sets(&self) -> Vec<&[SampleAttribute]> {
+        match self {
+            DataPoints::Number(points) => points.iter().map(|p| p.attributes.as_slice()).collect(),
+            DataPoints::Histogram(points) => {
+                points.iter().map(|p| p.attributes.as_slice()).collect()
+            }
+            DataPoints::ExponentialHistogram(points) => {
+                points.iter().map(|p| p.attributes.as_slice()).collect()
+            }
+        }
+    }
+}
+
 /// Represents a single data point of a metric
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SampleNumberDataPoint {
@@ -283,10 +318,16 @@ pub struct SampleMetric {
     pub instrument: SampleInstrument,
     /// Unit of the metric.
     pub unit: String,
+    /// Aggregation temporality of the metric, for sums and histograms.
+    #[serde(default)]
+    pub temporality: Option<SampleTemporality>,
     /// Data points of the metric.
     pub data_points: Option<DataPoints>,
     /// Live check result
     pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
 }
 
 impl LiveCheckRunner for SampleMetric {
@@ -297,28 +338,37 @@ impl LiveCheckRunner for SampleMetric {
         _parent_group: Option<Rc<ResolvedGroup>>,
         parent_signal: &Sample,
     ) -> Result<(), Error> {
+        if !live_checker.is_selected(&self.name) {
+            return Ok(());
+        }
         let mut result = LiveCheckResult::new();
         // find the metric in the registry
         let semconv_metric = live_checker.find_metric(&self.name);
+        let mut advice_list = Vec::new();
         if semconv_metric.is_none() {
-            result.add_advice(Advice {
-                advice_type: MISSING_METRIC_ADVICE_TYPE.to_owned(),
-                advice_context: Value::Null,
-                message: "Metric does not exist in the registry.".to_owned(),
-                advice_level: AdviceLevel::Violation,
+            advice_list.push(Advice {
                 signal_type: Some("metric".to_owned()),
                 signal_name: Some(self.name.clone()),
+                ..builtin_advice(
+                    BuiltinAdviceType::MissingMetric,
+                    Value::Null,
+                    AdviceLevel::Violation,
+                )
             });
         };
-        for advisor in live_checker.advisors.iter_mut() {
-            let advice_list = advisor.advise(
-                SampleRef::Metric(self),
-                parent_signal,
-                None,
-                semconv_metric.clone(),
-            )?;
-            result.add_advice_list(advice_list);
-        }
+        let mut advice_list = live_checker.process_advice(
+            SampleRef::Metric(self),
+            parent_signal.source(),
+            advice_list,
+        );
+        advice_list.extend(live_checker.run_advisors(
+            SampleRef::Metric(self),
+            parent_signal,
+            None,
+            semconv_metric.clone(),
+        )?);
+        live_checker.order_advice(&mut advice_list);
+        result.add_advice_list(advice_list);
         // Get advice for the data points
         match &mut self.data_points {
             Some(DataPoints::Number(points)) => {
@@ -6,11 +6,12 @@ use std::rc::Rc;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use weaver_checker::violation::{Advice, SampleSource};
 use weaver_forge::registry::ResolvedGroup;
 
 use crate::{
-    live_checker::LiveChecker, sample_attribute::SampleAttribute, Advisable, Error,
-    LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
+    advice::check_attributes, live_checker::LiveChecker, sample_attribute::SampleAttribute,
+    Advisable, Error, LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
 };
 
 /// Represents a resource
@@ -21,6 +22,21 @@ pub struct SampleResource {
     pub attributes: Vec<SampleAttribute>,
     /// Live check result
     pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
+}
+
+impl SampleResource {
+    /// Returns the `service.name` attribute value of the resource, if it is a string
+    #[must_use]
+    pub fn service_name(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == "service.name")
+            .and_then(|attribute| attribute.value.as_ref())
+            .and_then(|value| value.as_str())
+    }
 }
 
 impl Advisable for SampleResource {
@@ -31,6 +47,12 @@ impl Advisable for SampleResource {
     fn entity_type(&self) -> &str {
         "resource"
     }
+
+    /// Checks the attributes required by the resource groups of the registry
+    fn builtin_advice(&self, live_checker: &LiveChecker, parent_signal: &Sample) -> Vec<Advice> {
+        let semconv_attributes = live_checker.find_resource_attributes(&self.attributes);
+        check_attributes(&semconv_attributes, &self.attributes, parent_signal)
+    }
 }
 
 impl LiveCheckRunner for SampleResource {
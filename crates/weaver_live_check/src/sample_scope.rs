@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Intermediary format for telemetry sample instrumentation scopes
+
+use std::rc::Rc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use weaver_checker::violation::SampleSource;
+use weaver_forge::registry::ResolvedGroup;
+
+use crate::{
+    live_checker::LiveChecker, sample_attribute::SampleAttribute, Advisable, Error,
+    LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
+};
+
+/// Represents an instrumentation scope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SampleScope {
+    /// The name of the instrumentation scope
+    #[serde(default)]
+    pub name: String,
+    /// The version of the instrumentation scope
+    #[serde(default)]
+    pub version: Option<String>,
+    /// The attributes of the instrumentation scope
+    #[serde(default)]
+    pub attributes: Vec<SampleAttribute>,
+    /// Live check result
+    pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
+}
+
+impl Advisable for SampleScope {
+    fn as_sample_ref(&self) -> SampleRef<'_> {
+        SampleRef::Scope(self)
+    }
+
+    fn entity_type(&self) -> &str {
+        "scope"
+    }
+}
+
+impl LiveCheckRunner for SampleScope {
+    fn run_live_check(
+        &mut self,
+        live_checker: &mut LiveChecker,
+        stats: &mut LiveCheckStatistics,
+        parent_group: Option<Rc<ResolvedGroup>>,
+        parent_signal: &Sample,
+    ) -> Result<(), Error> {
+        self.live_check_result =
+            Some(self.run_advisors(live_checker, stats, parent_group.clone(), parent_signal)?);
+        self.attributes
+            .run_live_check(live_checker, stats, parent_group.clone(), parent_signal)?;
+        Ok(())
+    }
+}
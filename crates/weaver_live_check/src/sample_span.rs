@@ -6,12 +6,13 @@ use std::rc::Rc;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use weaver_checker::violation::{Advice, SampleSource};
 use weaver_forge::registry::ResolvedGroup;
 use weaver_semconv::group::SpanKindSpec;
 
 use crate::{
-    live_checker::LiveChecker, sample_attribute::SampleAttribute, Advisable, Error,
-    LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
+    advice::check_attributes, live_checker::LiveChecker, sample_attribute::SampleAttribute,
+    Advisable, Error, LiveCheckResult, LiveCheckRunner, LiveCheckStatistics, Sample, SampleRef,
 };
 
 /// The status code of the span
@@ -44,6 +45,12 @@ pub struct SampleSpan {
     pub kind: SpanKindSpec,
     /// Status
     pub status: Option<Status>,
+    /// The trace id of the span, in hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// The span id of the span, in hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
     /// The span's attributes
     #[serde(default)]
     pub attributes: Vec<SampleAttribute>,
@@ -55,6 +62,9 @@ pub struct SampleSpan {
     pub span_links: Vec<SampleSpanLink>,
     /// Live check result
     pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
 }
 
 impl Advisable for SampleSpan {
@@ -96,11 +106,17 @@ impl LiveCheckRunner for SampleSpan {
 pub struct SampleSpanEvent {
     /// The name of the event
     pub name: String,
+    /// The time of the event, in RFC 3339 format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
     /// The attributes of the event
     #[serde(default)]
     pub attributes: Vec<SampleAttribute>,
     /// Live check result
     pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
 }
 
 impl Advisable for SampleSpanEvent {
@@ -111,6 +127,14 @@ impl Advisable for SampleSpanEvent {
     fn entity_type(&self) -> &str {
         "span_event"
     }
+
+    /// Checks the attributes of the registry event with the same name, e.g. `exception`
+    fn builtin_advice(&self, live_checker: &LiveChecker, parent_signal: &Sample) -> Vec<Advice> {
+        live_checker
+            .find_event(&self.name)
+            .map(|event| check_attributes(&event.attributes, &self.attributes, parent_signal))
+            .unwrap_or_default()
+    }
 }
 
 impl LiveCheckRunner for SampleSpanEvent {
@@ -132,11 +156,20 @@ impl LiveCheckRunner for SampleSpanEvent {
 /// Represents a span link
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SampleSpanLink {
+    /// The trace id of the linked span, in hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// The span id of the linked span, in hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
     /// The attributes of the link
     #[serde(default)]
     pub attributes: Vec<SampleAttribute>,
     /// Live check result
     pub live_check_result: Option<LiveCheckResult>,
+    /// Where the sample was read from, not set for samples constructed programmatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SampleSource>,
 }
 
 impl Advisable for SampleSpanLink {
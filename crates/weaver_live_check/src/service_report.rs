@@ -0,0 +1,258 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Group the live check samples and statistics by the `service.name` of their resource
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use weaver_checker::violation::Advice;
+use weaver_forge::registry::ResolvedRegistry;
+
+use crate::{grouped_advice::sample_advice, LiveCheckStatistics, Sample};
+
+/// The service name used for samples without a `service.name` resource attribute
+pub const UNKNOWN_SERVICE_NAME: &str = "unknown";
+
+/// The samples and statistics of a single service
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceReport {
+    /// The `service.name` of the resource
+    pub service_name: String,
+    /// The live check samples of the service
+    pub samples: Vec<Sample>,
+    /// The statistics of the service
+    pub statistics: LiveCheckStatistics,
+}
+
+/// A live check report with the samples grouped by service
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiveCheckServiceReport {
+    /// The reports per service, ordered by service name
+    pub services: Vec<ServiceReport>,
+    /// Advice from batch advisors, given across all the samples
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub batch_advice: Vec<Advice>,
+    /// The statistics for all the services
+    pub statistics: LiveCheckStatistics,
+}
+
+impl LiveCheckServiceReport {
+    /// Returns all the advice of the report, the advice of each sample by service
+    /// followed by the batch advice
+    #[must_use]
+    pub fn all_advice(&self) -> Vec<Advice> {
+        let mut advice: Vec<Advice> = self
+            .services
+            .iter()
+            .flat_map(|service| service.samples.iter().flat_map(sample_advice))
+            .collect();
+        advice.extend(self.batch_advice.iter().cloned());
+        advice
+    }
+}
+
+/// Groups samples by service as they are checked.
+///
+/// Samples are attributed to the service of the last resource sample seen,
+/// which matches the order in which the OTLP ingester emits samples.
+/// Samples seen before any resource, or after a resource without a
+/// `service.name`, are grouped under [`UNKNOWN_SERVICE_NAME`].
+pub struct ServiceGroups {
+    current_service: String,
+    services: BTreeMap<String, ServiceReport>,
+}
+
+impl Default for ServiceGroups {
+    fn default() -> Self {
+        ServiceGroups {
+            current_service: UNKNOWN_SERVICE_NAME.to_owned(),
+            services: BTreeMap::new(),
+        }
+    }
+}
+
+impl ServiceGroups {
+    /// Create a new ServiceGroups
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the statistics of the service the sample belongs to. A resource
+    /// sample sets the service for the following samples.
+    pub fn statistics_for(
+        &mut self,
+        sample: &Sample,
+        registry: &ResolvedRegistry,
+    ) -> &mut LiveCheckStatistics {
+        if let Sample::Resource(resource) = sample {
+            resource
+                .service_name()
+                .unwrap_or(UNKNOWN_SERVICE_NAME)
+                .clone_into(&mut self.current_service);
+        }
+        &mut self
+            .services
+            .entry(self.current_service.clone())
+            .or_insert_with(|| ServiceReport {
+                service_name: self.current_service.clone(),
+                samples: Vec::new(),
+                statistics: LiveCheckStatistics::new(registry),
+            })
+            .statistics
+    }
+
+    /// Add a checked sample to the current service
+    pub fn add_sample(&mut self, sample: Sample, registry: &ResolvedRegistry) {
+        let _ = self.statistics_for(&sample, registry);
+        if let Some(service) = self.services.get_mut(&self.current_service) {
+            service.samples.push(sample);
+        }
+    }
+
+    /// Finalize the statistics and build the report. The overall statistics
+    /// include the batch advice.
+    #[must_use]
+    pub fn into_report(
+        self,
+        batch_advice: Vec<Advice>,
+        registry: &ResolvedRegistry,
+    ) -> LiveCheckServiceReport {
+        let mut statistics = LiveCheckStatistics::new(registry);
+        let services = self
+            .services
+            .into_values()
+            .map(|mut service| {
+                statistics.merge(&service.statistics);
+                service.statistics.finalize();
+                service
+            })
+            .collect();
+        statistics.add_batch_advice(&batch_advice);
+        statistics.finalize();
+        LiveCheckServiceReport {
+            services,
+            batch_advice,
+            statistics,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        advice::TypeAdvisor, live_checker::LiveChecker, sample_attribute::SampleAttribute,
+        sample_resource::SampleResource, LiveCheckReport, LiveCheckRunner,
+    };
+    use serde_json::{json, Value};
+    use weaver_checker::violation::AdviceLevel;
+
+    fn resource(attributes: &[&str]) -> Sample {
+        Sample::Resource(SampleResource {
+            attributes: attributes
+                .iter()
+                .map(|attribute| SampleAttribute::try_from(*attribute).unwrap())
+                .collect(),
+            live_check_result: None,
+            source: None,
+        })
+    }
+
+    #[test]
+    fn test_group_by_service() {
+        let registry = ResolvedRegistry {
+            registry_url: "TEST".to_owned(),
+            groups: vec![],
+        };
+        let samples = vec![
+            Sample::Attribute(SampleAttribute::try_from("before.resource").unwrap()),
+            resource(&["service.name=checkout"]),
+            Sample::Attribute(SampleAttribute::try_from("checkout.one").unwrap()),
+            Sample::Attribute(SampleAttribute::try_from("checkout.two").unwrap()),
+            resource(&["service.name=cart"]),
+            Sample::Attribute(SampleAttribute::try_from("cart.one").unwrap()),
+            resource(&["host.name=localhost"]),
+        ];
+
+        let mut live_checker = LiveChecker::new(registry.clone(), vec![Box::new(TypeAdvisor)]);
+        let mut groups = ServiceGroups::new();
+        for mut sample in samples {
+            let stats = groups.statistics_for(&sample, &live_checker.registry);
+            sample
+                .run_live_check(&mut live_checker, stats, None, &sample.clone())
+                .unwrap();
+            groups.add_sample(sample, &live_checker.registry);
+        }
+        let report = groups.into_report(vec![], &registry);
+
+        let summary: Vec<(&str, usize, usize)> = report
+            .services
+            .iter()
+            .map(|service| {
+                (
+                    service.service_name.as_str(),
+                    service.samples.len(),
+                    service.statistics.total_advisories,
+                )
+            })
+            .collect();
+        // Every attribute is missing from the empty registry
+        assert_eq!(
+            summary,
+            vec![("cart", 2, 2), ("checkout", 3, 3), ("unknown", 2, 2)]
+        );
+        assert_eq!(report.statistics.total_advisories, 7);
+        assert_eq!(report.statistics.total_entities, 10);
+        assert!(report.statistics.has_violations());
+
+        // The advice of the cart service comes first
+        let advice = report.all_advice();
+        assert_eq!(advice.len(), 7);
+        assert_eq!(
+            advice[1].advice_context,
+            json!({"attribute_name": "cart.one"})
+        );
+    }
+
+    #[test]
+    fn test_post_process_service_report() {
+        let registry = ResolvedRegistry {
+            registry_url: "TEST".to_owned(),
+            groups: vec![],
+        };
+        // Count the samples of each report seen by the hook
+        let live_checker = LiveChecker::builder(registry.clone())
+            .without_default_advisors()
+            .post_process(Box::new(|report: &mut LiveCheckReport| {
+                let count = report.samples.len();
+                report.batch_advice.push(Advice::new(
+                    "sample_count".into(),
+                    json!({ "count": count }),
+                    format!("{count} samples."),
+                    AdviceLevel::Information,
+                ));
+            }))
+            .build();
+        let mut groups = ServiceGroups::new();
+        for sample in [
+            resource(&["service.name=checkout"]),
+            Sample::Attribute(SampleAttribute::try_from("checkout.one").unwrap()),
+            resource(&["service.name=cart"]),
+        ] {
+            let _ = groups.statistics_for(&sample, &registry);
+            groups.add_sample(sample, &registry);
+        }
+        let mut report = groups.into_report(vec![], &registry);
+        live_checker.post_process_service_report(&mut report);
+
+        let counts: Vec<&Value> = report
+            .batch_advice
+            .iter()
+            .map(|advice| &advice.advice_context["count"])
+            .collect();
+        // cart, checkout, then the batch report without samples
+        assert_eq!(counts, vec![&json!(1), &json!(2), &json!(0)]);
+        assert_eq!(report.services[1].samples.len(), 2);
+    }
+}
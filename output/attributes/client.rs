@@ -0,0 +1,25 @@
+/*
+ * Copyright The OpenTelemetry Authors
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! DO NOT EDIT, THIS FILE HAS BEEN GENERATED BY WEAVER
+
+/// Client address - domain name if available without reverse DNS lookup; otherwise, IP address or Unix domain socket name.
+///
+/// When observed from the server side, and when communicating through an intermediary, `client.address` SHOULD represent the client address behind any intermediaries,  for example proxies, if it's available
+///
+/// ## Examples:
+/// - client.example.com
+/// - 10.1.2.80
+/// - /tmp/my.sock
+pub const CLIENT_ADDRESS: crate::attributes::AttributeKey<opentelemetry::StringValue> = crate::attributes::AttributeKey::new("client.address");
+
+/// Client port number.
+///
+/// When observed from the server side, and when communicating through an intermediary, `client.port` SHOULD represent the client port behind any intermediaries,  for example proxies, if it's available
+///
+/// ## Examples:
+/// - 65123
+pub const CLIENT_PORT: crate::attributes::AttributeKey<i64> = crate::attributes::AttributeKey::new("client.port");
+
@@ -11,6 +11,7 @@ use schemars::schema_for;
 use serde::Serialize;
 use serde_json::to_string_pretty;
 use std::{io::Write, path::PathBuf};
+use weaver_checker::violation::Advice;
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_forge::registry::ResolvedRegistry;
 use weaver_semconv::semconv::SemConvSpec;
@@ -60,6 +61,8 @@ pub enum JsonSchemaType {
     ResolvedRegistry,
     /// The JSON schema of a semantic convention group.
     SemconvGroup,
+    /// The JSON schema of an advice in the live check reports.
+    Advice,
 }
 
 impl From<Error> for DiagnosticMessages {
@@ -74,6 +77,7 @@ pub(crate) fn command(args: &RegistryJsonSchemaArgs) -> Result<ExitDirectives, D
     let json_schema = match args.json_schema {
         JsonSchemaType::ResolvedRegistry => schema_for!(ResolvedRegistry),
         JsonSchemaType::SemconvGroup => schema_for!(SemConvSpec),
+        JsonSchemaType::Advice => schema_for!(Advice),
     };
 
     let json_schema_str =
@@ -4,29 +4,45 @@
 //! - Comparing it to a semantic convention registry.
 //! - Running built-in and custom policies to provide advice on how to improve the telemetry.
 
-use std::path::PathBuf;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 use include_dir::{include_dir, Dir};
 
-use log::info;
+use log::{info, warn};
+use weaver_checker::violation::{Advice, AdviceLevel};
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::log_success;
+use weaver_common::vdir::VirtualDirectoryPath;
 use weaver_forge::config::{Params, WeaverConfig};
 use weaver_forge::file_loader::EmbeddedFileLoader;
+use weaver_forge::registry::ResolvedRegistry;
 use weaver_forge::{OutputDirective, TemplateEngine};
 use weaver_live_check::advice::{
-    Advisor, DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, StabilityAdvisor, TypeAdvisor,
+    AnyTypeAdvisor, DeltaCumulativeConsistencyAdvisor, DuplicateTimeSeriesAdvisor,
+    ExampleShapeAdvisor, MisplacedAttributeAdvisor, MisspellingAdvisor, NewerRegistryAdvisor,
+    NumericCoercion, RegoAdvisor, TraceContextAdvisor, TypeChangeAdvisor,
+    UnitInstrumentConsistencyAdvisor, UnknownAttributeAdvisor,
 };
+use weaver_live_check::advice_baseline::AdviceBaseline;
+use weaver_live_check::advice_renderer::{AdviceRenderer, TextRenderer, YamlRenderer};
 use weaver_live_check::json_file_ingester::JsonFileIngester;
+use weaver_live_check::json_lines_ingester::JsonLinesIngester;
 use weaver_live_check::json_stdin_ingester::JsonStdinIngester;
-use weaver_live_check::live_checker::LiveChecker;
+use weaver_live_check::live_checker::{
+    merge_registries, AdviceLevelOverride, LiveChecker, NameFilter, RegistryMeta, SeverityBudget,
+};
+use weaver_live_check::message_catalog::MessageCatalog;
+use weaver_live_check::otlp_protobuf::{OtlpProtobufIngester, OtlpSignal};
+use weaver_live_check::service_report::{LiveCheckServiceReport, ServiceGroups};
 use weaver_live_check::text_file_ingester::TextFileIngester;
 use weaver_live_check::text_stdin_ingester::TextStdinIngester;
-use weaver_live_check::{Error, Ingester, LiveCheckReport, LiveCheckRunner, LiveCheckStatistics};
+use weaver_live_check::{Error, Ingester, LiveCheckReport, LiveCheckStatistics};
+use weaver_semconv::registry_repo::RegistryRepo;
 
 use crate::registry::{PolicyArgs, RegistryArgs};
-use crate::util::prepare_main_registry;
+use crate::util::prepare_main_registry_with_manifest;
 use crate::{DiagnosticArgs, ExitDirectives};
 
 use super::otlp::otlp_ingester::OtlpIngester;
@@ -58,12 +74,17 @@ impl From<String> for InputSource {
 enum InputFormat {
     Text,
     Json,
+    JsonLines,
+    OtlpProtobuf(OtlpSignal),
 }
 
 impl From<String> for InputFormat {
     fn from(s: String) -> Self {
         match s.to_lowercase().as_str() {
             "json" | "js" => InputFormat::Json,
+            "jsonl" | "ndjson" => InputFormat::JsonLines,
+            "protobuf-metrics" => InputFormat::OtlpProtobuf(OtlpSignal::Metrics),
+            "protobuf-traces" => InputFormat::OtlpProtobuf(OtlpSignal::Traces),
             _ => InputFormat::Text,
         }
     }
@@ -88,11 +109,15 @@ pub struct RegistryLiveCheckArgs {
     #[arg(long, default_value = "otlp")]
     input_source: InputSource,
 
-    /// The format of the input telemetry. (Not required for OTLP). text | json
+    /// The format of the input telemetry. (Not required for OTLP). text | json | jsonl |
+    /// protobuf-metrics | protobuf-traces
     #[arg(long, default_value = "json")]
     input_format: InputFormat,
 
     /// Format used to render the report. Predefined formats are: ansi, json
+    ///
+    /// The text and yaml formats render the list of advice alone, grouped by
+    /// advice level for text. They disable stream mode.
     #[arg(long, default_value = "ansi")]
     format: String,
 
@@ -107,11 +132,23 @@ pub struct RegistryLiveCheckArgs {
     #[arg(long, default_value = "false")]
     no_stream: bool,
 
+    /// Group the report by the `service.name` of the sample resources, with a
+    /// summary per service. Samples without a service are grouped under "unknown".
+    ///
+    /// This flag disables stream mode.
+    #[arg(long, default_value = "false")]
+    group_by_service: bool,
+
     /// Path to the directory where the generated artifacts will be saved.
     /// If not specified, the report is printed to stdout.
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Path of a file where the statistics are written in the Prometheus text
+    /// exposition format, e.g. to scrape the advice counts of conformance runs.
+    #[arg(long)]
+    prometheus_output: Option<PathBuf>,
+
     /// Address used by the gRPC OTLP listener.
     #[clap(long, default_value = "0.0.0.0")]
     otlp_grpc_address: String,
@@ -120,7 +157,7 @@ pub struct RegistryLiveCheckArgs {
     #[clap(long, default_value = "4317")]
     otlp_grpc_port: u16,
 
-    /// Port used by the HTTP admin port (endpoints: /stop).
+    /// Port used by the HTTP admin port (endpoints: /stop, /pause, /resume).
     #[clap(long, default_value = "4320")]
     admin_port: u16,
 
@@ -128,9 +165,17 @@ pub struct RegistryLiveCheckArgs {
     #[clap(long, default_value = "10")]
     inactivity_timeout: u64,
 
+    /// Max number of OTLP requests buffered while the receiver is paused via the
+    /// /pause admin endpoint. Requests received once the buffer is full are dropped.
+    /// The policies are not reloaded on /resume.
+    #[clap(long, default_value = "1000")]
+    paused_buffer_size: usize,
+
     /// Advice policies directory. Set this to override the default policies.
+    ///
+    /// Can be repeated to merge the policies of several directories.
     #[arg(long)]
-    advice_policies: Option<PathBuf>,
+    advice_policies: Vec<PathBuf>,
 
     /// Advice preprocessor. A jq script to preprocess the registry data before passing to rego.
     ///
@@ -139,15 +184,431 @@ pub struct RegistryLiveCheckArgs {
     /// versus processing the data for every sample.
     #[arg(long)]
     advice_preprocessor: Option<PathBuf>,
+
+    /// Attach the evaluation trace of the advice policies to the advice they produce,
+    /// in an `explanation` field. Meant for debugging policies.
+    #[arg(long, default_value = "false")]
+    explain_advice_policies: bool,
+
+    /// Also evaluate the `live_check_batch_advice` package of the advice policies, once
+    /// on all the samples, for rules across samples. The samples are kept until the
+    /// input is closed.
+    #[arg(long, default_value = "false")]
+    batch_advice_policies: bool,
+
+    /// Advice type the advice policies are allowed to produce. Can be repeated.
+    ///
+    /// When set, advice of any other type from the policies is an error.
+    #[arg(long)]
+    allowed_advice_type: Vec<String>,
+
+    /// Experimental attribute opted in to. Can be repeated.
+    ///
+    /// When provided, any other experimental attribute seen in the samples is reported.
+    #[arg(long)]
+    experimental_opt_in: Vec<String>,
+
+    /// Only check the attributes and metrics whose name matches this glob pattern,
+    /// e.g. `db.*`. Can be repeated.
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Skip the attributes and metrics whose name matches this glob pattern.
+    /// Can be repeated.
+    ///
+    /// Skipped samples get no advice and are not counted in the statistics.
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Report the sample attributes not defined in any registry group with an
+    /// `attribute_not_in_registry` advice, for exhaustive registries.
+    #[arg(long, default_value = "false")]
+    report_unknown_attributes: bool,
+
+    /// Report the sample attributes typed `any` in the registry with an
+    /// `attribute_type_any` advice, encouraging concrete types.
+    #[arg(long, default_value = "false")]
+    report_any_type: bool,
+
+    /// Report the attribute values shaped unlike the examples of their registry
+    /// attribute, e.g. free text for integer examples, with a `value_unlike_examples` advice.
+    #[arg(long, default_value = "false")]
+    compare_examples: bool,
+
+    /// Suggest the closest registry attribute for the sample attributes not defined
+    /// in the registry, with a `possible_misspelling` advice carrying a confidence.
+    #[arg(long, default_value = "false")]
+    suggest_misspellings: bool,
+
+    /// The maximum number of registry attributes suggested for a misspelled attribute,
+    /// the closest first.
+    #[arg(long, default_value = "1")]
+    misspelling_suggestions: usize,
+
+    /// Report the span and metric attributes that the registry declares on an
+    /// entity, e.g. `service.name`, with an `attribute_should_be_resource` advice.
+    #[arg(long, default_value = "false")]
+    report_misplaced_attributes: bool,
+
+    /// Check the metric units against their name and instrument, e.g. a `*.duration`
+    /// histogram expected in `s`, with a `unit_instrument_inconsistent` advice.
+    #[arg(long, default_value = "false")]
+    check_unit_conventions: bool,
+
+    /// Check that the deltas of metrics reported with both temporalities sum up to
+    /// their cumulative value, with a `delta_cumulative_drift` advice.
+    #[arg(long, default_value = "false")]
+    check_delta_cumulative: bool,
+
+    /// Check the trace and span ids of spans, span links and exemplars, with an
+    /// `invalid_trace_id` or `invalid_span_id` advice.
+    #[arg(long, default_value = "false")]
+    check_trace_context: bool,
+
+    /// Report the data points of a metric sample sharing an identical attribute set,
+    /// with a `duplicate_time_series` advice.
+    #[arg(long, default_value = "false")]
+    check_duplicate_series: bool,
+
+    /// How double values with no fractional part, e.g. `5.0`, are treated for int
+    /// attributes. strict | lenient | lenient-with-advice
+    ///
+    /// Strict gives them a `type_mismatch`, lenient accepts them and lenient-with-advice
+    /// accepts them with a `numeric_coercion_applied` information advice.
+    #[arg(long, default_value = "strict", value_parser = parse_numeric_coercion)]
+    numeric_coercion: NumericCoercion,
+
+    /// Stop at the first sample given a violation, for fast feedback in CI.
+    ///
+    /// The report only covers the samples checked so far.
+    #[arg(long, default_value = "false")]
+    fail_fast: bool,
+
+    /// Maximum number of advice allowed for an advice level, e.g. `improvement=5`.
+    /// Can be repeated.
+    ///
+    /// With a budget, the exit code is non-zero when a level is over its maximum.
+    /// Violations are allowed none unless given a maximum, other levels are unlimited.
+    #[arg(long, value_parser = parse_budget)]
+    budget: Vec<(AdviceLevel, usize)>,
+
+    /// Collapse the identical advice given on a sample by several advisors into one
+    /// entry, with a `count` of the advisors that gave it.
+    #[arg(long, default_value = "false")]
+    dedup: bool,
+
+    /// Sort the advice of each sample, and the batch advice, by decreasing level,
+    /// advice type and sample name, for a deterministic output e.g. in golden files.
+    #[arg(long, default_value = "false")]
+    sort_advice: bool,
+
+    /// Reuse the advice computed for identical samples, keeping the advice of up to
+    /// this number of distinct samples. Speeds up high-volume uniform traffic.
+    ///
+    /// Batch advisors still run on every sample.
+    #[arg(long)]
+    advice_cache: Option<usize>,
+
+    /// Log the number of samples checked and of violations given so far every
+    /// this number of samples, to follow long runs.
+    #[arg(long)]
+    progress: Option<usize>,
+
+    /// Log the cumulative time spent in each advisor once the live check is done,
+    /// to find the slow advisors on a workload.
+    #[arg(long, default_value = "false")]
+    advisor_timings: bool,
+
+    /// Override the level of an advice type, e.g. `recommended_attribute_not_present=violation`.
+    /// Can be repeated.
+    ///
+    /// Overrides apply to the advice of all the advisors, builtin and Rego.
+    #[arg(long, value_parser = parse_advice_level_override)]
+    advice_level: Vec<(String, AdviceLevel)>,
+
+    /// Replace the message of an advice type with a template referring to the advice
+    /// context fields in braces, e.g. `type_mismatch=Le type de '{attribute_name}' est '{attribute_type}'`.
+    /// Can be repeated.
+    #[arg(long, value_parser = parse_advice_message)]
+    advice_message: Vec<(String, String)>,
+
+    /// Parameters to specify an optional baseline semantic convention registry.
+    ///
+    /// When provided, samples using an attribute type that was valid in the baseline
+    /// registry but has since changed are reported, as well as attributes whose
+    /// stability was lowered since.
+    #[arg(long)]
+    baseline_registry: Option<VirtualDirectoryPath>,
+
+    /// Parameters to specify an optional newer semantic convention registry.
+    ///
+    /// When provided, samples that are not valid against the registry but would be
+    /// against the newer one are reported, for example attributes added since.
+    #[arg(long)]
+    newer_registry: Option<VirtualDirectoryPath>,
+
+    /// Parameters to specify an optional extension semantic convention registry,
+    /// e.g. private conventions on top of the public ones.
+    ///
+    /// Samples are checked against both registries, the extension registry taking
+    /// precedence on the attributes, metrics and groups defined in both. The advice
+    /// records the registry that matched.
+    #[arg(long)]
+    extension_registry: Option<VirtualDirectoryPath>,
+
+    /// Path of a baseline file of accepted advice. Only the advice not in the
+    /// baseline is reported, and the baseline advice no longer given are reported
+    /// with a `baseline_advice_resolved` advice.
+    #[arg(long)]
+    advice_baseline: Option<PathBuf>,
+
+    /// Path of a file where the advice of the run is saved as a baseline, to be
+    /// given to `--advice-baseline` in later runs.
+    #[arg(long)]
+    write_advice_baseline: Option<PathBuf>,
+}
+
+/// Parse an advice level.
+fn parse_advice_level(level: &str) -> Result<AdviceLevel, String> {
+    level
+        .parse()
+        .map_err(|e: weaver_checker::Error| e.to_string())
+}
+
+/// Parse an `advice_type=level` advice level override.
+fn parse_advice_level_override(value: &str) -> Result<(String, AdviceLevel), String> {
+    let (advice_type, level) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected `advice_type=level`, found `{value}`"))?;
+    Ok((advice_type.to_owned(), parse_advice_level(level)?))
+}
+
+/// Parse a `level=count` budget of advice.
+fn parse_budget(value: &str) -> Result<(AdviceLevel, usize), String> {
+    let (level, max) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected `level=count`, found `{value}`"))?;
+    let max = max
+        .parse()
+        .map_err(|e| format!("Invalid count `{max}` for the `{level}` budget: {e}"))?;
+    Ok((parse_advice_level(level)?, max))
+}
+
+fn parse_numeric_coercion(value: &str) -> Result<NumericCoercion, String> {
+    match value {
+        "strict" => Ok(NumericCoercion::Strict),
+        "lenient" => Ok(NumericCoercion::Lenient),
+        "lenient-with-advice" => Ok(NumericCoercion::LenientWithAdvice),
+        _ => Err(format!(
+            "Expected `strict`, `lenient` or `lenient-with-advice`, found `{value}`"
+        )),
+    }
+}
+
+fn parse_advice_message(value: &str) -> Result<(String, String), String> {
+    let (advice_type, template) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected `advice_type=template`, found `{value}`"))?;
+    Ok((advice_type.to_owned(), template.to_owned()))
+}
+
+/// Log where a fail fast live check stopped.
+fn log_fail_fast(sample_index: usize) {
+    warn!("Stopped at sample #{sample_index} on its first violation (--fail-fast)");
+}
+
+/// Check the statistics against the budget, if any, logging the levels over their
+/// maximum. Returns true when the run fails: over budget, or with violations
+/// without a budget.
+fn is_failed_run(live_checker: &LiveChecker, stats: &mut LiveCheckStatistics) -> bool {
+    let Some(budget) = live_checker.check_budget(stats) else {
+        return stats.has_violations();
+    };
+    for usage in budget.exceeded() {
+        warn!(
+            "{} {} advice, {} over the budget of {} (--budget)",
+            usage.count, usage.level, usage.excess, usage.max
+        );
+    }
+    budget.is_exceeded()
+}
+
+/// Write the statistics in the Prometheus text exposition format.
+fn write_prometheus_output(
+    path: &Path,
+    stats: &LiveCheckStatistics,
+) -> Result<(), DiagnosticMessages> {
+    std::fs::write(path, stats.to_prometheus_text()).map_err(|e| {
+        DiagnosticMessages::from(Error::OutputError {
+            error: format!("Failed to write `{}`: {e}", path.display()),
+        })
+    })
 }
 
-fn default_advisors() -> Vec<Box<dyn Advisor>> {
-    vec![
-        Box::new(DeprecatedAdvisor),
-        Box::new(StabilityAdvisor),
-        Box::new(TypeAdvisor),
-        Box::new(EnumAdvisor),
-    ]
+/// Save the advice given by the live checker as a baseline.
+fn write_advice_baseline(
+    path: &Path,
+    live_checker: &LiveChecker,
+) -> Result<(), DiagnosticMessages> {
+    if let Some(observed_advice) = live_checker.observed_advice() {
+        observed_advice.save(path)?;
+    }
+    Ok(())
+}
+
+/// The formats rendering the list of advice with an [`AdviceRenderer`] instead of templates
+#[derive(Debug, Clone, Copy)]
+enum AdviceFormat {
+    /// Plain text grouped by advice level, color-coded on a terminal
+    Text,
+    /// A YAML sequence
+    Yaml,
+}
+
+impl AdviceFormat {
+    /// Returns the advice format of a `--format` value, if any
+    fn of(format: &str) -> Option<Self> {
+        match format {
+            "text" => Some(AdviceFormat::Text),
+            "yaml" => Some(AdviceFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Render the advice to the standard output or to a `live_check` file in the output directory
+    fn write(
+        self,
+        advice: &[Advice],
+        output: &Path,
+        output_directive: &OutputDirective,
+    ) -> Result<(), Error> {
+        let output_error = |e: std::io::Error| Error::OutputError {
+            error: e.to_string(),
+        };
+        match output_directive {
+            OutputDirective::Stdout => self.render(advice, &mut std::io::stdout()),
+            OutputDirective::Stderr => self.render(advice, &mut std::io::stderr()),
+            OutputDirective::File => {
+                std::fs::create_dir_all(output).map_err(output_error)?;
+                let extension = match self {
+                    AdviceFormat::Text => "txt",
+                    AdviceFormat::Yaml => "yaml",
+                };
+                let mut file =
+                    std::fs::File::create(output.join(format!("live_check.{extension}")))
+                        .map_err(output_error)?;
+                self.render(advice, &mut file)
+            }
+        }
+    }
+
+    /// Render the advice to a writer, the text being color-coded when the writer is a terminal
+    fn render<W: Write + IsTerminal>(self, advice: &[Advice], writer: &mut W) -> Result<(), Error> {
+        match self {
+            AdviceFormat::Text => TextRenderer::for_writer(writer).render(advice, writer),
+            AdviceFormat::Yaml => YamlRenderer.render(advice, writer),
+        }
+    }
+}
+
+/// How the result of a live check run is output
+enum ReportOutput {
+    /// Rendered with the templates of the format
+    Template(Box<TemplateEngine>),
+    /// The list of advice rendered in an advice format
+    Advice(AdviceFormat),
+}
+
+/// The result of a live check run, output once all the samples have been checked
+enum RunResult {
+    /// The report, without the samples when they were streamed
+    Report(LiveCheckReport),
+    /// The report with the samples grouped by service
+    ServiceReport(LiveCheckServiceReport),
+    /// The statistics alone, when the samples were streamed without batch advice
+    Statistics(LiveCheckStatistics),
+}
+
+impl RunResult {
+    fn statistics_mut(&mut self) -> &mut LiveCheckStatistics {
+        match self {
+            RunResult::Report(report) => &mut report.statistics,
+            RunResult::ServiceReport(report) => &mut report.statistics,
+            RunResult::Statistics(stats) => stats,
+        }
+    }
+}
+
+/// Finish a live check run: write the advice baseline and the Prometheus statistics,
+/// run the post-process hooks and output the result.
+/// Returns true when the run fails.
+fn finish_run(
+    args: &RegistryLiveCheckArgs,
+    live_checker: &LiveChecker,
+    mut result: RunResult,
+    report_output: &ReportOutput,
+    output: &Path,
+    output_directive: &OutputDirective,
+) -> Result<bool, DiagnosticMessages> {
+    if let Some(path) = &args.write_advice_baseline {
+        write_advice_baseline(path, live_checker)?;
+    }
+    if let Some(path) = &args.prometheus_output {
+        write_prometheus_output(path, result.statistics_mut())?;
+    }
+    let failed = is_failed_run(live_checker, result.statistics_mut());
+    match &mut result {
+        RunResult::Report(report) => live_checker.post_process(report),
+        RunResult::ServiceReport(report) => live_checker.post_process_service_report(report),
+        RunResult::Statistics(_) => {}
+    }
+    match report_output {
+        ReportOutput::Template(engine) => {
+            let generated = match &result {
+                RunResult::Report(report) => engine.generate(report, output, output_directive),
+                RunResult::ServiceReport(report) => {
+                    engine.generate(report, output, output_directive)
+                }
+                RunResult::Statistics(stats) => engine.generate(stats, output, output_directive),
+            };
+            generated.map_err(|e| {
+                DiagnosticMessages::from(Error::OutputError {
+                    error: e.to_string(),
+                })
+            })?;
+        }
+        ReportOutput::Advice(format) => {
+            let advice = match &result {
+                RunResult::Report(report) => report.all_advice(),
+                RunResult::ServiceReport(report) => report.all_advice(),
+                RunResult::Statistics(_) => Vec::new(),
+            };
+            format.write(&advice, output, output_directive)?;
+        }
+    }
+    Ok(failed)
+}
+
+/// Resolve an additional registry used by diff-aware advisors.
+fn load_diff_registry(
+    registry_name: &str,
+    registry_path: &VirtualDirectoryPath,
+    follow_symlinks: bool,
+    include_unreferenced: bool,
+    diag_msgs: &mut DiagnosticMessages,
+) -> Result<ResolvedRegistry, DiagnosticMessages> {
+    let registry_repo = RegistryRepo::try_new(registry_name, registry_path)?;
+    let registry = ResolvedRegistry::try_from_registry_repo(
+        &registry_repo,
+        follow_symlinks,
+        include_unreferenced,
+    )
+    .capture_non_fatal_errors(diag_msgs)?;
+    log_success(format!(
+        "`{registry_name}` semconv registry `{}` resolved",
+        registry_repo.registry_path_repr()
+    ));
+    Ok(registry)
 }
 
 /// Perform a live check on sample data by comparing it to a semantic convention registry.
@@ -168,7 +629,26 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
 
     let mut diag_msgs = DiagnosticMessages::empty();
 
-    let (registry, _) = prepare_main_registry(&args.registry, &args.policy, &mut diag_msgs)?;
+    let (registry, _, manifest) =
+        prepare_main_registry_with_manifest(&args.registry, &args.policy, &mut diag_msgs)?;
+
+    let (registry, registry_origins) = if let Some(extension_registry) = &args.extension_registry {
+        info!("Resolving extension registry `{extension_registry}`");
+        let extension = load_diff_registry(
+            "extension",
+            extension_registry,
+            args.registry.follow_symlinks,
+            args.registry.include_unreferenced,
+            &mut diag_msgs,
+        )?;
+        let (registry, registry_origins) = merge_registries(vec![
+            (extension_registry.to_string(), extension),
+            (args.registry.registry.to_string(), registry),
+        ]);
+        (registry, Some(registry_origins))
+    } else {
+        (registry, None)
+    };
 
     info!(
         "Performing live check with registry `{}`",
@@ -176,37 +656,176 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     );
 
     // Create the live checker with advisors
-    let mut live_checker = LiveChecker::new(registry, default_advisors());
+    let misspelling_advisor = args
+        .suggest_misspellings
+        .then(|| MisspellingAdvisor::new(&registry).with_top_k(args.misspelling_suggestions));
+    let misplaced_attribute_advisor = args
+        .report_misplaced_attributes
+        .then(|| MisplacedAttributeAdvisor::new(&registry));
+    let mut builder = LiveChecker::builder(registry);
+    if let Some(misspelling_advisor) = misspelling_advisor {
+        builder = builder.with_advisor(Box::new(misspelling_advisor));
+    }
+    if let Some(misplaced_attribute_advisor) = misplaced_attribute_advisor {
+        builder = builder.with_advisor(Box::new(misplaced_attribute_advisor));
+    }
+    if let Some(manifest) = &manifest {
+        builder = builder.registry_meta(RegistryMeta::from(manifest));
+    }
+    if let Some(registry_origins) = registry_origins {
+        builder = builder.registry_origins(registry_origins);
+    }
+    if let Some(baseline_registry) = &args.baseline_registry {
+        info!("Resolving baseline registry `{baseline_registry}`");
+        let baseline_registry = load_diff_registry(
+            "baseline",
+            baseline_registry,
+            args.registry.follow_symlinks,
+            args.registry.include_unreferenced,
+            &mut diag_msgs,
+        )?;
+        builder = builder
+            .with_advisor(Box::new(TypeChangeAdvisor::new(&baseline_registry)))
+            .baseline_registry(&baseline_registry);
+    }
+    if let Some(newer_registry) = &args.newer_registry {
+        info!("Resolving newer registry `{newer_registry}`");
+        let newer_registry = load_diff_registry(
+            "newer",
+            newer_registry,
+            args.registry.follow_symlinks,
+            args.registry.include_unreferenced,
+            &mut diag_msgs,
+        )?;
+        builder = builder.with_advisor(Box::new(NewerRegistryAdvisor::new(&newer_registry)));
+    }
+    if args.report_unknown_attributes {
+        builder = builder.with_advisor(Box::new(UnknownAttributeAdvisor::new()));
+    }
+    if args.report_any_type {
+        builder = builder.with_advisor(Box::new(AnyTypeAdvisor));
+    }
+    if let Some(path) = &args.advice_baseline {
+        builder = builder.advice_baseline(AdviceBaseline::load(path)?);
+    }
+    builder = builder.record_advice_baseline(args.write_advice_baseline.is_some());
+    if args.compare_examples {
+        builder = builder.with_advisor(Box::new(ExampleShapeAdvisor));
+    }
+    if args.check_unit_conventions {
+        builder = builder.with_advisor(Box::new(UnitInstrumentConsistencyAdvisor::new()));
+    }
+    if args.check_delta_cumulative {
+        builder = builder.with_advisor(Box::new(DeltaCumulativeConsistencyAdvisor::new()));
+    }
+    if args.check_trace_context {
+        builder = builder.with_advisor(Box::new(TraceContextAdvisor));
+    }
+    if args.check_duplicate_series {
+        builder = builder.with_advisor(Box::new(DuplicateTimeSeriesAdvisor::new()));
+    }
+    if args.numeric_coercion != NumericCoercion::Strict {
+        builder = builder.numeric_coercion(args.numeric_coercion);
+    }
+    if !args.experimental_opt_in.is_empty() {
+        builder = builder.experimental_opt_in(args.experimental_opt_in.iter().cloned().collect());
+    }
+    let mut filter = NameFilter::new();
+    for pattern in &args.include {
+        filter = filter.include(pattern)?;
+    }
+    for pattern in &args.exclude {
+        filter = filter.exclude(pattern)?;
+    }
+    builder = builder.filter(filter);
+    builder = builder
+        .dedup(args.dedup)
+        .sort_advice(args.sort_advice)
+        .fail_fast(args.fail_fast)
+        .advisor_timing(args.advisor_timings);
+    if let Some(capacity) = args.advice_cache {
+        builder = builder.advice_cache(capacity);
+    }
+    if !args.budget.is_empty() {
+        let budget = args
+            .budget
+            .iter()
+            .fold(SeverityBudget::new(), |budget, (level, max)| {
+                budget.with(level.clone(), *max)
+            });
+        builder = builder.budget(budget);
+    }
+    if let Some(every) = args.progress {
+        builder = builder.progress(
+            every,
+            Box::new(|processed, violations| {
+                info!("Checked {processed} samples, {violations} violations so far");
+            }),
+        );
+    }
+    let advice_level_override = args.advice_level.iter().fold(
+        AdviceLevelOverride::new(),
+        |levels, (advice_type, level)| levels.with(advice_type, level.clone()),
+    );
+    let message_catalog = args
+        .advice_message
+        .iter()
+        .fold(MessageCatalog::new(), |catalog, (advice_type, template)| {
+            catalog.with(advice_type, template)
+        });
+    builder = builder.message_catalog(message_catalog);
+    let mut live_checker = builder.advice_level_override(advice_level_override).build();
 
     let rego_advisor = RegoAdvisor::new(
         &live_checker,
         &args.advice_policies,
         &args.advice_preprocessor,
-    )?;
+    )?
+    .with_explain(args.explain_advice_policies)
+    .with_batch(args.batch_advice_policies)
+    .with_allowed_advice_types(args.allowed_advice_type.iter().cloned());
     live_checker.add_advisor(Box::new(rego_advisor));
 
-    // Prepare the template engine
-    let loader = EmbeddedFileLoader::try_new(
-        &DEFAULT_LIVE_CHECK_TEMPLATES,
-        args.templates.clone(),
-        &args.format,
-    )
-    .map_err(|e| {
-        DiagnosticMessages::from(Error::OutputError {
-            error: format!(
-                "Failed to create the embedded file loader for the live check templates: {e}"
-            ),
-        })
-    })?;
-    let config = WeaverConfig::try_from_loader(&loader).map_err(|e| {
-        DiagnosticMessages::from(Error::OutputError {
-            error: format!("Failed to load `defaults/live_check_templates/weaver.yaml`: {e}"),
-        })
-    })?;
-    let engine = TemplateEngine::try_new(config, loader, Params::default())?;
+    // Prepare the template engine, unless the advice are rendered in an advice format
+    let report_output = if let Some(format) = AdviceFormat::of(&args.format) {
+        ReportOutput::Advice(format)
+    } else {
+        let loader = EmbeddedFileLoader::try_new(
+            &DEFAULT_LIVE_CHECK_TEMPLATES,
+            args.templates.clone(),
+            &args.format,
+        )
+        .map_err(|e| {
+            DiagnosticMessages::from(Error::OutputError {
+                error: format!(
+                    "Failed to create the embedded file loader for the live check templates: {e}"
+                ),
+            })
+        })?;
+        let config = WeaverConfig::try_from_loader(&loader).map_err(|e| {
+            DiagnosticMessages::from(Error::OutputError {
+                error: format!("Failed to load `defaults/live_check_templates/weaver.yaml`: {e}"),
+            })
+        })?;
+        ReportOutput::Template(Box::new(TemplateEngine::try_new(
+            config,
+            loader,
+            Params::default(),
+        )?))
+    };
 
     // Prepare the ingester
+    // The JSON lines ingester is kept to report the malformed lines once the input is consumed
+    let mut json_lines_ingester = None;
     let ingester = match (&args.input_source, &args.input_format) {
+        (InputSource::File(path), InputFormat::JsonLines) => json_lines_ingester
+            .insert(JsonLinesIngester::from_file(path))
+            .ingest()?,
+
+        (InputSource::Stdin, InputFormat::JsonLines) => json_lines_ingester
+            .insert(JsonLinesIngester::from_stdin())
+            .ingest()?,
+
         (InputSource::File(path), InputFormat::Text) => TextFileIngester::new(path).ingest()?,
 
         (InputSource::Stdin, InputFormat::Text) => TextStdinIngester::new().ingest()?,
@@ -215,10 +834,19 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
 
         (InputSource::Stdin, InputFormat::Json) => JsonStdinIngester::new().ingest()?,
 
+        (InputSource::File(path), InputFormat::OtlpProtobuf(signal)) => {
+            OtlpProtobufIngester::from_file(path, *signal).ingest()?
+        }
+
+        (InputSource::Stdin, InputFormat::OtlpProtobuf(signal)) => {
+            OtlpProtobufIngester::from_stdin(*signal).ingest()?
+        }
+
         (InputSource::Otlp, _) => (OtlpIngester {
             otlp_grpc_address: args.otlp_grpc_address.clone(),
             otlp_grpc_port: args.otlp_grpc_port,
             admin_port: args.admin_port,
+            paused_buffer_size: args.paused_buffer_size,
             inactivity_timeout: args.inactivity_timeout,
         })
         .ingest()?,
@@ -229,56 +857,87 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     let report_mode = if let OutputDirective::File = output_directive {
         // File output forces report mode
         true
+    } else if let ReportOutput::Advice(_) = report_output {
+        // The advice formats render the advice of all the samples at once
+        true
     } else {
         // This flag is not set by default. The user can set it to disable streaming output
         // and force report mode.
         args.no_stream
     };
 
-    let mut stats = LiveCheckStatistics::new(&live_checker.registry);
-    let mut samples = Vec::new();
-    for mut sample in ingester {
-        sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone())?;
-        if report_mode {
-            samples.push(sample);
+    let result = if args.group_by_service {
+        let mut groups = ServiceGroups::new();
+        for (index, mut sample) in ingester.enumerate() {
+            let stats = groups.statistics_for(&sample, &live_checker.registry);
+            let stop = live_checker.check_sample(index, &mut sample, stats)?;
+            groups.add_sample(sample, &live_checker.registry);
+            if stop {
+                log_fail_fast(index);
+                break;
+            }
+        }
+        let batch_advice = live_checker.finalize()?;
+        RunResult::ServiceReport(groups.into_report(batch_advice, &live_checker.registry))
+    } else {
+        let mut stats = LiveCheckStatistics::new(&live_checker.registry);
+        let mut samples = Vec::new();
+        for (index, mut sample) in ingester.enumerate() {
+            let stop = live_checker.check_sample(index, &mut sample, &mut stats)?;
+            match &report_output {
+                ReportOutput::Template(engine) if !report_mode => engine
+                    .generate(&sample, output.as_path(), &output_directive)
+                    .map_err(|e| {
+                        DiagnosticMessages::from(Error::OutputError {
+                            error: e.to_string(),
+                        })
+                    })?,
+                _ => samples.push(sample),
+            }
+            if stop {
+                log_fail_fast(index);
+                break;
+            }
+        }
+        let batch_advice = live_checker.finalize()?;
+        stats.add_batch_advice(&batch_advice);
+        stats.finalize();
+        if report_mode || !batch_advice.is_empty() {
+            // Package into a report, with only the batch advice along with the stats
+            // when streaming
+            RunResult::Report(LiveCheckReport {
+                statistics: stats,
+                samples,
+                batch_advice,
+            })
         } else {
-            engine
-                .generate(&sample, output.as_path(), &output_directive)
-                .map_err(|e| {
-                    DiagnosticMessages::from(Error::OutputError {
-                        error: e.to_string(),
-                    })
-                })?;
+            RunResult::Statistics(stats)
         }
-    }
-    stats.finalize();
-    // Set the exit_code to a non-zero code if there are any violations
-    if stats.has_violations() {
+    };
+    // Set the exit_code to a non-zero code if there are any violations, or
+    // over the budget
+    if finish_run(
+        args,
+        &live_checker,
+        result,
+        &report_output,
+        output.as_path(),
+        &output_directive,
+    )? {
         exit_code = 1;
     }
 
-    if report_mode {
-        // Package into a report
-        let report = LiveCheckReport {
-            statistics: stats,
-            samples,
-        };
-        engine
-            .generate(&report, output.as_path(), &output_directive)
-            .map_err(|e| {
-                DiagnosticMessages::from(Error::OutputError {
-                    error: e.to_string(),
-                })
-            })?;
-    } else {
-        // Output the stats
-        engine
-            .generate(&stats, output.as_path(), &output_directive)
-            .map_err(|e| {
-                DiagnosticMessages::from(Error::OutputError {
-                    error: e.to_string(),
-                })
-            })?;
+    if let Some(timings) = live_checker.advisor_timings() {
+        for (advisor_id, elapsed) in timings.iter() {
+            info!("Advisor `{advisor_id}` took {:.3}s", elapsed.as_secs_f64());
+        }
+    }
+
+    if let Some(json_lines_ingester) = &json_lines_ingester {
+        // Malformed lines are reported once all the other lines have been checked
+        diag_msgs.extend(DiagnosticMessages::from_errors(
+            json_lines_ingester.errors(),
+        ));
     }
 
     log_success(format!(
@@ -129,7 +129,7 @@ pub enum RegistrySubCommand {
     ///
     /// Includes: Flexible input ingestion, configurable assessment, and template-based output.
     #[clap(verbatim_doc_comment)]
-    LiveCheck(RegistryLiveCheckArgs),
+    LiveCheck(Box<RegistryLiveCheckArgs>),
 }
 
 /// Set of parameters used to specify a semantic convention registry.
@@ -1,52 +1,7 @@
 // This file is @generated by prost-build.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ExportTraceServiceRequest {
-    /// An array of ResourceSpans.
-    /// For data coming from a single resource this array will typically contain one
-    /// element. Intermediary nodes (such as OpenTelemetry Collector) that receive
-    /// data from multiple origins typically batch the data before forwarding further and
-    /// in that case this array will contain multiple elements.
-    #[prost(message, repeated, tag = "1")]
-    pub resource_spans: ::prost::alloc::vec::Vec<super::super::super::trace::v1::ResourceSpans>,
-}
-#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
-pub struct ExportTraceServiceResponse {
-    /// The details of a partially successful export request.
-    ///
-    /// If the request is only partially accepted
-    /// (i.e. when the server accepts only parts of the data and rejects the rest)
-    /// the server MUST initialize the `partial_success` field and MUST
-    /// set the `rejected_<signal>` with the number of items it rejected.
-    ///
-    /// Servers MAY also make use of the `partial_success` field to convey
-    /// warnings/suggestions to senders even when the request was fully accepted.
-    /// In such cases, the `rejected_<signal>` MUST have a value of `0` and
-    /// the `error_message` MUST be non-empty.
-    ///
-    /// A `partial_success` message with an empty value (rejected\_<signal> = 0 and
-    /// `error_message` = "") is equivalent to it not being set/present. Senders
-    /// SHOULD interpret it the same way as in the full success case.
-    #[prost(message, optional, tag = "1")]
-    pub partial_success: ::core::option::Option<ExportTracePartialSuccess>,
-}
-#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
-pub struct ExportTracePartialSuccess {
-    /// The number of rejected spans.
-    ///
-    /// A `rejected_<signal>` field holding a `0` value indicates that the
-    /// request was fully accepted.
-    #[prost(int64, tag = "1")]
-    pub rejected_spans: i64,
-    /// A developer-facing human-readable message in English. It should be used
-    /// either to explain why the server rejected parts of the data during a partial
-    /// success or to convey warnings/suggestions during a full success. The message
-    /// should offer guidance on how users can address such issues.
-    ///
-    /// error_message is an optional field. An error_message with an empty value
-    /// is equivalent to it not being set.
-    #[prost(string, tag = "2")]
-    pub error_message: ::prost::alloc::string::String,
-}
+pub use weaver_live_check::otlp_protobuf::proto::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
 /// Generated client implementations.
 pub mod trace_service_client {
     #![allow(
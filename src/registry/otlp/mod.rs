@@ -2,7 +2,6 @@
 
 //! A basic OTLP receiver integrated into Weaver.
 
-pub mod conversion;
 pub mod otlp_ingester;
 
 use grpc_stubs::proto::collector::logs::v1::logs_service_server::{LogsService, LogsServiceServer};
@@ -19,8 +18,10 @@ use grpc_stubs::proto::collector::trace::v1::trace_service_server::{
 use grpc_stubs::proto::collector::trace::v1::{
     ExportTraceServiceRequest, ExportTraceServiceResponse,
 };
+use log::warn;
 use miette::Diagnostic;
 use serde::Serialize;
+use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 use std::net::{AddrParseError, SocketAddr};
 use std::time::{Duration, Instant};
@@ -81,34 +82,8 @@ pub mod grpc_stubs {
             pub mod v1;
         }
 
-        #[path = ""]
-        pub mod metrics {
-            #[allow(rustdoc::invalid_html_tags)]
-            #[allow(dead_code)]
-            #[path = "opentelemetry.proto.metrics.v1.rs"]
-            pub mod v1;
-        }
-
-        #[path = ""]
-        pub mod trace {
-            #[allow(rustdoc::invalid_html_tags)]
-            #[allow(dead_code)]
-            #[path = "opentelemetry.proto.trace.v1.rs"]
-            pub mod v1;
-        }
-
-        #[path = ""]
-        pub mod common {
-            #[allow(clippy::enum_variant_names)]
-            #[path = "opentelemetry.proto.common.v1.rs"]
-            pub mod v1;
-        }
-
-        #[path = ""]
-        pub mod resource {
-            #[path = "opentelemetry.proto.resource.v1.rs"]
-            pub mod v1;
-        }
+        // The messages shared with the protobuf decoding of `weaver_live_check`.
+        pub use weaver_live_check::otlp_protobuf::proto::{common, resource};
     }
 }
 
@@ -140,6 +115,14 @@ pub enum OtlpRequest {
 
     Error(Error),
     Stop(StopSignal),
+
+    /// HTTP POST to /pause. Received requests are buffered until resumed.
+    /// The policies are not reloaded on resume, the live check keeps the Rego
+    /// advisor it was started with.
+    Pause,
+    /// HTTP POST to /resume. Buffered requests are delivered in order and the
+    /// inactivity timeout restarts.
+    Resume,
 }
 
 /// Enum to represent stop signals.
@@ -171,11 +154,16 @@ impl Display for StopSignal {
 ///
 /// This function guarantees that the OTLP server is started and ready when the
 /// result is Ok(iterator).
+///
+/// While the receiver is paused (HTTP POST to /pause), up to `paused_buffer_size`
+/// OTLP requests are buffered and delivered once resumed (HTTP POST to /resume).
+/// Requests received while the buffer is full are dropped.
 pub fn listen_otlp_requests(
     grpc_addr: &str,
     grpc_port: u16,
     admin_port: u16,
     inactivity_timeout: Duration,
+    paused_buffer_size: usize,
 ) -> Result<impl Iterator<Item = OtlpRequest>, Error> {
     let addr: SocketAddr =
         format!("{grpc_addr}:{grpc_port}")
@@ -197,6 +185,8 @@ pub fn listen_otlp_requests(
     let stop_tx = tx.clone();
     // Create a watch channel for the last activity timestamp
     let (activity_tx, activity_rx) = watch::channel(Instant::now());
+    // Create a watch channel for the pause state, the inactivity monitor is suspended while paused
+    let (paused_tx, paused_rx) = watch::channel(false);
     let logs_service = LogsServiceImpl {
         tx: tx.clone(),
         activity_tx: activity_tx.clone(),
@@ -225,10 +215,18 @@ pub fn listen_otlp_requests(
 
                 // Spawn tasks to handle different stop signals
                 spawn_stop_signal_handlers(stop_tx.clone(), &mut tasks);
-                spawn_http_stop_handler(stop_tx.clone(), admin_port, &mut tasks).await;
+                spawn_http_admin_handler(
+                    stop_tx.clone(),
+                    paused_tx,
+                    activity_tx,
+                    admin_port,
+                    &mut tasks,
+                )
+                .await;
                 spawn_inactivity_monitor(
                     stop_tx.clone(),
                     activity_rx,
+                    paused_rx,
                     inactivity_timeout,
                     &mut tasks,
                 );
@@ -266,7 +264,7 @@ pub fn listen_otlp_requests(
         error: format!("OTLP server dropped before signaling readiness (error: {e})"),
     })?;
 
-    Ok(SyncReceiver { receiver: rx })
+    Ok(PausableReceiver::new(rx, paused_buffer_size))
 }
 
 /// Spawn tasks to handle CTRL+C and SIGHUP signals.
@@ -304,13 +302,17 @@ fn spawn_stop_signal_handlers(stop_tx: mpsc::Sender<OtlpRequest>, tasks: &mut Jo
     }
 }
 
-/// Spawn a minimal HTTP server that handles the /stop endpoint
+/// Spawn a minimal HTTP server that handles the /stop, /pause, and /resume endpoints.
+/// A resume counts as activity so the inactivity timeout does not stop the receiver
+/// right after a long pause.
 ///
 /// Note: All the tasks created in this function are recorded into a
 /// JoinSet. `JoinSet::spawn` returns a `AbortHandle` that we can
 /// ignore as we don't need to abort these tasks.
-async fn spawn_http_stop_handler(
+async fn spawn_http_admin_handler(
     stop_tx: mpsc::Sender<OtlpRequest>,
+    paused_tx: watch::Sender<bool>,
+    activity_tx: watch::Sender<Instant>,
     port: u16,
     tasks: &mut JoinSet<()>,
 ) {
@@ -333,19 +335,30 @@ async fn spawn_http_stop_handler(
                                 let lines: Vec<&str> = request.lines().collect();
                                 if let Some(first_line) = lines.first() {
                                     let parts: Vec<&str> = first_line.split_whitespace().collect();
-                                    if parts.len() >= 2 && parts[0] == "POST" && parts[1] == "/stop"
-                                    {
-                                        // Send stop signal
-                                        let _ = stop_tx
-                                            .send(OtlpRequest::Stop(StopSignal::AdminStop))
-                                            .await
-                                            .ok();
+                                    let admin_request = match parts.as_slice() {
+                                        ["POST", "/stop", ..] => {
+                                            stop_signal_received = true;
+                                            Some(OtlpRequest::Stop(StopSignal::AdminStop))
+                                        }
+                                        ["POST", "/pause", ..] => {
+                                            let _ = paused_tx.send(true);
+                                            Some(OtlpRequest::Pause)
+                                        }
+                                        ["POST", "/resume", ..] => {
+                                            let _ = activity_tx.send(Instant::now());
+                                            let _ = paused_tx.send(false);
+                                            Some(OtlpRequest::Resume)
+                                        }
+                                        _ => None,
+                                    };
+                                    if let Some(admin_request) = admin_request {
+                                        // Forward the admin request
+                                        let _ = stop_tx.send(admin_request).await.ok();
 
                                         // Send HTTP 200 OK response
                                         let response =
                                             "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK";
                                         let _ = socket.write_all(response.as_bytes()).await.ok();
-                                        stop_signal_received = true;
                                     } else {
                                         // Send HTTP 404 Not Found for any other request
                                         let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\nNot Found";
@@ -375,7 +388,8 @@ async fn spawn_http_stop_handler(
     }
 }
 
-/// Spawn a task that monitors for inactivity and triggers shutdown if timeout is reached
+/// Spawn a task that monitors for inactivity and triggers shutdown if timeout is reached.
+/// The inactivity timeout does not apply while the receiver is paused.
 ///
 /// Note: All the tasks created in this function are recorded into a
 /// JoinSet. `JoinSet::spawn` returns a `AbortHandle` that we can
@@ -383,6 +397,7 @@ async fn spawn_http_stop_handler(
 fn spawn_inactivity_monitor(
     stop_tx: mpsc::Sender<OtlpRequest>,
     activity_rx: watch::Receiver<Instant>,
+    paused_rx: watch::Receiver<bool>,
     timeout: Duration,
     tasks: &mut JoinSet<()>,
 ) {
@@ -393,7 +408,7 @@ fn spawn_inactivity_monitor(
 
             // Check if we've exceeded the inactivity timeout
             let last_activity = *activity_rx.borrow();
-            if last_activity.elapsed() >= timeout {
+            if last_activity.elapsed() >= timeout && !*paused_rx.borrow() {
                 let _ = stop_tx
                     .send(OtlpRequest::Stop(StopSignal::Inactivity))
                     .await
@@ -410,15 +425,73 @@ fn spawn_inactivity_monitor(
 }
 
 // Synchronous iterator wrapping a Tokio mpsc::Receiver.
-pub struct SyncReceiver<T> {
-    receiver: mpsc::Receiver<T>,
+//
+// Pause and resume requests are handled here: while paused, OTLP requests are
+// buffered (up to `buffer_size`) instead of being returned to the caller.
+pub struct PausableReceiver {
+    receiver: mpsc::Receiver<OtlpRequest>,
+    paused: bool,
+    buffer: VecDeque<OtlpRequest>,
+    buffer_size: usize,
+    dropped: usize,
+}
+
+impl PausableReceiver {
+    fn new(receiver: mpsc::Receiver<OtlpRequest>, buffer_size: usize) -> Self {
+        Self {
+            receiver,
+            paused: false,
+            buffer: VecDeque::new(),
+            buffer_size,
+            dropped: 0,
+        }
+    }
+
+    fn resume(&mut self) {
+        self.paused = false;
+        if self.dropped > 0 {
+            warn!(
+                "{} OTLP requests were dropped while the receiver was paused (buffer size: {}).",
+                self.dropped, self.buffer_size
+            );
+            self.dropped = 0;
+        }
+    }
 }
 
-impl<T> Iterator for SyncReceiver<T> {
-    type Item = T;
+impl Iterator for PausableReceiver {
+    type Item = OtlpRequest;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.receiver.blocking_recv()
+        loop {
+            if !self.paused {
+                if let Some(request) = self.buffer.pop_front() {
+                    return Some(request);
+                }
+            }
+            let Some(request) = self.receiver.blocking_recv() else {
+                // The channel is closed, deliver what has been buffered.
+                self.resume();
+                return self.buffer.pop_front();
+            };
+            match request {
+                OtlpRequest::Pause => self.paused = true,
+                OtlpRequest::Resume => self.resume(),
+                OtlpRequest::Stop(_) | OtlpRequest::Error(_) if self.paused => {
+                    // Deliver the buffered requests before stopping.
+                    self.resume();
+                    self.buffer.push_back(request);
+                }
+                request if self.paused => {
+                    if self.buffer.len() < self.buffer_size {
+                        self.buffer.push_back(request);
+                    } else {
+                        self.dropped += 1;
+                    }
+                }
+                request => return Some(request),
+            }
+        }
     }
 }
 
@@ -515,7 +588,8 @@ mod tests {
         let inactivity_timeout = Duration::from_millis(500);
 
         let mut receiver =
-            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout).unwrap();
+            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout, 100)
+                .unwrap();
         let grpc_endpoint = format!("http://127.0.0.1:{grpc_port}");
         let expected_metrics_count = 3;
         let expected_logs_count = 4;
@@ -608,7 +682,8 @@ mod tests {
         let inactivity_timeout = Duration::from_secs(5);
 
         let mut receiver =
-            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout).unwrap();
+            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout, 100)
+                .unwrap();
 
         // Give the server a little time to finish binding the port.
         thread::sleep(Duration::from_millis(200));
@@ -631,4 +706,102 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_http_pause_resume_endpoints() {
+        let grpc_port = portpicker::pick_unused_port().expect("No free ports");
+        let admin_port = portpicker::pick_unused_port().expect("No free ports");
+        let inactivity_timeout = Duration::from_secs(5);
+        let paused_buffer_size = 2;
+
+        let mut receiver = listen_otlp_requests(
+            "127.0.0.1",
+            grpc_port,
+            admin_port,
+            inactivity_timeout,
+            paused_buffer_size,
+        )
+        .unwrap();
+
+        // Give the server a little time to finish binding the port.
+        thread::sleep(Duration::from_millis(200));
+
+        let url = format!("http://127.0.0.1:{admin_port}/pause");
+        let response = ureq::post(&url).call().expect("HTTP POST to /pause failed");
+        assert_eq!(response.status(), 200);
+
+        // Send 3 metrics while paused, only 2 of them can be buffered.
+        let grpc_endpoint = format!("http://127.0.0.1:{grpc_port}");
+        let _ = thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async {
+                    let mut metrics_client =
+                        MetricsServiceClient::connect(grpc_endpoint).await.unwrap();
+                    for _ in 0..3 {
+                        let _ = metrics_client
+                            .export(ExportMetricsServiceRequest::default())
+                            .await;
+                    }
+                });
+        })
+        .join();
+
+        let url = format!("http://127.0.0.1:{admin_port}/resume");
+        let response = ureq::post(&url)
+            .call()
+            .expect("HTTP POST to /resume failed");
+        assert_eq!(response.status(), 200);
+
+        let url = format!("http://127.0.0.1:{admin_port}/stop");
+        let response = ureq::post(&url).call().expect("HTTP POST to /stop failed");
+        assert_eq!(response.status(), 200);
+
+        let mut metrics_count = 0;
+        loop {
+            match receiver.next() {
+                Some(OtlpRequest::Metrics(_)) => metrics_count += 1,
+                Some(OtlpRequest::Stop(StopSignal::AdminStop)) => break,
+                other => panic!("Unexpected request: {other:?}"),
+            }
+        }
+        assert_eq!(metrics_count, paused_buffer_size);
+    }
+
+    #[test]
+    fn test_resume_restarts_inactivity_timeout() {
+        let grpc_port = portpicker::pick_unused_port().expect("No free ports");
+        let admin_port = portpicker::pick_unused_port().expect("No free ports");
+        let inactivity_timeout = Duration::from_secs(1);
+
+        let mut receiver =
+            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout, 100)
+                .unwrap();
+
+        // Give the server a little time to finish binding the port.
+        thread::sleep(Duration::from_millis(200));
+
+        let url = format!("http://127.0.0.1:{admin_port}/pause");
+        let response = ureq::post(&url).call().expect("HTTP POST to /pause failed");
+        assert_eq!(response.status(), 200);
+
+        // Stay paused longer than the inactivity timeout.
+        thread::sleep(Duration::from_millis(1300));
+
+        let url = format!("http://127.0.0.1:{admin_port}/resume");
+        let response = ureq::post(&url)
+            .call()
+            .expect("HTTP POST to /resume failed");
+        assert_eq!(response.status(), 200);
+        let resumed_at = Instant::now();
+
+        // The receiver is not stopped right after the resume.
+        match receiver.next() {
+            Some(OtlpRequest::Stop(StopSignal::Inactivity)) => {}
+            other => panic!("Expected OtlpRequest::Stop, got {other:?}"),
+        }
+        assert!(resumed_at.elapsed() >= inactivity_timeout);
+    }
 }
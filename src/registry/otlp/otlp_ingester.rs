@@ -5,19 +5,12 @@ use std::time::Duration;
 
 use log::info;
 use weaver_common::log_info;
-use weaver_live_check::{
-    sample_resource::SampleResource,
-    sample_span::{SampleSpan, SampleSpanEvent, SampleSpanLink},
-    Error, Ingester, Sample,
+use weaver_live_check::otlp_protobuf::conversion::{
+    otlp_metrics_request_to_samples, otlp_trace_request_to_samples,
 };
+use weaver_live_check::{Error, Ingester, Sample};
 
-use super::{
-    conversion::{
-        otlp_metric_to_sample, sample_attribute_from_key_value, span_kind_from_otlp_kind,
-        status_from_otlp_status,
-    },
-    listen_otlp_requests, OtlpRequest,
-};
+use super::{listen_otlp_requests, OtlpRequest};
 
 /// An ingester for OTLP data
 pub struct OtlpIngester {
@@ -29,6 +22,8 @@ pub struct OtlpIngester {
     pub admin_port: u16,
     /// The inactivity timeout
     pub inactivity_timeout: u64,
+    /// The maximum number of OTLP requests buffered while the receiver is paused
+    pub paused_buffer_size: usize,
 }
 
 /// Iterator for OTLP samples
@@ -52,112 +47,17 @@ impl OtlpIterator {
                 Some(0)
             }
             OtlpRequest::Metrics(metrics) => {
-                for resource_metric in metrics.resource_metrics {
-                    if let Some(resource) = resource_metric.resource {
-                        let mut sample_resource = SampleResource {
-                            attributes: Vec::new(),
-                            live_check_result: None,
-                        };
-                        for attribute in resource.attributes {
-                            sample_resource
-                                .attributes
-                                .push(sample_attribute_from_key_value(&attribute));
-                        }
-                        self.buffer.push(Sample::Resource(sample_resource));
-                    }
-
-                    for scope_metric in resource_metric.scope_metrics {
-                        if let Some(scope) = scope_metric.scope {
-                            // TODO SampleInstrumentationScope?
-                            for attribute in scope.attributes {
-                                self.buffer.push(Sample::Attribute(
-                                    sample_attribute_from_key_value(&attribute),
-                                ));
-                            }
-                        }
-
-                        for metric in scope_metric.metrics {
-                            let sample_metric = Sample::Metric(otlp_metric_to_sample(metric));
-                            self.buffer.push(sample_metric);
-                        }
-                    }
-                }
+                self.buffer.extend(otlp_metrics_request_to_samples(metrics));
                 Some(self.buffer.len())
             }
             OtlpRequest::Traces(trace) => {
-                for resource_span in trace.resource_spans {
-                    if let Some(resource) = resource_span.resource {
-                        let mut sample_resource = SampleResource {
-                            attributes: Vec::new(),
-                            live_check_result: None,
-                        };
-                        for attribute in resource.attributes {
-                            sample_resource
-                                .attributes
-                                .push(sample_attribute_from_key_value(&attribute));
-                        }
-                        self.buffer.push(Sample::Resource(sample_resource));
-                    }
-
-                    for scope_span in resource_span.scope_spans {
-                        if let Some(scope) = scope_span.scope {
-                            // TODO SampleInstrumentationScope?
-                            for attribute in scope.attributes {
-                                self.buffer.push(Sample::Attribute(
-                                    sample_attribute_from_key_value(&attribute),
-                                ));
-                            }
-                        }
-
-                        for span in scope_span.spans {
-                            let span_kind = span.kind();
-                            let mut sample_span = SampleSpan {
-                                name: span.name,
-                                kind: span_kind_from_otlp_kind(span_kind),
-                                status: status_from_otlp_status(span.status),
-                                attributes: Vec::new(),
-                                span_events: Vec::new(),
-                                span_links: Vec::new(),
-                                live_check_result: None,
-                            };
-                            for attribute in span.attributes {
-                                sample_span
-                                    .attributes
-                                    .push(sample_attribute_from_key_value(&attribute));
-                            }
-                            for event in span.events {
-                                let mut sample_event = SampleSpanEvent {
-                                    name: event.name,
-                                    attributes: Vec::new(),
-                                    live_check_result: None,
-                                };
-                                for attribute in event.attributes {
-                                    sample_event
-                                        .attributes
-                                        .push(sample_attribute_from_key_value(&attribute));
-                                }
-                                sample_span.span_events.push(sample_event);
-                            }
-                            for link in span.links {
-                                let mut sample_link = SampleSpanLink {
-                                    attributes: Vec::new(),
-                                    live_check_result: None,
-                                };
-                                for attribute in link.attributes {
-                                    sample_link
-                                        .attributes
-                                        .push(sample_attribute_from_key_value(&attribute));
-                                }
-                                sample_span.span_links.push(sample_link);
-                            }
-                            self.buffer.push(Sample::Span(sample_span));
-                        }
-                    }
-                }
+                self.buffer.extend(otlp_trace_request_to_samples(trace));
                 Some(self.buffer.len())
             }
             OtlpRequest::Stop(_reason) => None,
             OtlpRequest::Error(_error) => None,
+            // Pause and resume are handled by the receiver itself
+            OtlpRequest::Pause | OtlpRequest::Resume => Some(0),
         }
     }
 }
@@ -186,6 +86,7 @@ impl Ingester for OtlpIngester {
             self.otlp_grpc_port,
             self.admin_port,
             Duration::from_secs(self.inactivity_timeout),
+            self.paused_buffer_size,
         )
         .map_err(|e| Error::IngestError {
             error: format!("Failed to listen to OTLP requests: {e}"),
@@ -205,6 +106,10 @@ impl Ingester for OtlpIngester {
             "The OTLP receiver will stop after {} seconds of inactivity.",
             self.inactivity_timeout
         );
+        info!(
+            "To pause or resume the checks, send a POST request to the /pause or /resume endpoint (up to {} OTLP requests are buffered while paused).",
+            self.paused_buffer_size
+        );
 
         Ok(Box::new(OtlpIterator::new(Box::new(otlp_requests))))
     }
@@ -16,6 +16,7 @@ use weaver_common::vdir::VirtualDirectory;
 use weaver_forge::registry::ResolvedRegistry;
 use weaver_resolved_schema::ResolvedTelemetrySchema;
 use weaver_resolver::SchemaResolver;
+use weaver_semconv::manifest::RegistryManifest;
 use weaver_semconv::registry::SemConvRegistry;
 use weaver_semconv::registry_repo::RegistryRepo;
 use weaver_semconv::semconv::SemConvSpec;
@@ -248,6 +249,18 @@ pub(crate) fn prepare_main_registry(
     policy_args: &PolicyArgs,
     diag_msgs: &mut DiagnosticMessages,
 ) -> Result<(ResolvedRegistry, Option<Engine>), DiagnosticMessages> {
+    let (registry, policy_engine, _) =
+        prepare_main_registry_with_manifest(registry_args, policy_args, diag_msgs)?;
+    Ok((registry, policy_engine))
+}
+
+/// Same as [`prepare_main_registry`], also returning the manifest of the main
+/// registry when it has one.
+pub(crate) fn prepare_main_registry_with_manifest(
+    registry_args: &RegistryArgs,
+    policy_args: &PolicyArgs,
+    diag_msgs: &mut DiagnosticMessages,
+) -> Result<(ResolvedRegistry, Option<Engine>, Option<RegistryManifest>), DiagnosticMessages> {
     let registry_path = &registry_args.registry;
 
     let main_registry_repo = RegistryRepo::try_new("main", registry_path)?;
@@ -342,5 +355,9 @@ pub(crate) fn prepare_main_registry(
         .capture_non_fatal_errors(diag_msgs)?;
     }
 
-    Ok((main_resolved_registry, policy_engine))
+    Ok((
+        main_resolved_registry,
+        policy_engine,
+        main_registry_repo.manifest().cloned(),
+    ))
 }